@@ -4,7 +4,10 @@ use rocket_okapi::settings::UrlObject;
 use rocket_okapi::{mount_endpoints_and_merged_docs, rapidoc::*, swagger_ui::*};
 
 mod error;
+// The `uri!` macros that Rocket exports for the routes are not used.
+#[allow(unused_imports)]
 mod message;
+#[allow(unused_imports)]
 mod post;
 
 pub type Result<T> = std::result::Result<rocket::serde::json::Json<T>, error::Error>;
@@ -1,4 +1,4 @@
-use rocket::form::{Form,FromForm};
+use rocket::form::{Form, FromForm};
 use rocket::{get, post, serde::json::Json};
 use rocket_okapi::okapi::schemars;
 use rocket_okapi::okapi::schemars::JsonSchema;
@@ -92,7 +92,7 @@ fn create_post_by_query(post: Post) -> Option<Json<Post>> {
 
 /// Returns the created post.
 #[openapi(tag = "Posts")]
-#[post("/post_by_form", format="multipart/form-data", data="<post>")]
+#[post("/post_by_form", format = "multipart/form-data", data = "<post>")]
 fn create_post_by_form(post: Form<Post>) -> Option<Json<Post>> {
     Some(Json(post.into_inner()))
 }
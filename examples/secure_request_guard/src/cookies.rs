@@ -13,6 +13,7 @@ use rocket_okapi::{
     request::{OpenApiFromRequest, RequestHeaderInput},
 };
 
+#[allow(dead_code)]
 pub struct CookieAuth(String);
 
 // Implement the actual checks for the authentication
@@ -22,16 +22,23 @@ use rocket_okapi::{openapi_get_routes, rapidoc::*, swagger_ui::*, OpenApiError};
 // - Just Cookies (for just 1 route/endpoint)
 // ---------------------------------------------------------------------------------
 
+// The `uri!` macros that Rocket exports for the routes are not used.
+#[allow(unused_imports)]
 mod no_auth;
 
+#[allow(unused_imports)]
 mod api_key;
 
+#[allow(unused_imports)]
 mod http_auth;
 
+#[allow(unused_imports)]
 mod oauth2;
 
+#[allow(unused_imports)]
 mod open_id;
 
+#[allow(unused_imports)]
 mod cookies;
 
 #[tokio::main]
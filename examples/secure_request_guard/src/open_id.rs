@@ -14,6 +14,7 @@ use rocket_okapi::{
     request::{OpenApiFromRequest, RequestHeaderInput},
 };
 
+#[allow(dead_code)]
 pub struct OpenId(String);
 
 // Implement the actual checks for the authentication
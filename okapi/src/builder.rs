@@ -0,0 +1,561 @@
+//! Builders to create OpenAPI documents by hand.
+//!
+//! The fields that are required by the OpenAPI specification are arguments of the `new` functions
+//! (or of the functions named after a parameter location), all other fields have a method to set
+//! them and default to empty.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use okapi::builder::{
+//!     Method, OpenApiBuilder, OperationBuilder, ParameterBuilder, ResponseBuilder,
+//! };
+//! use schemars::schema::{InstanceType, SchemaObject};
+//!
+//! let string = SchemaObject {
+//!     instance_type: Some(InstanceType::String.into()),
+//!     ..SchemaObject::default()
+//! };
+//! let user = ResponseBuilder::new("The user").json(string.clone()).build();
+//! let spec = OpenApiBuilder::new("Users API", "1.0.0")
+//!     .server("https://example.com/api")
+//!     .get(
+//!         "/users/{id}",
+//!         OperationBuilder::new("200", user)
+//!             .operation_id("get_user")
+//!             .parameter(ParameterBuilder::path("id", string).build())
+//!             .response("404", ResponseBuilder::new("User not found").build())
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! assert_eq!(spec.info.title, "Users API");
+//! assert!(spec.paths["/users/{id}"].get.is_some());
+//! assert!(spec.validate().is_ok());
+//!
+//! // Operations without a builder method for their HTTP method.
+//! let spec = OpenApiBuilder::from_spec(spec)
+//!     .operation(
+//!         "/users/{id}",
+//!         Method::Head,
+//!         OperationBuilder::new("200", ResponseBuilder::new("The user exists").build()).build(),
+//!     )
+//!     .build();
+//! assert!(spec.paths["/users/{id}"].head.is_some());
+//! ```
+
+use crate::openapi3::{
+    Components, Header, Info, MediaType, OpenApi, Operation, Parameter, ParameterValue, PathItem,
+    Ref, RefOr, RequestBody, Response, SecurityRequirement, SecurityScheme, Server, Tag,
+};
+use crate::Map;
+use schemars::schema::SchemaObject;
+use serde_json::Value;
+
+/// The HTTP method of an operation in a [`PathItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `PUT`
+    Put,
+    /// `POST`
+    Post,
+    /// `DELETE`
+    Delete,
+    /// `OPTIONS`
+    Options,
+    /// `HEAD`
+    Head,
+    /// `PATCH`
+    Patch,
+    /// `TRACE`
+    Trace,
+}
+
+/// Builder for an [`OpenApi`] document.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct OpenApiBuilder {
+    spec: OpenApi,
+}
+
+impl OpenApiBuilder {
+    /// Create a builder for a document with the given `info.title` and `info.version`.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        OpenApiBuilder {
+            spec: OpenApi {
+                openapi: OpenApi::default_version(),
+                info: Info {
+                    title: title.into(),
+                    version: version.into(),
+                    ..Info::default()
+                },
+                ..OpenApi::default()
+            },
+        }
+    }
+
+    /// Start from an existing document, to add or replace parts of it.
+    pub fn from_spec(spec: OpenApi) -> Self {
+        OpenApiBuilder { spec }
+    }
+
+    /// Set the version of the OpenAPI specification. The default is `3.0.0`.
+    pub fn openapi_version(mut self, version: impl Into<String>) -> Self {
+        self.spec.openapi = version.into();
+        self
+    }
+
+    /// Set the `info.description`.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.spec.info.description = Some(description.into());
+        self
+    }
+
+    /// Add a server with the given URL.
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.spec.servers.push(Server {
+            url: url.into(),
+            ..Server::default()
+        });
+        self
+    }
+
+    /// Add a tag with the given name.
+    pub fn tag(self, name: impl Into<String>) -> Self {
+        self.tag_object(Tag {
+            name: name.into(),
+            ..Tag::default()
+        })
+    }
+
+    /// Add a tag, to set other fields than the name.
+    pub fn tag_object(mut self, tag: Tag) -> Self {
+        self.spec.tags.push(tag);
+        self
+    }
+
+    /// Add (or replace) the operation for `method` on `path`.
+    pub fn operation(
+        mut self,
+        path: impl Into<String>,
+        method: Method,
+        operation: Operation,
+    ) -> Self {
+        let path_item = self.spec.paths.entry(path.into()).or_default();
+        let slot = match method {
+            Method::Get => &mut path_item.get,
+            Method::Put => &mut path_item.put,
+            Method::Post => &mut path_item.post,
+            Method::Delete => &mut path_item.delete,
+            Method::Options => &mut path_item.options,
+            Method::Head => &mut path_item.head,
+            Method::Patch => &mut path_item.patch,
+            Method::Trace => &mut path_item.trace,
+        };
+        *slot = Some(operation);
+        self
+    }
+
+    /// Add (or replace) the `GET` operation on `path`.
+    pub fn get(self, path: impl Into<String>, operation: Operation) -> Self {
+        self.operation(path, Method::Get, operation)
+    }
+
+    /// Add (or replace) the `PUT` operation on `path`.
+    pub fn put(self, path: impl Into<String>, operation: Operation) -> Self {
+        self.operation(path, Method::Put, operation)
+    }
+
+    /// Add (or replace) the `POST` operation on `path`.
+    pub fn post(self, path: impl Into<String>, operation: Operation) -> Self {
+        self.operation(path, Method::Post, operation)
+    }
+
+    /// Add (or replace) the `DELETE` operation on `path`.
+    pub fn delete(self, path: impl Into<String>, operation: Operation) -> Self {
+        self.operation(path, Method::Delete, operation)
+    }
+
+    /// Add (or replace) the `PATCH` operation on `path`.
+    pub fn patch(self, path: impl Into<String>, operation: Operation) -> Self {
+        self.operation(path, Method::Patch, operation)
+    }
+
+    /// Add (or replace) the full path item of `path`.
+    pub fn path_item(mut self, path: impl Into<String>, path_item: PathItem) -> Self {
+        self.spec.paths.insert(path.into(), path_item);
+        self
+    }
+
+    /// Add (or replace) a schema in `components.schemas`. It can be used with a `$ref` to
+    /// `#/components/schemas/{name}`.
+    pub fn schema(mut self, name: impl Into<String>, schema: SchemaObject) -> Self {
+        self.components().schemas.insert(name.into(), schema);
+        self
+    }
+
+    /// Add (or replace) a response in `components.responses`.
+    pub fn component_response(mut self, name: impl Into<String>, response: Response) -> Self {
+        self.components()
+            .responses
+            .insert(name.into(), RefOr::Object(response));
+        self
+    }
+
+    /// Add (or replace) a parameter in `components.parameters`.
+    pub fn component_parameter(mut self, name: impl Into<String>, parameter: Parameter) -> Self {
+        self.components()
+            .parameters
+            .insert(name.into(), RefOr::Object(parameter));
+        self
+    }
+
+    /// Add (or replace) a security scheme in `components.securitySchemes`.
+    pub fn security_scheme(mut self, name: impl Into<String>, scheme: SecurityScheme) -> Self {
+        self.components()
+            .security_schemes
+            .insert(name.into(), RefOr::Object(scheme));
+        self
+    }
+
+    /// Require the security scheme `name` (with the given OAuth2 `scopes`) for all operations
+    /// that do not set their own `security`.
+    pub fn security(mut self, name: impl Into<String>, scopes: Vec<String>) -> Self {
+        self.spec.security.push(security_requirement(name, scopes));
+        self
+    }
+
+    /// Add an extension, `key` should start with `x-`.
+    pub fn extension(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.spec.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Create the document.
+    pub fn build(self) -> OpenApi {
+        self.spec
+    }
+
+    fn components(&mut self) -> &mut Components {
+        self.spec.components.get_or_insert_with(Components::default)
+    }
+}
+
+/// Builder for an [`Operation`].
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct OperationBuilder {
+    operation: Operation,
+}
+
+impl OperationBuilder {
+    /// Create a builder for an operation with the response for `status`, like `200`. An
+    /// operation needs at least one response, more can be added with [`response`](Self::response).
+    pub fn new(status: impl Into<String>, response: Response) -> Self {
+        OperationBuilder {
+            operation: Operation::default(),
+        }
+        .response(status, response)
+    }
+
+    /// Set the `operationId`, it should be unique in the document.
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation.operation_id = Some(operation_id.into());
+        self
+    }
+
+    /// Set the summary.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    /// Add a tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    /// Add a parameter, see [`ParameterBuilder`].
+    pub fn parameter(mut self, parameter: Parameter) -> Self {
+        self.operation.parameters.push(RefOr::Object(parameter));
+        self
+    }
+
+    /// Add a reference to a parameter in `components.parameters`.
+    pub fn parameter_ref(mut self, name: &str) -> Self {
+        self.operation.parameters.push(RefOr::Ref(Ref {
+            reference: format!("#/components/parameters/{}", name),
+        }));
+        self
+    }
+
+    /// Set the request body, see [`RequestBodyBuilder`].
+    pub fn request_body(mut self, request_body: RequestBody) -> Self {
+        self.operation.request_body = Some(RefOr::Object(request_body));
+        self
+    }
+
+    /// Add (or replace) the response for `status`, like `200` or `4XX`.
+    pub fn response(mut self, status: impl Into<String>, response: Response) -> Self {
+        self.operation
+            .responses
+            .responses
+            .insert(status.into(), RefOr::Object(response));
+        self
+    }
+
+    /// Add (or replace) the response for `status` with a reference to a response in
+    /// `components.responses`.
+    pub fn response_ref(mut self, status: impl Into<String>, name: &str) -> Self {
+        self.operation.responses.responses.insert(
+            status.into(),
+            RefOr::Ref(Ref {
+                reference: format!("#/components/responses/{}", name),
+            }),
+        );
+        self
+    }
+
+    /// Set the response for all status codes that do not have their own response.
+    pub fn default_response(mut self, response: Response) -> Self {
+        self.operation.responses.default = Some(RefOr::Object(response));
+        self
+    }
+
+    /// Mark the operation as deprecated.
+    pub fn deprecated(mut self) -> Self {
+        self.operation.deprecated = true;
+        self
+    }
+
+    /// Require the security scheme `name` (with the given OAuth2 `scopes`) for this operation.
+    /// This replaces the document-wide `security`.
+    pub fn security(mut self, name: impl Into<String>, scopes: Vec<String>) -> Self {
+        self.operation
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(security_requirement(name, scopes));
+        self
+    }
+
+    /// Add an extension, `key` should start with `x-`.
+    pub fn extension(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.operation.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Create the operation.
+    pub fn build(self) -> Operation {
+        self.operation
+    }
+}
+
+/// Builder for a [`Parameter`] that is described by a schema.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct ParameterBuilder {
+    parameter: Parameter,
+}
+
+impl ParameterBuilder {
+    /// Create a builder for an optional parameter in the query string.
+    pub fn query(name: impl Into<String>, schema: SchemaObject) -> Self {
+        Self::new(name, "query", schema, false)
+    }
+
+    /// Create a builder for a parameter in the path. Path parameters are always required.
+    pub fn path(name: impl Into<String>, schema: SchemaObject) -> Self {
+        Self::new(name, "path", schema, true)
+    }
+
+    /// Create a builder for an optional header.
+    pub fn header(name: impl Into<String>, schema: SchemaObject) -> Self {
+        Self::new(name, "header", schema, false)
+    }
+
+    /// Create a builder for an optional cookie.
+    pub fn cookie(name: impl Into<String>, schema: SchemaObject) -> Self {
+        Self::new(name, "cookie", schema, false)
+    }
+
+    fn new(name: impl Into<String>, location: &str, schema: SchemaObject, required: bool) -> Self {
+        ParameterBuilder {
+            parameter: Parameter {
+                name: name.into(),
+                location: location.to_owned(),
+                description: None,
+                required,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema,
+                    example: None,
+                    examples: None,
+                },
+                extensions: Map::new(),
+            },
+        }
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.parameter.description = Some(description.into());
+        self
+    }
+
+    /// Set if the parameter is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.parameter.required = required;
+        self
+    }
+
+    /// Mark the parameter as deprecated.
+    pub fn deprecated(mut self) -> Self {
+        self.parameter.deprecated = true;
+        self
+    }
+
+    /// Set an example value.
+    pub fn example(mut self, value: Value) -> Self {
+        if let ParameterValue::Schema { example, .. } = &mut self.parameter.value {
+            *example = Some(value);
+        }
+        self
+    }
+
+    /// Create the parameter.
+    pub fn build(self) -> Parameter {
+        self.parameter
+    }
+}
+
+/// Builder for a [`RequestBody`].
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct RequestBodyBuilder {
+    request_body: RequestBody,
+}
+
+impl RequestBodyBuilder {
+    /// Create a builder for a required request body with the given media type and schema.
+    pub fn new(media_type: impl Into<String>, schema: SchemaObject) -> Self {
+        RequestBodyBuilder {
+            request_body: RequestBody {
+                content: media_type_map(media_type, schema),
+                required: true,
+                ..RequestBody::default()
+            },
+        }
+    }
+
+    /// Create a builder for a required `application/json` request body.
+    pub fn json(schema: SchemaObject) -> Self {
+        Self::new("application/json", schema)
+    }
+
+    /// Add (or replace) the schema for another media type.
+    pub fn content(mut self, media_type: impl Into<String>, schema: SchemaObject) -> Self {
+        self.request_body
+            .content
+            .extend(media_type_map(media_type, schema));
+        self
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.request_body.description = Some(description.into());
+        self
+    }
+
+    /// Set if the request body is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.request_body.required = required;
+        self
+    }
+
+    /// Create the request body.
+    pub fn build(self) -> RequestBody {
+        self.request_body
+    }
+}
+
+/// Builder for a [`Response`].
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct ResponseBuilder {
+    response: Response,
+}
+
+impl ResponseBuilder {
+    /// Create a builder for a response without content.
+    pub fn new(description: impl Into<String>) -> Self {
+        ResponseBuilder {
+            response: Response {
+                description: description.into(),
+                ..Response::default()
+            },
+        }
+    }
+
+    /// Add (or replace) the schema of the content for `media_type`.
+    pub fn content(mut self, media_type: impl Into<String>, schema: SchemaObject) -> Self {
+        self.response
+            .content
+            .extend(media_type_map(media_type, schema));
+        self
+    }
+
+    /// Add (or replace) the schema of the `application/json` content.
+    pub fn json(self, schema: SchemaObject) -> Self {
+        self.content("application/json", schema)
+    }
+
+    /// Add (or replace) a header of the response.
+    pub fn header(mut self, name: impl Into<String>, header: Header) -> Self {
+        self.response
+            .headers
+            .insert(name.into(), RefOr::Object(header));
+        self
+    }
+
+    /// Add an extension, `key` should start with `x-`.
+    pub fn extension(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.response.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Create the response.
+    pub fn build(self) -> Response {
+        self.response
+    }
+}
+
+fn media_type_map(media_type: impl Into<String>, schema: SchemaObject) -> Map<String, MediaType> {
+    let mut content = Map::new();
+    content.insert(
+        media_type.into(),
+        MediaType {
+            schema: Some(schema),
+            ..MediaType::default()
+        },
+    );
+    content
+}
+
+fn security_requirement(name: impl Into<String>, scopes: Vec<String>) -> SecurityRequirement {
+    let mut requirement = SecurityRequirement::new();
+    requirement.insert(name.into(), scopes);
+    requirement
+}
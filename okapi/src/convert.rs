@@ -0,0 +1,562 @@
+//! Convert documents between the OpenAPI 3.0 and 3.1 dialects.
+//!
+//! Okapi (and `Schemars`) generate OpenAPI 3.0 style schemas. The functions in this module
+//! rewrite the parts that differ between the versions, so the same document can be published
+//! as both, or downgraded to Swagger 2.0 with [`to_swagger2`].
+
+mod swagger2;
+
+pub use swagger2::to_swagger2;
+
+use crate::openapi3::{
+    Callback, Components, Header, MediaType, OpenApi, Operation, Parameter, ParameterValue,
+    PathItem, RefOr, RequestBody, Response,
+};
+use crate::Map;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec, SubschemaValidation};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::Value;
+
+/// Convert an OpenAPI 3.0 document to OpenAPI 3.1.
+///
+/// - `nullable: true` is replaced by a `null` entry in `type`. Schemas without a `type` (like
+///   references) are wrapped in an `anyOf` with `{"type": "null"}`.
+/// - The boolean `exclusiveMinimum`/`exclusiveMaximum` are replaced by their numeric form.
+/// - The `x-webhooks` extension is moved to the `webhooks` field.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::{Components, OpenApi};
+/// use okapi::schemars::schema::{InstanceType, SchemaObject};
+///
+/// let mut schema = SchemaObject {
+///     instance_type: Some(InstanceType::String.into()),
+///     ..SchemaObject::default()
+/// };
+/// schema.extensions.insert("nullable".to_owned(), true.into());
+/// let mut spec = OpenApi::new();
+/// spec.components = Some(Components::default());
+/// spec.components.as_mut().unwrap().schemas.insert("Name".to_owned(), schema);
+///
+/// let spec = okapi::convert::to_v31(spec);
+/// let schema = &spec.components.unwrap().schemas["Name"];
+/// assert_eq!(spec.openapi, "3.1.0");
+/// assert_eq!(
+///     serde_json::to_value(&schema.instance_type).unwrap(),
+///     serde_json::json!(["string", "null"])
+/// );
+/// ```
+#[must_use]
+pub fn to_v31(mut spec: OpenApi) -> OpenApi {
+    spec.openapi = "3.1.0".to_owned();
+    visit_spec_schemas(&mut spec, &mut ToV31);
+    rename_extension(&mut spec.extensions, "x-webhooks", "webhooks");
+    visit_webhooks(&mut spec.extensions, "webhooks", &mut ToV31);
+    spec
+}
+
+/// Convert an OpenAPI 3.1 document to OpenAPI 3.0.
+///
+/// This is the reverse of [`to_v31`]:
+/// - A `null` entry in `type` (or an `anyOf`/`oneOf` with `{"type": "null"}`) is replaced by
+///   `nullable: true`.
+/// - The numeric `exclusiveMinimum`/`exclusiveMaximum` are replaced by `minimum`/`maximum` and
+///   the boolean form.
+/// - The `webhooks` field is moved to the `x-webhooks` extension, as OpenAPI 3.0 does not
+///   support webhooks.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+/// use serde_json::json;
+///
+/// let document = json!({
+///     "openapi": "3.0.0",
+///     "info": { "title": "Shop", "version": "1.0.0" },
+///     "paths": {
+///         "/items": {
+///             "get": {
+///                 "parameters": [{
+///                     "name": "limit",
+///                     "in": "query",
+///                     "schema": { "type": "integer", "maximum": 100.0, "exclusiveMaximum": true }
+///                 }],
+///                 "responses": {
+///                     "200": {
+///                         "description": "",
+///                         "content": {
+///                             "application/json": {
+///                                 "schema": {
+///                                     "type": "array",
+///                                     "items": { "$ref": "#/components/schemas/Item" }
+///                                 }
+///                             }
+///                         }
+///                     }
+///                 }
+///             }
+///         }
+///     },
+///     "components": {
+///         "schemas": {
+///             "Item": {
+///                 "type": "object",
+///                 "properties": {
+///                     "price": { "type": "number", "minimum": 5.0, "exclusiveMinimum": true }
+///                 }
+///             }
+///         }
+///     }
+/// });
+///
+/// let spec: OpenApi = serde_json::from_value(document.clone()).unwrap();
+/// let spec = okapi::convert::to_v31(spec);
+/// let v31 = serde_json::to_value(&spec).unwrap();
+/// assert_eq!(
+///     v31["components"]["schemas"]["Item"]["properties"]["price"],
+///     json!({ "type": "number", "exclusiveMinimum": 5.0 })
+/// );
+/// assert_eq!(
+///     v31["paths"]["/items"]["get"]["parameters"][0]["schema"],
+///     json!({ "type": "integer", "exclusiveMaximum": 100.0 })
+/// );
+///
+/// let spec = okapi::convert::to_v30(spec);
+/// assert_eq!(serde_json::to_value(&spec).unwrap(), document);
+/// ```
+#[must_use]
+pub fn to_v30(mut spec: OpenApi) -> OpenApi {
+    spec.openapi = "3.0.0".to_owned();
+    visit_spec_schemas(&mut spec, &mut ToV30);
+    rename_extension(&mut spec.extensions, "webhooks", "x-webhooks");
+    visit_webhooks(&mut spec.extensions, "x-webhooks", &mut ToV30);
+    spec
+}
+
+/// Replace the `null` entries in `type` (like `type: [string, null]`) by an `anyOf` with
+/// `{"type": "null"}`, like `anyOf: [{type: string}, {type: null}]`, for tools that do not support
+/// type arrays. The description and other metadata stay on the outer schema.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::{Components, OpenApi};
+/// use okapi::schemars::schema::{InstanceType, SchemaObject};
+///
+/// let schema = SchemaObject {
+///     instance_type: Some(vec![InstanceType::String, InstanceType::Null].into()),
+///     ..SchemaObject::default()
+/// };
+/// let mut spec = OpenApi::new();
+/// spec.components = Some(Components::default());
+/// spec.components.as_mut().unwrap().schemas.insert("Name".to_owned(), schema);
+///
+/// let spec = okapi::convert::null_types_to_any_of(spec);
+/// let schema = &spec.components.unwrap().schemas["Name"];
+/// assert_eq!(
+///     serde_json::to_value(schema).unwrap(),
+///     serde_json::json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+/// );
+/// ```
+#[must_use]
+pub fn null_types_to_any_of(mut spec: OpenApi) -> OpenApi {
+    visit_spec_schemas(&mut spec, &mut NullTypesToAnyOf);
+    spec
+}
+
+/// Rewrites type arrays with `null` to an `anyOf`.
+#[derive(Debug, Clone)]
+struct NullTypesToAnyOf;
+
+impl Visitor for NullTypesToAnyOf {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        let instance_types = match &mut schema.instance_type {
+            Some(SingleOrVec::Vec(instance_types))
+                if instance_types.contains(&InstanceType::Null) =>
+            {
+                instance_types
+            }
+            _ => return,
+        };
+        instance_types.retain(|instance_type| *instance_type != InstanceType::Null);
+        schema.instance_type = match instance_types.as_slice() {
+            [] => None,
+            [instance_type] => Some((*instance_type).into()),
+            _ => schema.instance_type.take(),
+        };
+        let metadata = schema.metadata.take();
+        let inner = std::mem::take(schema);
+        schema.metadata = metadata;
+        schema.subschemas = Some(Box::new(SubschemaValidation {
+            any_of: Some(vec![Schema::Object(inner), null_schema()]),
+            ..SubschemaValidation::default()
+        }));
+    }
+}
+
+/// Rewrites OpenAPI 3.0 schemas to OpenAPI 3.1.
+#[derive(Debug, Clone)]
+struct ToV31;
+
+impl Visitor for ToV31 {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        nullable_to_type_array(schema);
+        if let Some(number) = &mut schema.number {
+            if take_bool_extension(&mut schema.extensions, "exclusiveMinimum") {
+                number.exclusive_minimum = number.minimum.take();
+            }
+            if take_bool_extension(&mut schema.extensions, "exclusiveMaximum") {
+                number.exclusive_maximum = number.maximum.take();
+            }
+        }
+    }
+}
+
+/// Rewrites OpenAPI 3.1 schemas to OpenAPI 3.0.
+#[derive(Debug, Clone)]
+struct ToV30;
+
+impl Visitor for ToV30 {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        type_array_to_nullable(schema);
+        if let Some(number) = &mut schema.number {
+            // The boolean form is not supported by `NumberValidation`, so it is added as an
+            // extension. Both are serialized as `exclusiveMinimum`.
+            if let Some(minimum) = number.exclusive_minimum.take() {
+                number.minimum = Some(minimum);
+                schema
+                    .extensions
+                    .insert("exclusiveMinimum".to_owned(), true.into());
+            }
+            if let Some(maximum) = number.exclusive_maximum.take() {
+                number.maximum = Some(maximum);
+                schema
+                    .extensions
+                    .insert("exclusiveMaximum".to_owned(), true.into());
+            }
+        }
+    }
+}
+
+fn nullable_to_type_array(schema: &mut SchemaObject) {
+    if !take_bool_extension(&mut schema.extensions, "nullable") {
+        return;
+    }
+    match &mut schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) if **instance_type != InstanceType::Null => {
+            schema.instance_type = Some(vec![**instance_type, InstanceType::Null].into());
+        }
+        Some(SingleOrVec::Vec(instance_types)) if !instance_types.contains(&InstanceType::Null) => {
+            instance_types.push(InstanceType::Null);
+        }
+        Some(_) => {}
+        None if schema.reference.is_some() || schema.subschemas.is_some() => {
+            // `{"allOf": [{"$ref": ...}], "nullable": true}` is the common way to make a
+            // reference nullable, it can become an `anyOf` directly.
+            let single_all_of = match &mut schema.subschemas {
+                Some(subschemas) if is_single_all_of(subschemas) => subschemas.all_of.take(),
+                _ => None,
+            };
+            let schemas = match single_all_of {
+                Some(all_of) => {
+                    schema.subschemas = None;
+                    all_of
+                }
+                None => vec![Schema::Object(std::mem::take(schema))],
+            };
+            schema.subschemas = Some(Box::new(SubschemaValidation {
+                any_of: Some(schemas.into_iter().chain(Some(null_schema())).collect()),
+                ..SubschemaValidation::default()
+            }));
+        }
+        // A schema without a type already allows `null`.
+        None => {}
+    }
+}
+
+pub(crate) fn type_array_to_nullable(schema: &mut SchemaObject) {
+    let mut nullable = false;
+    if let Some(SingleOrVec::Vec(instance_types)) = &mut schema.instance_type {
+        if instance_types.contains(&InstanceType::Null) {
+            instance_types.retain(|instance_type| *instance_type != InstanceType::Null);
+            nullable = true;
+            schema.instance_type = match instance_types.as_slice() {
+                [] => None,
+                [instance_type] => Some((*instance_type).into()),
+                _ => schema.instance_type.take(),
+            };
+        }
+    }
+    if let Some(subschemas) = &mut schema.subschemas {
+        let SubschemaValidation {
+            all_of,
+            any_of,
+            one_of,
+            ..
+        } = &mut **subschemas;
+        for list in [any_of, one_of] {
+            if let Some(schemas) = list {
+                let len = schemas.len();
+                schemas.retain(|schema| *schema != null_schema());
+                if schemas.len() != len {
+                    nullable = true;
+                    // A single schema left is written as `allOf`, like `Schemars` does.
+                    if schemas.len() == 1 && all_of.is_none() {
+                        *all_of = list.take();
+                    }
+                }
+            }
+        }
+    }
+    if nullable {
+        schema.extensions.insert("nullable".to_owned(), true.into());
+    }
+}
+
+/// Only the `allOf` is set, and it contains a single schema.
+fn is_single_all_of(subschemas: &SubschemaValidation) -> bool {
+    matches!(&subschemas.all_of, Some(all_of) if all_of.len() == 1)
+        && SubschemaValidation {
+            all_of: None,
+            ..subschemas.clone()
+        } == SubschemaValidation::default()
+}
+
+fn null_schema() -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Null.into()),
+        ..SchemaObject::default()
+    })
+}
+
+/// Remove the extension, returns `true` if it was set to `true`.
+fn take_bool_extension(extensions: &mut Map<String, Value>, name: &str) -> bool {
+    extensions.remove(name) == Some(Value::Bool(true))
+}
+
+/// The OpenAPI 3.0 boolean `exclusiveMinimum`/`exclusiveMaximum`, and the extension they are
+/// moved to while the schema is deserialized. `NumberValidation` only accepts the numeric form,
+/// and the flattened extensions can not take a field it already claimed.
+const BOOLEAN_BOUNDS: [(&str, &str); 2] = [
+    ("exclusiveMinimum", "x-okapi-exclusiveMinimum"),
+    ("exclusiveMaximum", "x-okapi-exclusiveMaximum"),
+];
+
+/// Deserialize a schema that can contain the boolean `exclusiveMinimum`/`exclusiveMaximum` of
+/// OpenAPI 3.0. The boolean forms are kept as extensions, like [`to_v30`] writes them.
+pub(crate) fn schema_from_value(mut value: Value) -> serde_json::Result<SchemaObject> {
+    move_boolean_bounds(&mut value);
+    let mut schema = serde_json::from_value(value)?;
+    RestoreBooleanBounds.visit_schema_object(&mut schema);
+    Ok(schema)
+}
+
+/// Move the boolean bounds of the schema (and its subschemas) out of the way of
+/// `NumberValidation`.
+fn move_boolean_bounds(value: &mut Value) {
+    let object = match value {
+        Value::Object(object) => object,
+        _ => return,
+    };
+    for (name, temporary) in BOOLEAN_BOUNDS {
+        if let Some(Value::Bool(exclusive)) = object.get(name) {
+            let exclusive = *exclusive;
+            object.remove(name);
+            object.insert(temporary.to_owned(), exclusive.into());
+        }
+    }
+    for (key, value) in object.iter_mut() {
+        match key.as_str() {
+            "items" | "allOf" | "anyOf" | "oneOf" => match value {
+                Value::Array(schemas) => schemas.iter_mut().for_each(move_boolean_bounds),
+                schema => move_boolean_bounds(schema),
+            },
+            "properties" | "patternProperties" => {
+                if let Value::Object(schemas) = value {
+                    schemas.values_mut().for_each(move_boolean_bounds);
+                }
+            }
+            "additionalProperties"
+            | "additionalItems"
+            | "contains"
+            | "propertyNames"
+            | "not"
+            | "if"
+            | "then"
+            | "else" => move_boolean_bounds(value),
+            _ => {}
+        }
+    }
+}
+
+/// Moves the boolean bounds back to the `exclusiveMinimum`/`exclusiveMaximum` extensions.
+struct RestoreBooleanBounds;
+
+impl Visitor for RestoreBooleanBounds {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        for (name, temporary) in BOOLEAN_BOUNDS {
+            rename_extension(&mut schema.extensions, temporary, name);
+        }
+    }
+}
+
+fn rename_extension(extensions: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = extensions.remove(from) {
+        extensions.insert(to.to_owned(), value);
+    }
+}
+
+/// Webhooks are not part of the OpenAPI 3.0 `OpenApi` object, so they are stored as an
+/// extension. Convert them to path items to visit their schemas.
+fn visit_webhooks<V: Visitor>(extensions: &mut Map<String, Value>, name: &str, v: &mut V) {
+    let webhooks = match extensions.get(name) {
+        Some(webhooks) => webhooks.clone(),
+        None => return,
+    };
+    if let Ok(mut webhooks) = serde_json::from_value::<Map<String, PathItem>>(webhooks) {
+        for path_item in webhooks.values_mut() {
+            visit_path_item(path_item, v);
+        }
+        if let Ok(webhooks) = serde_json::to_value(webhooks) {
+            extensions.insert(name.to_owned(), webhooks);
+        }
+    }
+}
+
+/// Call the visitor on all schemas in the document.
+pub(crate) fn visit_spec_schemas<V: Visitor>(spec: &mut OpenApi, v: &mut V) {
+    for path_item in spec.paths.values_mut() {
+        visit_path_item(path_item, v);
+    }
+    if let Some(components) = &mut spec.components {
+        visit_components(components, v);
+    }
+}
+
+fn visit_components<V: Visitor>(components: &mut Components, v: &mut V) {
+    let Components {
+        schemas,
+        responses,
+        parameters,
+        request_bodies,
+        headers,
+        callbacks,
+        ..
+    } = components;
+    for schema in schemas.values_mut() {
+        v.visit_schema_object(schema);
+    }
+    for response in responses.values_mut() {
+        visit_ref_or(response, v, visit_response);
+    }
+    for parameter in parameters.values_mut() {
+        visit_ref_or(parameter, v, visit_parameter);
+    }
+    for request_body in request_bodies.values_mut() {
+        visit_ref_or(request_body, v, visit_request_body);
+    }
+    for header in headers.values_mut() {
+        visit_ref_or(header, v, visit_header);
+    }
+    for callback in callbacks.values_mut() {
+        visit_ref_or(callback, v, visit_callback);
+    }
+}
+
+fn visit_ref_or<T, V: Visitor>(ref_or: &mut RefOr<T>, v: &mut V, f: fn(&mut T, &mut V)) {
+    if let RefOr::Object(object) = ref_or {
+        f(object, v);
+    }
+}
+
+fn visit_path_item<V: Visitor>(path_item: &mut PathItem, v: &mut V) {
+    for parameter in &mut path_item.parameters {
+        visit_ref_or(parameter, v, visit_parameter);
+    }
+    let PathItem {
+        get,
+        put,
+        post,
+        delete,
+        options,
+        head,
+        patch,
+        trace,
+        ..
+    } = path_item;
+    for operation in vec![get, put, post, delete, options, head, patch, trace]
+        .into_iter()
+        .flatten()
+    {
+        visit_operation(operation, v);
+    }
+}
+
+fn visit_operation<V: Visitor>(operation: &mut Operation, v: &mut V) {
+    for parameter in &mut operation.parameters {
+        visit_ref_or(parameter, v, visit_parameter);
+    }
+    if let Some(request_body) = &mut operation.request_body {
+        visit_ref_or(request_body, v, visit_request_body);
+    }
+    if let Some(response) = &mut operation.responses.default {
+        visit_ref_or(response, v, visit_response);
+    }
+    for response in operation.responses.responses.values_mut() {
+        visit_ref_or(response, v, visit_response);
+    }
+    for callback in operation.callbacks.values_mut() {
+        visit_ref_or(callback, v, visit_callback);
+    }
+}
+
+fn visit_parameter<V: Visitor>(parameter: &mut Parameter, v: &mut V) {
+    visit_parameter_value(&mut parameter.value, v);
+}
+
+fn visit_header<V: Visitor>(header: &mut Header, v: &mut V) {
+    visit_parameter_value(&mut header.value, v);
+}
+
+fn visit_parameter_value<V: Visitor>(value: &mut ParameterValue, v: &mut V) {
+    match value {
+        ParameterValue::Schema { schema, .. } => v.visit_schema_object(schema),
+        ParameterValue::Content { content } => visit_content(content, v),
+    }
+}
+
+fn visit_request_body<V: Visitor>(request_body: &mut RequestBody, v: &mut V) {
+    visit_content(&mut request_body.content, v);
+}
+
+fn visit_response<V: Visitor>(response: &mut Response, v: &mut V) {
+    for header in response.headers.values_mut() {
+        visit_ref_or(header, v, visit_header);
+    }
+    visit_content(&mut response.content, v);
+}
+
+fn visit_callback<V: Visitor>(callback: &mut Callback, v: &mut V) {
+    for path_item in callback.callbacks.values_mut() {
+        visit_path_item(path_item, v);
+    }
+}
+
+fn visit_content<V: Visitor>(content: &mut Map<String, MediaType>, v: &mut V) {
+    for media_type in content.values_mut() {
+        if let Some(schema) = &mut media_type.schema {
+            v.visit_schema_object(schema);
+        }
+        for encoding in media_type.encoding.values_mut() {
+            for header in encoding.headers.values_mut() {
+                visit_ref_or(header, v, visit_header);
+            }
+        }
+    }
+}
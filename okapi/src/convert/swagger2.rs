@@ -0,0 +1,528 @@
+use super::{visit_spec_schemas, ToV30};
+use crate::openapi3::{OAuthFlows, OpenApi, RefOr, SecurityScheme, SecuritySchemeData, Server};
+use schemars::schema::SchemaObject;
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::{json, Map, Value};
+
+/// The fields of a schema that can be used in a Swagger 2.0 non-body parameter or header.
+const SIMPLE_SCHEMA_FIELDS: &[&str] = &[
+    "type",
+    "format",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+const FORM_MEDIA_TYPES: &[&str] = &["application/x-www-form-urlencoded", "multipart/form-data"];
+
+/// Convert an OpenAPI 3.0 document to a Swagger 2.0 (OpenAPI 2.0) document.
+///
+/// Swagger 2.0 can not express everything OpenAPI 3.0 can, the parts that have no counterpart
+/// are converted where possible and left out otherwise:
+/// - Only the first of the `servers` is used for the `host`, `basePath` and `schemes`.
+/// - Request bodies become a `body` parameter, or `formData` parameters for forms. Only the
+///   schema of the first media type is used, all media types are listed in `consumes`.
+/// - Only the schema of the first media type of a response is used, all media types are listed
+///   in `produces`.
+/// - `nullable` is written as the `x-nullable` extension.
+/// - Cookie parameters, callbacks, links, `trace` operations, and security schemes other than
+///   `apiKey`, `http` `basic` and `oauth2` are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::{OpenApi, Server};
+///
+/// let mut spec = OpenApi::new();
+/// spec.servers.push(Server {
+///     url: "https://example.com/api".to_owned(),
+///     ..Server::default()
+/// });
+///
+/// let swagger = okapi::convert::to_swagger2(&spec).unwrap();
+/// assert_eq!(swagger["swagger"], "2.0");
+/// assert_eq!(swagger["host"], "example.com");
+/// assert_eq!(swagger["basePath"], "/api");
+/// ```
+pub fn to_swagger2(spec: &OpenApi) -> serde_json::Result<Value> {
+    let mut spec = spec.clone();
+    visit_spec_schemas(&mut spec, &mut ToV30);
+    visit_spec_schemas(&mut spec, &mut Swagger2Schemas);
+    let document = serde_json::to_value(&spec)?;
+
+    let mut swagger = Map::new();
+    swagger.insert("swagger".to_owned(), "2.0".into());
+    swagger.insert("info".to_owned(), document["info"].clone());
+    if let Some(server) = spec.servers.first() {
+        add_server(&mut swagger, server);
+    }
+    let mut paths = Map::new();
+    if let Some(Value::Object(path_items)) = document.get("paths") {
+        for (path, path_item) in path_items {
+            paths.insert(path.clone(), convert_path_item(&document, path_item));
+        }
+    }
+    swagger.insert("paths".to_owned(), Value::Object(paths));
+
+    if let Some(components) = &spec.components {
+        let schemas = &document["components"]["schemas"];
+        if !components.schemas.is_empty() {
+            swagger.insert("definitions".to_owned(), schemas.clone());
+        }
+        let parameters: Map<String, Value> = components
+            .parameters
+            .keys()
+            .filter_map(|name| {
+                let parameter = &document["components"]["parameters"][name];
+                convert_parameter(&document, parameter).map(|parameter| (name.clone(), parameter))
+            })
+            .collect();
+        if !parameters.is_empty() {
+            swagger.insert("parameters".to_owned(), Value::Object(parameters));
+        }
+        let responses: Map<String, Value> = components
+            .responses
+            .keys()
+            .map(|name| {
+                let response = &document["components"]["responses"][name];
+                let response = convert_response(&document, response, &mut Vec::new());
+                (name.clone(), response)
+            })
+            .collect();
+        if !responses.is_empty() {
+            swagger.insert("responses".to_owned(), Value::Object(responses));
+        }
+        let security_definitions: Map<String, Value> = components
+            .security_schemes
+            .iter()
+            .filter_map(|(name, scheme)| match scheme {
+                RefOr::Object(scheme) => {
+                    convert_security_scheme(scheme).map(|scheme| (name.clone(), scheme))
+                }
+                RefOr::Ref(_) => None,
+            })
+            .collect();
+        if !security_definitions.is_empty() {
+            swagger.insert(
+                "securityDefinitions".to_owned(),
+                Value::Object(security_definitions),
+            );
+        }
+    }
+    for key in &["security", "tags", "externalDocs"] {
+        if let Some(value) = document.get(*key) {
+            swagger.insert((*key).to_owned(), value.clone());
+        }
+    }
+    copy_extensions(&document, &mut swagger);
+
+    let mut swagger = Value::Object(swagger);
+    rewrite_refs(&mut swagger);
+    Ok(swagger)
+}
+
+/// Writes `nullable` as `x-nullable`, the extension used by most Swagger 2.0 tools.
+#[derive(Debug, Clone)]
+struct Swagger2Schemas;
+
+impl Visitor for Swagger2Schemas {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        if let Some(nullable) = schema.extensions.remove("nullable") {
+            schema.extensions.insert("x-nullable".to_owned(), nullable);
+        }
+    }
+}
+
+fn add_server(swagger: &mut Map<String, Value>, server: &Server) {
+    let mut url = server.url.clone();
+    for (name, variable) in &server.variables {
+        url = url.replace(&format!("{{{}}}", name), &variable.default);
+    }
+    let path = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            swagger.insert("schemes".to_owned(), json!([scheme]));
+            let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            swagger.insert("host".to_owned(), host.into());
+            path
+        }
+        None => url.as_str(),
+    };
+    if path.starts_with('/') && path != "/" {
+        swagger.insert("basePath".to_owned(), path.trim_end_matches('/').into());
+    }
+}
+
+fn convert_path_item(document: &Value, path_item: &Value) -> Value {
+    let mut converted = Map::new();
+    if let Some(reference) = path_item.get("$ref") {
+        converted.insert("$ref".to_owned(), reference.clone());
+    }
+    for method in &["get", "put", "post", "delete", "options", "head", "patch"] {
+        if let Some(operation) = path_item.get(*method) {
+            converted.insert((*method).to_owned(), convert_operation(document, operation));
+        }
+    }
+    let parameters = convert_parameters(document, path_item);
+    if !parameters.is_empty() {
+        converted.insert("parameters".to_owned(), Value::Array(parameters));
+    }
+    copy_extensions(path_item, &mut converted);
+    Value::Object(converted)
+}
+
+fn convert_operation(document: &Value, operation: &Value) -> Value {
+    let mut converted = Map::new();
+    for key in &[
+        "tags",
+        "summary",
+        "description",
+        "externalDocs",
+        "operationId",
+        "deprecated",
+        "security",
+    ] {
+        if let Some(value) = operation.get(*key) {
+            converted.insert((*key).to_owned(), value.clone());
+        }
+    }
+    let mut parameters = convert_parameters(document, operation);
+    if let Some(request_body) = operation.get("requestBody") {
+        let request_body = resolve(document, request_body);
+        let content = request_body.get("content").and_then(Value::as_object);
+        if let Some(content) = content.filter(|content| !content.is_empty()) {
+            let consumes: Vec<&String> = content.keys().collect();
+            converted.insert("consumes".to_owned(), json!(consumes));
+            let form = content
+                .iter()
+                .find(|(media_type, _)| FORM_MEDIA_TYPES.contains(&media_type.as_str()));
+            match form {
+                Some((_, media_type)) => {
+                    parameters.extend(form_parameters(document, &media_type["schema"]))
+                }
+                None => {
+                    let (_, media_type) = content.iter().next().unwrap();
+                    let mut body = Map::new();
+                    body.insert("name".to_owned(), "body".into());
+                    body.insert("in".to_owned(), "body".into());
+                    if let Some(description) = request_body.get("description") {
+                        body.insert("description".to_owned(), description.clone());
+                    }
+                    if let Some(required) = request_body.get("required") {
+                        body.insert("required".to_owned(), required.clone());
+                    }
+                    body.insert(
+                        "schema".to_owned(),
+                        media_type
+                            .get("schema")
+                            .cloned()
+                            .unwrap_or_else(|| json!({})),
+                    );
+                    parameters.push(Value::Object(body));
+                }
+            }
+        }
+    }
+    if !parameters.is_empty() {
+        converted.insert("parameters".to_owned(), Value::Array(parameters));
+    }
+    let mut produces = Vec::new();
+    let mut responses = Map::new();
+    if let Some(Value::Object(operation_responses)) = operation.get("responses") {
+        for (status, response) in operation_responses {
+            if status.starts_with("x-") {
+                responses.insert(status.clone(), response.clone());
+            } else {
+                let response = convert_response(document, response, &mut produces);
+                responses.insert(status.clone(), response);
+            }
+        }
+    }
+    if !produces.is_empty() {
+        converted.insert("produces".to_owned(), json!(produces));
+    }
+    converted.insert("responses".to_owned(), Value::Object(responses));
+    copy_extensions(operation, &mut converted);
+    Value::Object(converted)
+}
+
+fn convert_parameters(document: &Value, object: &Value) -> Vec<Value> {
+    match object.get("parameters") {
+        Some(Value::Array(parameters)) => parameters
+            .iter()
+            .filter_map(|parameter| convert_parameter(document, parameter))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a non-body parameter. Returns `None` if it can not be represented.
+fn convert_parameter(document: &Value, parameter: &Value) -> Option<Value> {
+    if parameter.get("$ref").is_some() {
+        // The reference is dropped if the parameter it points to can not be converted.
+        return convert_parameter(document, resolve(document, parameter))
+            .map(|_| parameter.clone());
+    }
+    let location = parameter.get("in")?.as_str()?;
+    if location == "cookie" {
+        return None;
+    }
+    let mut converted = Map::new();
+    for key in &["name", "in", "description", "required", "allowEmptyValue"] {
+        if let Some(value) = parameter.get(*key) {
+            converted.insert((*key).to_owned(), value.clone());
+        }
+    }
+    let schema = parameter.get("schema")?;
+    converted.extend(simple_schema(document, schema));
+    if converted.get("type") == Some(&Value::from("array")) {
+        let style = parameter.get("style").and_then(Value::as_str);
+        let explode = parameter
+            .get("explode")
+            .and_then(Value::as_bool)
+            .unwrap_or(style.unwrap_or("form") == "form");
+        let collection_format = match style {
+            Some("spaceDelimited") => "ssv",
+            Some("pipeDelimited") => "pipes",
+            _ if explode && (location == "query" || location == "formData") => "multi",
+            _ => "csv",
+        };
+        converted.insert("collectionFormat".to_owned(), collection_format.into());
+    }
+    copy_extensions(parameter, &mut converted);
+    Some(Value::Object(converted))
+}
+
+/// Every property of the form schema becomes a `formData` parameter.
+fn form_parameters(document: &Value, schema: &Value) -> Vec<Value> {
+    let schema = resolve(document, schema);
+    let required: Vec<&Value> = match schema.get("required") {
+        Some(Value::Array(required)) => required.iter().collect(),
+        _ => Vec::new(),
+    };
+    let properties = match schema.get("properties") {
+        Some(Value::Object(properties)) => properties,
+        _ => return Vec::new(),
+    };
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let mut parameter = Map::new();
+            parameter.insert("name".to_owned(), name.clone().into());
+            parameter.insert("in".to_owned(), "formData".into());
+            if let Some(description) = resolve(document, property).get("description") {
+                parameter.insert("description".to_owned(), description.clone());
+            }
+            parameter.insert(
+                "required".to_owned(),
+                required.contains(&&Value::from(name.as_str())).into(),
+            );
+            parameter.extend(simple_schema(document, property));
+            if parameter.get("format") == Some(&Value::from("binary")) {
+                parameter.insert("type".to_owned(), "file".into());
+                parameter.remove("format");
+            }
+            Value::Object(parameter)
+        })
+        .collect()
+}
+
+/// Convert a response, the media types of its content are added to `produces`.
+fn convert_response(document: &Value, response: &Value, produces: &mut Vec<String>) -> Value {
+    if response.get("$ref").is_some() {
+        convert_response(document, resolve(document, response), produces);
+        return response.clone();
+    }
+    let mut converted = Map::new();
+    converted.insert(
+        "description".to_owned(),
+        response.get("description").cloned().unwrap_or_default(),
+    );
+    if let Some(Value::Object(content)) = response.get("content") {
+        for media_type in content.keys() {
+            if !produces.contains(media_type) {
+                produces.push(media_type.clone());
+            }
+        }
+        if let Some(schema) = content
+            .values()
+            .find_map(|media_type| media_type.get("schema"))
+        {
+            converted.insert("schema".to_owned(), schema.clone());
+        }
+        let examples: Map<String, Value> = content
+            .iter()
+            .filter_map(|(media_type, content)| {
+                Some((media_type.clone(), content.get("example")?.clone()))
+            })
+            .collect();
+        if !examples.is_empty() {
+            converted.insert("examples".to_owned(), Value::Object(examples));
+        }
+    }
+    if let Some(Value::Object(headers)) = response.get("headers") {
+        let headers: Map<String, Value> = headers
+            .iter()
+            .filter_map(|(name, header)| {
+                let header = resolve(document, header);
+                let mut converted = simple_schema(document, header.get("schema")?);
+                if let Some(description) = header.get("description") {
+                    converted.insert("description".to_owned(), description.clone());
+                }
+                Some((name.clone(), Value::Object(converted)))
+            })
+            .collect();
+        if !headers.is_empty() {
+            converted.insert("headers".to_owned(), Value::Object(headers));
+        }
+    }
+    copy_extensions(response, &mut converted);
+    Value::Object(converted)
+}
+
+/// Swagger 2.0 parameters and headers can not use references or nested schemas, only the
+/// fields in `SIMPLE_SCHEMA_FIELDS` (and `items`) are kept.
+fn simple_schema(document: &Value, schema: &Value) -> Map<String, Value> {
+    let mut schema = resolve(document, schema);
+    // `Schemars` wraps references with a description (or `nullable`) in an `allOf`.
+    if let Some(Value::Array(all_of)) = schema.get("allOf") {
+        if let [inner] = all_of.as_slice() {
+            schema = resolve(document, inner);
+        }
+    }
+    let mut converted: Map<String, Value> = SIMPLE_SCHEMA_FIELDS
+        .iter()
+        .filter_map(|key| Some(((*key).to_owned(), schema.get(*key)?.clone())))
+        .collect();
+    if let Some(items) = schema.get("items") {
+        converted.insert(
+            "items".to_owned(),
+            Value::Object(simple_schema(document, items)),
+        );
+    }
+    converted
+        .entry("type")
+        .or_insert_with(|| Value::from("string"));
+    converted
+}
+
+fn convert_security_scheme(scheme: &SecurityScheme) -> Option<Value> {
+    let mut converted = match &scheme.data {
+        SecuritySchemeData::ApiKey { name, location } if location != "cookie" => {
+            json!({"type": "apiKey", "name": name, "in": location})
+        }
+        SecuritySchemeData::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+            json!({"type": "basic"})
+        }
+        SecuritySchemeData::OAuth2 { flows } => match flows {
+            OAuthFlows::Implicit {
+                authorization_url,
+                scopes,
+                ..
+            } => json!({
+                "type": "oauth2",
+                "flow": "implicit",
+                "authorizationUrl": authorization_url,
+                "scopes": scopes,
+            }),
+            OAuthFlows::Password {
+                token_url, scopes, ..
+            } => json!({
+                "type": "oauth2",
+                "flow": "password",
+                "tokenUrl": token_url,
+                "scopes": scopes,
+            }),
+            OAuthFlows::ClientCredentials {
+                token_url, scopes, ..
+            } => json!({
+                "type": "oauth2",
+                "flow": "application",
+                "tokenUrl": token_url,
+                "scopes": scopes,
+            }),
+            OAuthFlows::AuthorizationCode {
+                authorization_url,
+                token_url,
+                scopes,
+                ..
+            } => json!({
+                "type": "oauth2",
+                "flow": "accessCode",
+                "authorizationUrl": authorization_url,
+                "tokenUrl": token_url,
+                "scopes": scopes,
+            }),
+        },
+        _ => return None,
+    };
+    if let Some(description) = &scheme.description {
+        converted["description"] = description.clone().into();
+    }
+    Some(converted)
+}
+
+/// Follow a local `$ref`, returns the value itself if it is not a reference.
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    // Limit the depth, in case of a reference cycle.
+    for _ in 0..64 {
+        match value
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            Some(target) => value = target,
+            None => break,
+        }
+    }
+    value
+}
+
+fn copy_extensions(from: &Value, to: &mut Map<String, Value>) {
+    if let Value::Object(from) = from {
+        for (key, value) in from {
+            if key.starts_with("x-") {
+                to.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Point the references to the locations used by Swagger 2.0.
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object.iter_mut() {
+                match (key.as_str(), item) {
+                    ("$ref", Value::String(reference)) => {
+                        for (from, to) in &[
+                            ("#/components/schemas/", "#/definitions/"),
+                            ("#/components/parameters/", "#/parameters/"),
+                            ("#/components/responses/", "#/responses/"),
+                        ] {
+                            if let Some(name) = reference.strip_prefix(*from) {
+                                *reference = format!("{}{}", to, name);
+                                break;
+                            }
+                        }
+                    }
+                    (_, item) => rewrite_refs(item),
+                }
+            }
+        }
+        Value::Array(array) => array.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
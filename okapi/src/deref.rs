@@ -0,0 +1,185 @@
+use crate::openapi3::{
+    Callback, Components, Example, Header, Link, OpenApi, Parameter, RefOr, RequestBody, Response,
+    SecurityScheme,
+};
+use crate::Map;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// The maximum number of references that are followed to resolve a single reference.
+const MAX_REF_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+pub struct DerefError {
+    pub msg: String,
+}
+
+impl fmt::Display for DerefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for DerefError {}
+
+impl DerefError {
+    fn new<S: AsRef<str>>(msg: S) -> Self {
+        DerefError {
+            msg: msg.as_ref().to_owned(),
+        }
+    }
+}
+
+/// Objects that can be stored in the `Components` object, and thus be referenced with a `$ref`.
+pub trait ComponentsObject: Sized {
+    /// The path to the objects of this type in the document, like `#/components/responses/`.
+    const REF_PREFIX: &'static str;
+
+    /// The map in `components` that contains the objects of this type.
+    fn components_map(components: &Components) -> &Map<String, RefOr<Self>>;
+}
+
+macro_rules! impl_components_object {
+    ($($ty:ty => $field:ident, $name:literal;)*) => {
+        $(
+            impl ComponentsObject for $ty {
+                const REF_PREFIX: &'static str = concat!("#/components/", $name, "/");
+
+                fn components_map(components: &Components) -> &Map<String, RefOr<Self>> {
+                    &components.$field
+                }
+            }
+        )*
+    };
+}
+
+impl_components_object! {
+    Response => responses, "responses";
+    Parameter => parameters, "parameters";
+    Example => examples, "examples";
+    RequestBody => request_bodies, "requestBodies";
+    Header => headers, "headers";
+    SecurityScheme => security_schemes, "securitySchemes";
+    Link => links, "links";
+    Callback => callbacks, "callbacks";
+}
+
+impl<T: ComponentsObject> RefOr<T> {
+    /// Get the object, looking it up in `components` if this is a `$ref`.
+    /// References to other references are followed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Components, Ref, RefOr, Response};
+    ///
+    /// let mut components = Components::default();
+    /// components.responses.insert(
+    ///     "NotFound".to_owned(),
+    ///     RefOr::Object(Response {
+    ///         description: "Not found".to_owned(),
+    ///         ..Response::default()
+    ///     }),
+    /// );
+    /// let response: RefOr<Response> = RefOr::Ref(Ref {
+    ///     reference: "#/components/responses/NotFound".to_owned(),
+    /// });
+    ///
+    /// assert_eq!(response.resolve(&components).unwrap().description, "Not found");
+    /// ```
+    pub fn resolve<'a>(&'a self, components: &'a Components) -> Result<&'a T, DerefError> {
+        resolve(self, components)
+    }
+}
+
+/// Get the object, looking it up in `components` if it is a `$ref`.
+/// References to other references are followed.
+pub fn resolve<'a, T: ComponentsObject>(
+    ref_or: &'a RefOr<T>,
+    components: &'a Components,
+) -> Result<&'a T, DerefError> {
+    let mut current = ref_or;
+    for _ in 0..MAX_REF_DEPTH {
+        let reference = match current {
+            RefOr::Object(object) => return Ok(object),
+            RefOr::Ref(reference) => &reference.reference,
+        };
+        let name = reference.strip_prefix(T::REF_PREFIX).ok_or_else(|| {
+            DerefError::new(format!(
+                "Reference `{}` does not start with `{}`.",
+                reference,
+                T::REF_PREFIX
+            ))
+        })?;
+        current = T::components_map(components)
+            .get(name)
+            .ok_or_else(|| DerefError::new(format!("Reference `{}` does not exist.", reference)))?;
+    }
+    Err(DerefError::new("Too many nested references."))
+}
+
+/// Return a copy of the document where all local `$ref`s (`#/...`) are replaced by the object
+/// they point to. Other fields next to a `$ref` (like a `description`) are kept.
+///
+/// References that are part of a cycle (recursive schemas) can not be inlined and are kept as is.
+/// The `components` are not removed, so these references are still valid.
+pub fn inline_all_refs(spec: &OpenApi) -> Result<OpenApi, DerefError> {
+    let document = serde_json::to_value(spec)
+        .map_err(|err| DerefError::new(format!("Could not serialize document: {}", err)))?;
+    let mut inlined = document.clone();
+    inline_refs(&document, &mut inlined, &mut Vec::new())?;
+    serde_json::from_value(inlined)
+        .map_err(|err| DerefError::new(format!("Could not deserialize document: {}", err)))
+}
+
+fn inline_refs(
+    document: &Value,
+    value: &mut Value,
+    stack: &mut Vec<String>,
+) -> Result<(), DerefError> {
+    match value {
+        Value::Object(object) => {
+            let reference = match object.get("$ref") {
+                Some(Value::String(reference)) if reference.starts_with('#') => reference.clone(),
+                _ => {
+                    for item in object.values_mut() {
+                        inline_refs(document, item, stack)?;
+                    }
+                    return Ok(());
+                }
+            };
+            if stack.contains(&reference) {
+                // Recursive reference, keep the `$ref`.
+                return Ok(());
+            }
+            let mut target = document.pointer(&reference[1..]).cloned().ok_or_else(|| {
+                DerefError::new(format!("Reference `{}` does not exist.", reference))
+            })?;
+            stack.push(reference);
+            inline_refs(document, &mut target, stack)?;
+            stack.pop();
+            // Keep the fields that are defined next to the `$ref`.
+            object.remove("$ref");
+            let siblings = std::mem::take(object);
+            match target {
+                Value::Object(mut target) => {
+                    for (key, mut item) in siblings {
+                        inline_refs(document, &mut item, stack)?;
+                        target.insert(key, item);
+                    }
+                    *value = Value::Object(target);
+                }
+                target => *value = target,
+            }
+            Ok(())
+        }
+        Value::Array(array) => {
+            for item in array {
+                inline_refs(document, item, stack)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
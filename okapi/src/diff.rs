@@ -0,0 +1,836 @@
+use crate::openapi3::{
+    MediaType, OpenApi, Operation, Parameter, ParameterValue, RefOr, RequestBody, Response,
+};
+use crate::validate::{escape_pointer, operations};
+use crate::Map;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// The differences between two OpenAPI documents, as returned by [`diff`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Diff {
+    /// All changes that were found, in the order of the new document.
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// The changes that can break existing clients.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    /// Returns `true` if one of the changes can break existing clients.
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_changes().next().is_some()
+    }
+}
+
+/// A single difference between two OpenAPI documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// JSON Pointer to the location of the change in the document, like
+    /// `/paths/~1users/get/responses/200`.
+    pub location: String,
+    /// The kind of change.
+    pub kind: ChangeKind,
+    /// If `true`, the change can break existing clients.
+    pub breaking: bool,
+}
+
+/// The different kinds of changes found by [`diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// A path was added. Not breaking.
+    PathAdded,
+    /// A path was removed. Breaking.
+    PathRemoved,
+    /// An operation was added to an existing path. Not breaking.
+    OperationAdded,
+    /// An operation was removed from an existing path. Breaking.
+    OperationRemoved,
+    /// A parameter was added. Breaking if it is required.
+    ParameterAdded {
+        /// The name of the parameter.
+        name: String,
+        /// The location of the parameter (`query`, `path`, `header` or `cookie`).
+        location: String,
+    },
+    /// A parameter was removed. Not breaking, the value is ignored.
+    ParameterRemoved {
+        /// The name of the parameter.
+        name: String,
+        /// The location of the parameter (`query`, `path`, `header` or `cookie`).
+        location: String,
+    },
+    /// An optional parameter became required. Breaking.
+    ParameterRequired {
+        /// The name of the parameter.
+        name: String,
+        /// The location of the parameter (`query`, `path`, `header` or `cookie`).
+        location: String,
+    },
+    /// The request body became required. Breaking.
+    RequestBodyRequired,
+    /// A media type of a request body or response was added. Not breaking.
+    MediaTypeAdded(String),
+    /// A media type of a request body or response was removed. Breaking.
+    MediaTypeRemoved(String),
+    /// A response was added. Not breaking.
+    ResponseAdded(String),
+    /// A response was removed. Breaking.
+    ResponseRemoved(String),
+    /// The `type` of a schema changed. Breaking.
+    TypeChanged {
+        /// The old `type`, `None` if it was not set.
+        old: Option<String>,
+        /// The new `type`, `None` if it is not set.
+        new: Option<String>,
+    },
+    /// A property of a schema became required. Breaking in requests.
+    PropertyRequired(String),
+    /// A required property of a schema became optional. Breaking in responses.
+    PropertyOptional(String),
+    /// A property of a schema was added. Not breaking.
+    PropertyAdded(String),
+    /// A property of a schema was removed. Breaking in responses.
+    PropertyRemoved(String),
+    /// Values were removed from an `enum` (it was narrowed). Breaking in requests.
+    EnumValuesRemoved(Vec<Value>),
+    /// Values were added to an `enum`. Breaking in responses.
+    EnumValuesAdded(Vec<Value>),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |values: &[Value]| {
+            values
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if self.breaking {
+            write!(f, "Breaking: ")?;
+        }
+        match &self.kind {
+            ChangeKind::PathAdded => write!(f, "Path added")?,
+            ChangeKind::PathRemoved => write!(f, "Path removed")?,
+            ChangeKind::OperationAdded => write!(f, "Operation added")?,
+            ChangeKind::OperationRemoved => write!(f, "Operation removed")?,
+            ChangeKind::ParameterAdded { name, location } => {
+                write!(f, "Parameter `{}` added to `{}`", name, location)?
+            }
+            ChangeKind::ParameterRemoved { name, location } => {
+                write!(f, "Parameter `{}` removed from `{}`", name, location)?
+            }
+            ChangeKind::ParameterRequired { name, location } => {
+                write!(f, "Parameter `{}` in `{}` became required", name, location)?
+            }
+            ChangeKind::RequestBodyRequired => write!(f, "Request body became required")?,
+            ChangeKind::MediaTypeAdded(media_type) => {
+                write!(f, "Media type `{}` added", media_type)?
+            }
+            ChangeKind::MediaTypeRemoved(media_type) => {
+                write!(f, "Media type `{}` removed", media_type)?
+            }
+            ChangeKind::ResponseAdded(status) => write!(f, "Response `{}` added", status)?,
+            ChangeKind::ResponseRemoved(status) => write!(f, "Response `{}` removed", status)?,
+            ChangeKind::TypeChanged { old, new } => write!(
+                f,
+                "Type changed from `{}` to `{}`",
+                old.as_deref().unwrap_or("any"),
+                new.as_deref().unwrap_or("any")
+            )?,
+            ChangeKind::PropertyRequired(name) => write!(f, "Property `{}` became required", name)?,
+            ChangeKind::PropertyOptional(name) => write!(f, "Property `{}` became optional", name)?,
+            ChangeKind::PropertyAdded(name) => write!(f, "Property `{}` added", name)?,
+            ChangeKind::PropertyRemoved(name) => write!(f, "Property `{}` removed", name)?,
+            ChangeKind::EnumValuesRemoved(values) => {
+                write!(f, "Enum values removed: {}", list(values))?
+            }
+            ChangeKind::EnumValuesAdded(values) => {
+                write!(f, "Enum values added: {}", list(values))?
+            }
+        }
+        write!(f, " at `{}`.", self.location)
+    }
+}
+
+/// Compare two versions of a document, and report the changes that were made to the paths,
+/// operations, parameters, request bodies and responses (including their schemas).
+///
+/// Every change is classified as breaking or not. Schema changes depend on where the schema is
+/// used: for example, a new required property breaks clients sending a request body, but not
+/// clients reading it from a response.
+///
+/// To check for breaking changes before a release, load the last released document in a test
+/// and compare it to the current one: `assert!(!okapi::diff(&released, &current).is_breaking())`.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::{OpenApi, Operation, PathItem};
+/// use okapi::diff::ChangeKind;
+///
+/// let mut old = OpenApi::new();
+/// old.paths.insert(
+///     "/users".to_owned(),
+///     PathItem {
+///         get: Some(Operation::default()),
+///         ..PathItem::default()
+///     },
+/// );
+/// let new = OpenApi::new();
+///
+/// let diff = okapi::diff(&old, &new);
+/// assert!(diff.is_breaking());
+/// assert_eq!(diff.changes[0].location, "/paths/~1users");
+/// assert_eq!(diff.changes[0].kind, ChangeKind::PathRemoved);
+/// ```
+#[must_use]
+pub fn diff(old: &OpenApi, new: &OpenApi) -> Diff {
+    let mut differ = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+    };
+    differ.diff_paths();
+    Diff {
+        changes: differ.changes,
+    }
+}
+
+impl OpenApi {
+    /// Returns `true` if both documents describe the same API, even if they are written
+    /// differently. Unlike `==`, this ignores:
+    /// - The order of the keys in maps.
+    /// - Whether an object is defined inline or in `components` and used with a `$ref`.
+    ///   Components that are not used are ignored, except for `securitySchemes`.
+    /// - Whether an empty collection (like `tags: []` or `properties: {}`) is present or not.
+    ///
+    /// This is useful for snapshot tests, where `==` would report differences that do not
+    /// change the API.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Components, OpenApi, Operation, PathItem, Ref, RefOr, Response};
+    ///
+    /// let not_found = Response {
+    ///     description: "Not found".to_owned(),
+    ///     ..Response::default()
+    /// };
+    /// let spec_with = |response: RefOr<Response>| {
+    ///     let mut operation = Operation::default();
+    ///     operation.responses.responses.insert("404".to_owned(), response);
+    ///     let mut spec = OpenApi::new();
+    ///     spec.paths.insert(
+    ///         "/users".to_owned(),
+    ///         PathItem {
+    ///             get: Some(operation),
+    ///             ..PathItem::default()
+    ///         },
+    ///     );
+    ///     spec
+    /// };
+    ///
+    /// let spec = spec_with(RefOr::Object(not_found.clone()));
+    /// let mut other = spec_with(RefOr::Ref(Ref {
+    ///     reference: "#/components/responses/NotFound".to_owned(),
+    /// }));
+    /// let mut components = Components::default();
+    /// components
+    ///     .responses
+    ///     .insert("NotFound".to_owned(), RefOr::Object(not_found));
+    /// other.components = Some(components);
+    ///
+    /// assert_ne!(spec, other);
+    /// assert!(spec.semantically_equals(&other));
+    /// ```
+    #[must_use]
+    pub fn semantically_equals(&self, other: &OpenApi) -> bool {
+        let (mut spec, mut other) = match (serde_json::to_value(self), serde_json::to_value(other))
+        {
+            (Ok(spec), Ok(other)) => (spec, other),
+            _ => return false,
+        };
+        remove_empty(&mut spec, false);
+        remove_empty(&mut other, false);
+        let comparer = Comparer {
+            spec: &spec,
+            other: &other,
+        };
+        let mut assumed = HashSet::new();
+        // The other components are only compared where they are used.
+        let security_schemes = |spec: &Value| {
+            spec.pointer("/components/securitySchemes")
+                .cloned()
+                .unwrap_or_default()
+        };
+        let without_components = |spec: &Value| {
+            let mut spec = spec.clone();
+            if let Value::Object(object) = &mut spec {
+                object.remove("components");
+            }
+            spec
+        };
+        comparer.equals(
+            &security_schemes(&spec),
+            &security_schemes(&other),
+            &mut assumed,
+        ) && comparer.equals(
+            &without_components(&spec),
+            &without_components(&other),
+            &mut assumed,
+        )
+    }
+}
+
+/// The fields that contain a map, where the keys are names instead of field names.
+/// Empty objects in these maps are kept, as `{}` is a valid value (like a schema that accepts
+/// anything).
+const MAP_FIELDS: &[&str] = &[
+    "paths",
+    "webhooks",
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "content",
+    "encoding",
+    "variables",
+    "scopes",
+    "mapping",
+    "properties",
+    "patternProperties",
+    "definitions",
+];
+
+/// Compares two JSON values of two documents, where a `$ref` is equal to the object it points
+/// to.
+struct Comparer<'a> {
+    spec: &'a Value,
+    other: &'a Value,
+}
+
+impl<'a> Comparer<'a> {
+    /// `assumed` contains the pairs of values (identified by their `$ref`, or else their
+    /// content) that are already being compared. They are assumed to be equal, so recursive
+    /// schemas can be compared.
+    fn equals(&self, a: &Value, b: &Value, assumed: &mut HashSet<(String, String)>) -> bool {
+        let (a, a_ref) = match resolve_value(self.spec, a) {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+        let (b, b_ref) = match resolve_value(self.other, b) {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+        if a_ref.is_some() || b_ref.is_some() {
+            let key = |reference: Option<String>, value: &Value| {
+                reference.unwrap_or_else(|| value.to_string())
+            };
+            if !assumed.insert((key(a_ref, &a), key(b_ref, &b))) {
+                return true;
+            }
+        }
+        match (&a, &b) {
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, a)| match b.get(key) {
+                        Some(b) => self.equals(a, b, assumed),
+                        None => false,
+                    })
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| self.equals(a, b, assumed))
+            }
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// Follow the local `$ref`s in `value`, the fields next to a `$ref` are added to the object it
+/// points to. Returns the object and the last `$ref` that was followed, or `None` if a `$ref`
+/// does not exist.
+fn resolve_value(document: &Value, value: &Value) -> Option<(Value, Option<String>)> {
+    let mut value = value.clone();
+    let mut last_ref = None;
+    // Limit the depth, in case of a reference cycle.
+    for _ in 0..64 {
+        let reference = match value.get("$ref") {
+            Some(Value::String(reference)) if reference.starts_with('#') => reference.clone(),
+            _ => break,
+        };
+        let mut target = document.pointer(&reference[1..])?.clone();
+        if let (Value::Object(object), Value::Object(target)) = (&mut value, &mut target) {
+            object.remove("$ref");
+            for (key, item) in std::mem::take(object) {
+                target.insert(key, item);
+            }
+        }
+        value = target;
+        last_ref = Some(reference);
+    }
+    Some((value, last_ref))
+}
+
+/// Remove empty arrays, and empty `components` and objects in `MAP_FIELDS`. If `is_map` is `true`, `value` is one
+/// of the maps in `MAP_FIELDS`, and its (empty) entries are kept.
+fn remove_empty(value: &mut Value, is_map: bool) {
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object.iter_mut() {
+                remove_empty(item, !is_map && MAP_FIELDS.contains(&key.as_str()));
+            }
+            if !is_map {
+                object.retain(|key, item| match item {
+                    Value::Array(array) => !array.is_empty(),
+                    Value::Object(map) => {
+                        !map.is_empty()
+                            || !(key == "components" || MAP_FIELDS.contains(&key.as_str()))
+                    }
+                    _ => true,
+                });
+            }
+        }
+        Value::Array(array) => {
+            for item in array {
+                remove_empty(item, false);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether a schema describes data sent by the client, or data returned to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Differ<'a> {
+    old: &'a OpenApi,
+    new: &'a OpenApi,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    fn push(&mut self, location: &str, kind: ChangeKind, breaking: bool) {
+        self.changes.push(Change {
+            location: location.to_owned(),
+            kind,
+            breaking,
+        });
+    }
+
+    fn diff_paths(&mut self) {
+        for path in self.old.paths.keys() {
+            if !self.new.paths.contains_key(path) {
+                let location = format!("/paths/{}", escape_pointer(path));
+                self.push(&location, ChangeKind::PathRemoved, true);
+            }
+        }
+        for (path, new_item) in &self.new.paths {
+            let location = format!("/paths/{}", escape_pointer(path));
+            let old_item = match self.old.paths.get(path) {
+                Some(old_item) => old_item,
+                None => {
+                    self.push(&location, ChangeKind::PathAdded, false);
+                    continue;
+                }
+            };
+            let old_operations: Map<&str, &Operation> = operations(old_item).collect();
+            let new_operations: Map<&str, &Operation> = operations(new_item).collect();
+            for method in old_operations.keys() {
+                if !new_operations.contains_key(method) {
+                    let location = format!("{}/{}", location, method);
+                    self.push(&location, ChangeKind::OperationRemoved, true);
+                }
+            }
+            for (method, new_operation) in &new_operations {
+                let location = format!("{}/{}", location, method);
+                match old_operations.get(method) {
+                    Some(old_operation) => {
+                        let old_parameters = old_item.parameters.iter();
+                        let new_parameters = new_item.parameters.iter();
+                        self.diff_parameters(
+                            old_parameters.chain(&old_operation.parameters),
+                            new_parameters.chain(&new_operation.parameters),
+                            &location,
+                        );
+                        self.diff_request_body(old_operation, new_operation, &location);
+                        self.diff_responses(old_operation, new_operation, &location);
+                    }
+                    None => self.push(&location, ChangeKind::OperationAdded, false),
+                }
+            }
+        }
+    }
+
+    fn diff_parameters(
+        &mut self,
+        old: impl Iterator<Item = &'a RefOr<Parameter>>,
+        new: impl Iterator<Item = &'a RefOr<Parameter>>,
+        location: &str,
+    ) {
+        let old: Vec<&Parameter> = old
+            .filter_map(|parameter| self.old.resolve_parameter(parameter))
+            .collect();
+        let new: Vec<&Parameter> = new
+            .filter_map(|parameter| self.new.resolve_parameter(parameter))
+            .collect();
+        let find = |parameters: &[&'a Parameter], parameter: &Parameter| {
+            parameters
+                .iter()
+                .find(|p| p.name == parameter.name && p.location == parameter.location)
+                .copied()
+        };
+        for old_parameter in &old {
+            if find(&new, old_parameter).is_none() {
+                let kind = ChangeKind::ParameterRemoved {
+                    name: old_parameter.name.clone(),
+                    location: old_parameter.location.clone(),
+                };
+                self.push(&format!("{}/parameters", location), kind, false);
+            }
+        }
+        for new_parameter in &new {
+            let parameter_location = format!(
+                "{}/parameters/{}",
+                location,
+                escape_pointer(&new_parameter.name)
+            );
+            let old_parameter = match find(&old, new_parameter) {
+                Some(old_parameter) => old_parameter,
+                None => {
+                    let kind = ChangeKind::ParameterAdded {
+                        name: new_parameter.name.clone(),
+                        location: new_parameter.location.clone(),
+                    };
+                    self.push(&parameter_location, kind, new_parameter.required);
+                    continue;
+                }
+            };
+            if new_parameter.required && !old_parameter.required {
+                let kind = ChangeKind::ParameterRequired {
+                    name: new_parameter.name.clone(),
+                    location: new_parameter.location.clone(),
+                };
+                self.push(&parameter_location, kind, true);
+            }
+            match (&old_parameter.value, &new_parameter.value) {
+                (
+                    ParameterValue::Schema {
+                        schema: old_schema, ..
+                    },
+                    ParameterValue::Schema {
+                        schema: new_schema, ..
+                    },
+                ) => self.diff_schema(
+                    old_schema,
+                    new_schema,
+                    &format!("{}/schema", parameter_location),
+                    Direction::Request,
+                    &mut HashSet::new(),
+                ),
+                (
+                    ParameterValue::Content {
+                        content: old_content,
+                    },
+                    ParameterValue::Content {
+                        content: new_content,
+                    },
+                ) => self.diff_content(
+                    old_content,
+                    new_content,
+                    &format!("{}/content", parameter_location),
+                    Direction::Request,
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn diff_request_body(&mut self, old: &'a Operation, new: &'a Operation, location: &str) {
+        let location = format!("{}/requestBody", location);
+        let resolve = |spec: &'a OpenApi, body: &'a Option<RefOr<RequestBody>>| {
+            let body = body.as_ref()?;
+            match body {
+                RefOr::Object(body) => Some(body),
+                RefOr::Ref(_) => body.resolve(spec.components.as_ref()?).ok(),
+            }
+        };
+        let old_body = resolve(self.old, &old.request_body);
+        let new_body = resolve(self.new, &new.request_body);
+        match (old_body, new_body) {
+            (Some(old_body), Some(new_body)) => {
+                if new_body.required && !old_body.required {
+                    self.push(&location, ChangeKind::RequestBodyRequired, true);
+                }
+                self.diff_content(
+                    &old_body.content,
+                    &new_body.content,
+                    &format!("{}/content", location),
+                    Direction::Request,
+                );
+            }
+            (None, Some(new_body)) if new_body.required => {
+                self.push(&location, ChangeKind::RequestBodyRequired, true);
+            }
+            _ => {}
+        }
+    }
+
+    fn diff_responses(&mut self, old: &'a Operation, new: &'a Operation, location: &str) {
+        let responses = |operation: &'a Operation| {
+            let default = operation
+                .responses
+                .default
+                .as_ref()
+                .map(|response| ("default", response));
+            operation
+                .responses
+                .responses
+                .iter()
+                .map(|(status, response)| (status.as_str(), response))
+                .chain(default)
+                .collect::<Map<&str, &RefOr<Response>>>()
+        };
+        let old_responses = responses(old);
+        let new_responses = responses(new);
+        for status in old_responses.keys() {
+            if !new_responses.contains_key(status) {
+                let response_location =
+                    format!("{}/responses/{}", location, escape_pointer(status));
+                let kind = ChangeKind::ResponseRemoved((*status).to_owned());
+                self.push(&response_location, kind, true);
+            }
+        }
+        for (status, new_response) in &new_responses {
+            let response_location = format!("{}/responses/{}", location, escape_pointer(status));
+            let old_response = match old_responses.get(status) {
+                Some(old_response) => old_response,
+                None => {
+                    let kind = ChangeKind::ResponseAdded((*status).to_owned());
+                    self.push(&response_location, kind, false);
+                    continue;
+                }
+            };
+            let resolve = |spec: &'a OpenApi, response: &'a RefOr<Response>| match response {
+                RefOr::Object(response) => Some(response),
+                RefOr::Ref(_) => response.resolve(spec.components.as_ref()?).ok(),
+            };
+            if let (Some(old_response), Some(new_response)) = (
+                resolve(self.old, old_response),
+                resolve(self.new, new_response),
+            ) {
+                self.diff_content(
+                    &old_response.content,
+                    &new_response.content,
+                    &format!("{}/content", response_location),
+                    Direction::Response,
+                );
+            }
+        }
+    }
+
+    fn diff_content(
+        &mut self,
+        old: &'a Map<String, MediaType>,
+        new: &'a Map<String, MediaType>,
+        location: &str,
+        direction: Direction,
+    ) {
+        for media_type in old.keys() {
+            if !new.contains_key(media_type) {
+                let kind = ChangeKind::MediaTypeRemoved(media_type.clone());
+                self.push(location, kind, true);
+            }
+        }
+        for (media_type, new_content) in new {
+            let content_location = format!("{}/{}", location, escape_pointer(media_type));
+            match old.get(media_type) {
+                Some(old_content) => {
+                    if let (Some(old_schema), Some(new_schema)) =
+                        (&old_content.schema, &new_content.schema)
+                    {
+                        self.diff_schema(
+                            old_schema,
+                            new_schema,
+                            &format!("{}/schema", content_location),
+                            direction,
+                            &mut HashSet::new(),
+                        );
+                    }
+                }
+                None => {
+                    let kind = ChangeKind::MediaTypeAdded(media_type.clone());
+                    self.push(&content_location, kind, false);
+                }
+            }
+        }
+    }
+
+    /// Compare two schemas, `visited` contains the pairs of references that are already being
+    /// compared, to stop at recursive schemas.
+    fn diff_schema(
+        &mut self,
+        old: &'a SchemaObject,
+        new: &'a SchemaObject,
+        location: &str,
+        direction: Direction,
+        visited: &mut HashSet<(&'a str, &'a str)>,
+    ) {
+        if let (Some(old_ref), Some(new_ref)) = (&old.reference, &new.reference) {
+            if !visited.insert((old_ref.as_str(), new_ref.as_str())) {
+                return;
+            }
+        }
+        let old = resolve_schema(self.old, old);
+        let new = resolve_schema(self.new, new);
+        if old == new {
+            return;
+        }
+        let old_type = old.instance_type.as_ref().map(type_name);
+        let new_type = new.instance_type.as_ref().map(type_name);
+        if old_type != new_type && old_type.is_some() {
+            let kind = ChangeKind::TypeChanged {
+                old: old_type,
+                new: new_type,
+            };
+            self.push(location, kind, true);
+            return;
+        }
+        if let (Some(old_enum), Some(new_enum)) = (&old.enum_values, &new.enum_values) {
+            let removed: Vec<Value> = old_enum
+                .iter()
+                .filter(|value| !new_enum.contains(value))
+                .cloned()
+                .collect();
+            let added: Vec<Value> = new_enum
+                .iter()
+                .filter(|value| !old_enum.contains(value))
+                .cloned()
+                .collect();
+            if !removed.is_empty() {
+                let kind = ChangeKind::EnumValuesRemoved(removed);
+                self.push(location, kind, direction == Direction::Request);
+            }
+            if !added.is_empty() {
+                let kind = ChangeKind::EnumValuesAdded(added);
+                self.push(location, kind, direction == Direction::Response);
+            }
+        }
+        if let (Some(old_object), Some(new_object)) = (&old.object, &new.object) {
+            for name in &new_object.required {
+                if !old_object.required.contains(name) {
+                    let kind = ChangeKind::PropertyRequired(name.clone());
+                    self.push(location, kind, direction == Direction::Request);
+                }
+            }
+            for name in &old_object.required {
+                if !new_object.required.contains(name) && new_object.properties.contains_key(name) {
+                    let kind = ChangeKind::PropertyOptional(name.clone());
+                    self.push(location, kind, direction == Direction::Response);
+                }
+            }
+            for name in old_object.properties.keys() {
+                if !new_object.properties.contains_key(name) {
+                    let kind = ChangeKind::PropertyRemoved(name.clone());
+                    self.push(location, kind, direction == Direction::Response);
+                }
+            }
+            for (name, new_property) in &new_object.properties {
+                let property_location = format!("{}/properties/{}", location, escape_pointer(name));
+                match old_object.properties.get(name) {
+                    Some(Schema::Object(old_property)) => {
+                        if let Schema::Object(new_property) = new_property {
+                            self.diff_schema(
+                                old_property,
+                                new_property,
+                                &property_location,
+                                direction,
+                                visited,
+                            );
+                        }
+                    }
+                    Some(Schema::Bool(_)) => {}
+                    None if !new_object.required.contains(name) => {
+                        let kind = ChangeKind::PropertyAdded(name.clone());
+                        self.push(&property_location, kind, false);
+                    }
+                    // Reported as `PropertyRequired`.
+                    None => {}
+                }
+            }
+        }
+        if let (Some(old_array), Some(new_array)) = (&old.array, &new.array) {
+            if let (Some(SingleOrVec::Single(old_items)), Some(SingleOrVec::Single(new_items))) =
+                (&old_array.items, &new_array.items)
+            {
+                if let (Schema::Object(old_items), Schema::Object(new_items)) =
+                    (&**old_items, &**new_items)
+                {
+                    self.diff_schema(
+                        old_items,
+                        new_items,
+                        &format!("{}/items", location),
+                        direction,
+                        visited,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Follow `$ref`s to `components.schemas`, and the `allOf` with a single schema `Schemars`
+/// uses to add a description or `nullable` to a reference.
+fn resolve_schema<'a>(spec: &'a OpenApi, schema: &'a SchemaObject) -> &'a SchemaObject {
+    let mut schema = schema;
+    // Limit the depth, in case of a reference cycle.
+    for _ in 0..64 {
+        if let Some(reference) = &schema.reference {
+            let target = reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| spec.components.as_ref()?.schemas.get(name));
+            match target {
+                Some(target) => schema = target,
+                None => break,
+            }
+        } else {
+            match &schema.subschemas {
+                Some(subschemas) => match subschemas.all_of.as_deref() {
+                    Some([inner]) if schema.object.is_none() && schema.array.is_none() => {
+                        match inner {
+                            Schema::Object(inner) => schema = inner,
+                            Schema::Bool(_) => break,
+                        }
+                    }
+                    _ => break,
+                },
+                None => break,
+            }
+        }
+    }
+    schema
+}
+
+fn type_name(instance_type: &SingleOrVec<InstanceType>) -> String {
+    serde_json::to_value(instance_type)
+        .map(|value| match value {
+            Value::String(name) => name,
+            value => value.to_string(),
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,184 @@
+use crate::openapi3::OpenApi;
+use crate::Map;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::{json, Value};
+
+const SCHEMAS_PATH: &str = "#/components/schemas/";
+
+impl OpenApi {
+    /// Add a `discriminator` to the schemas of tagged enums, which many client generators need
+    /// to create polymorphic types.
+    ///
+    /// A schema in `components.schemas` is a tagged enum if it is a `oneOf` of object schemas
+    /// that all have a required string property with a single `enum` value (the tag), like the
+    /// schemas Schemars generates for enums with serde's internally (`#[serde(tag = "...")]`)
+    /// or adjacently (`#[serde(tag = "...", content = "...")]`) tagged representation.
+    /// The variants that are defined inline are moved to `components.schemas` (named after the
+    /// enum and their tag, like `PetCat`), so the `mapping` of the discriminator can reference
+    /// them. Enums whose variants would replace other schemas are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Components, OpenApi};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// #[serde(tag = "kind")]
+    /// enum Pet {
+    ///     Cat { lives: u8 },
+    ///     Dog { name: String },
+    /// }
+    ///
+    /// let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+    /// gen.subschema_for::<Pet>();
+    /// let mut spec = OpenApi::new();
+    /// spec.components = Some(Components {
+    ///     schemas: gen
+    ///         .take_definitions()
+    ///         .into_iter()
+    ///         .map(|(name, schema)| (name, schema.into_object()))
+    ///         .collect(),
+    ///     ..Components::default()
+    /// });
+    /// spec.add_discriminators();
+    ///
+    /// let schemas = &spec.components.unwrap().schemas;
+    /// assert_eq!(
+    ///     schemas["Pet"].extensions["discriminator"],
+    ///     serde_json::json!({
+    ///         "propertyName": "kind",
+    ///         "mapping": {
+    ///             "Cat": "#/components/schemas/PetCat",
+    ///             "Dog": "#/components/schemas/PetDog",
+    ///         },
+    ///     })
+    /// );
+    /// assert!(schemas.contains_key("PetCat"));
+    /// ```
+    pub fn add_discriminators(&mut self) {
+        let schemas = match &mut self.components {
+            Some(components) => &mut components.schemas,
+            None => return,
+        };
+        let names: Vec<String> = schemas.keys().cloned().collect();
+        for name in names {
+            if let Some((schema, variants)) = discriminated(schemas, &name) {
+                for (variant_name, variant) in variants {
+                    schemas.insert(variant_name, variant);
+                }
+                schemas.insert(name, schema);
+            }
+        }
+    }
+}
+
+/// Get the schema `name` with a discriminator, and the inline variants that are moved to
+/// `components.schemas`, if it is a tagged enum.
+fn discriminated(
+    schemas: &Map<String, SchemaObject>,
+    name: &str,
+) -> Option<(SchemaObject, Vec<(String, SchemaObject)>)> {
+    let schema = schemas.get(name)?;
+    if schema.extensions.contains_key("discriminator") {
+        return None;
+    }
+    let one_of = schema.subschemas.as_ref()?.one_of.as_ref()?;
+    // The variants, and the schemas they are defined by (to find the tag).
+    let variants = one_of
+        .iter()
+        .map(|variant| match variant {
+            Schema::Object(object) => match &object.reference {
+                Some(reference) => {
+                    let target = schemas.get(reference.strip_prefix(SCHEMAS_PATH)?)?;
+                    Some((variant, target))
+                }
+                None => Some((variant, object)),
+            },
+            Schema::Bool(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let (_, first) = variants.first()?;
+    let property = first.object.as_ref()?.properties.keys().find(|property| {
+        variants
+            .iter()
+            .all(|(_, variant)| tag_value(variant, property).is_some())
+    })?;
+
+    let mut schema = schema.clone();
+    let mut moved = Vec::new();
+    let mut mapping = serde_json::Map::new();
+    let mut one_of = Vec::new();
+    for (variant, target) in variants {
+        let value = tag_value(target, property)?;
+        let reference = match variant {
+            Schema::Object(SchemaObject {
+                reference: Some(reference),
+                ..
+            }) => reference.clone(),
+            _ => {
+                let variant_name = format!("{}{}", name, pascal_case(value));
+                if schemas.contains_key(&variant_name)
+                    || moved.iter().any(|(moved, _)| moved == &variant_name)
+                {
+                    return None;
+                }
+                let reference = format!("{}{}", SCHEMAS_PATH, variant_name);
+                moved.push((variant_name, target.clone()));
+                reference
+            }
+        };
+        if mapping
+            .insert(value.to_owned(), Value::String(reference.clone()))
+            .is_some()
+        {
+            // Two variants with the same tag can not be told apart.
+            return None;
+        }
+        one_of.push(Schema::Object(SchemaObject::new_ref(reference)));
+    }
+    schema.subschemas.as_mut()?.one_of = Some(one_of);
+    schema.extensions.insert(
+        "discriminator".to_owned(),
+        json!({
+            "propertyName": property,
+            "mapping": mapping,
+        }),
+    );
+    Some((schema, moved))
+}
+
+/// Get the value of the tag `property` of a variant: the single value of its `enum`, if the
+/// property is a required string.
+pub(crate) fn tag_value<'a>(variant: &'a SchemaObject, property: &str) -> Option<&'a str> {
+    let object = variant.object.as_ref()?;
+    if !object.required.contains(property) {
+        return None;
+    }
+    let tag = match object.properties.get(property)? {
+        Schema::Object(tag) => tag,
+        Schema::Bool(_) => return None,
+    };
+    if tag.instance_type != Some(SingleOrVec::Single(Box::new(InstanceType::String))) {
+        return None;
+    }
+    match tag.enum_values.as_deref()? {
+        [Value::String(value)] => Some(value),
+        _ => None,
+    }
+}
+
+/// Convert a tag like `dog-house` or `dog_house` to `DogHouse`, for the names of schemas.
+fn pascal_case(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
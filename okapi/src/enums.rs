@@ -0,0 +1,236 @@
+//! Change how the schemas of enums are written, for client generators (like
+//! `openapi-generator` and `swagger-codegen`) that reject the schemas `Schemars` generates for
+//! some serde enum representations. See [`OpenApi::convert_enums`].
+
+use crate::convert::{type_array_to_nullable, visit_spec_schemas};
+use crate::discriminator::tag_value;
+use crate::openapi3::OpenApi;
+use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject, SubschemaValidation};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::Value;
+
+/// How the schemas of enums are written. The default leaves them as `Schemars` generates them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EnumRepresentation {
+    /// The subschemas of untagged enums (`#[serde(untagged)]`).
+    pub untagged: UntaggedEnums,
+    /// Write adjacently tagged enums (`#[serde(tag = "t", content = "c")]`) as one object, with
+    /// all tags in the `enum` of the tag property and the contents in a `oneOf` of the content
+    /// property, instead of a `oneOf` with an object per variant. The default is `false`.
+    pub flatten_adjacently_tagged: bool,
+    /// Write `null` variants as `nullable: true` (like for `Option<T>` variants, or the unit
+    /// variants of untagged enums), and nullable references as `allOf` with the reference,
+    /// because OpenAPI 3.0 has no `null` type and ignores the siblings of a `$ref`. The default
+    /// is `false`.
+    pub nullable_variants: bool,
+}
+
+/// How the subschemas of untagged enums are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UntaggedEnums {
+    /// `anyOf`, as `Schemars` generates them: a value can match several variants, and serde
+    /// uses the first that matches.
+    #[default]
+    AnyOf,
+    /// `oneOf`, which more client generators support. This is only correct if a value can not
+    /// match several variants (like a number and a string).
+    OneOf,
+}
+
+impl OpenApi {
+    /// Rewrite the schemas of enums (in the whole document), as configured by `representation`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::enums::{EnumRepresentation, UntaggedEnums};
+    /// use okapi::openapi3::{Components, OpenApi};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// #[serde(tag = "t", content = "c")]
+    /// enum Message {
+    ///     Text(String),
+    ///     Ping,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// #[serde(untagged)]
+    /// enum Id {
+    ///     Number(u64),
+    ///     Name(String),
+    ///     None,
+    /// }
+    ///
+    /// let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+    /// gen.subschema_for::<Message>();
+    /// gen.subschema_for::<Id>();
+    /// let mut spec = OpenApi::new();
+    /// spec.components = Some(Components {
+    ///     schemas: gen
+    ///         .take_definitions()
+    ///         .into_iter()
+    ///         .map(|(name, schema)| (name, schema.into_object()))
+    ///         .collect(),
+    ///     ..Components::default()
+    /// });
+    /// spec.convert_enums(&EnumRepresentation {
+    ///     untagged: UntaggedEnums::OneOf,
+    ///     flatten_adjacently_tagged: true,
+    ///     nullable_variants: true,
+    /// });
+    ///
+    /// let schemas = serde_json::to_value(&spec.components.unwrap().schemas).unwrap();
+    /// assert_eq!(
+    ///     schemas["Message"],
+    ///     serde_json::json!({
+    ///         "type": "object",
+    ///         "required": ["t"],
+    ///         "properties": {
+    ///             "t": { "type": "string", "enum": ["Text", "Ping"] },
+    ///             "c": { "oneOf": [{ "type": "string" }] },
+    ///         },
+    ///     })
+    /// );
+    /// assert_eq!(schemas["Id"]["oneOf"].as_array().unwrap().len(), 2);
+    /// assert_eq!(schemas["Id"]["nullable"], true);
+    /// ```
+    pub fn convert_enums(&mut self, representation: &EnumRepresentation) {
+        if *representation != EnumRepresentation::default() {
+            visit_spec_schemas(self, &mut ConvertEnums(*representation));
+        }
+    }
+}
+
+/// Rewrites the schemas of enums.
+struct ConvertEnums(EnumRepresentation);
+
+impl Visitor for ConvertEnums {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        if self.0.untagged == UntaggedEnums::OneOf {
+            if let Some(subschemas) = &mut schema.subschemas {
+                if subschemas.one_of.is_none() {
+                    subschemas.one_of = subschemas.any_of.take();
+                }
+            }
+        }
+        if self.0.flatten_adjacently_tagged {
+            flatten_adjacently_tagged(schema);
+        }
+        if self.0.nullable_variants {
+            type_array_to_nullable(schema);
+            nullable_reference_to_all_of(schema);
+        }
+    }
+}
+
+/// Replace `{"$ref": ..., "nullable": true}` by `{"allOf": [{"$ref": ...}], "nullable": true}`.
+fn nullable_reference_to_all_of(schema: &mut SchemaObject) {
+    if schema.extensions.get("nullable") != Some(&Value::Bool(true)) {
+        return;
+    }
+    if let Some(reference) = schema.reference.take() {
+        schema.subschemas = Some(Box::new(SubschemaValidation {
+            all_of: Some(vec![Schema::Object(SchemaObject::new_ref(reference))]),
+            ..SubschemaValidation::default()
+        }));
+    }
+}
+
+/// Replace the `oneOf` of an adjacently tagged enum by one object.
+fn flatten_adjacently_tagged(schema: &mut SchemaObject) {
+    let one_of = match &schema.subschemas {
+        Some(subschemas)
+            if **subschemas
+                == SubschemaValidation {
+                    one_of: subschemas.one_of.clone(),
+                    ..SubschemaValidation::default()
+                } =>
+        {
+            subschemas.one_of.as_ref()
+        }
+        _ => None,
+    };
+    let variants = match one_of {
+        Some(one_of) => one_of
+            .iter()
+            .map(|variant| match variant {
+                Schema::Object(variant) if variant.reference.is_none() => Some(variant),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>(),
+        None => return,
+    };
+    let variants = match variants {
+        Some(variants) if !variants.is_empty() => variants,
+        _ => return,
+    };
+    let tag = match variants[0].object.as_ref().and_then(|object| {
+        object
+            .properties
+            .keys()
+            .find(|property| variants.iter().all(|v| tag_value(v, property).is_some()))
+    }) {
+        Some(tag) => tag.clone(),
+        None => return,
+    };
+
+    let mut content = None;
+    let mut tags = Vec::new();
+    let mut contents = Vec::new();
+    for variant in &variants {
+        let object = match &variant.object {
+            Some(object) => object,
+            None => return,
+        };
+        for (property, schema) in &object.properties {
+            if *property == tag {
+                continue;
+            }
+            // The variants can only have the tag and the content.
+            if content.get_or_insert_with(|| property.clone()) != property
+                || !object.required.contains(property)
+            {
+                return;
+            }
+            contents.push(schema.clone());
+        }
+        if object.properties.len() > 2 {
+            return;
+        }
+        tags.push(Value::from(tag_value(variant, &tag).unwrap_or_default()));
+    }
+    let content = match content {
+        Some(content) => content,
+        // Only unit variants, like an internally tagged enum without data.
+        None => return,
+    };
+
+    let mut object = ObjectValidation::default();
+    object.required.insert(tag.clone());
+    if contents.len() == variants.len() {
+        object.required.insert(content.clone());
+    }
+    object.properties.insert(
+        tag,
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(tags),
+            ..SchemaObject::default()
+        }),
+    );
+    object.properties.insert(
+        content,
+        Schema::Object(SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(contents),
+                ..SubschemaValidation::default()
+            })),
+            ..SchemaObject::default()
+        }),
+    );
+    schema.subschemas = None;
+    schema.instance_type = Some(InstanceType::Object.into());
+    schema.object = Some(Box::new(object));
+}
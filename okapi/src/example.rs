@@ -0,0 +1,297 @@
+//! Generate example values from the schemas of a document, for mock servers, exported
+//! collections, ...
+
+use crate::convert::visit_spec_schemas;
+use crate::openapi3::{ExampleValue, MediaType, OpenApi};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use schemars::visit::{visit_schema_object, Visitor};
+use serde_json::Value;
+
+/// Get an example value for a content of a request or response: its `example`, the first of its
+/// `examples`, or else a value generated from its schema with [`schema_example`].
+/// Returns `null` if the content has no example or schema.
+pub fn media_type_example(spec: &OpenApi, content: &MediaType) -> Value {
+    if let Some(example) = &content.example {
+        return example.clone();
+    }
+    let example = content
+        .examples
+        .iter()
+        .flat_map(|examples| examples.values())
+        .find_map(|example| match &example.value {
+            ExampleValue::Value(value) => Some(value.clone()),
+            ExampleValue::ExternalValue(_) => None,
+        });
+    if let Some(example) = example {
+        return example;
+    }
+    match &content.schema {
+        Some(schema) => schema_example(spec, schema),
+        None => Value::Null,
+    }
+}
+
+/// Generate a value that matches `schema`. References to `#/components/schemas/` are looked up
+/// in `spec`.
+///
+/// The first of the `examples` of a schema is used, or else its `default`, `const` or first
+/// `enum` value. Otherwise a value is generated from its type: `0` (or the `minimum`) for
+/// numbers, `true`, `"string"` (or an example of the `format`, like `1970-01-01` for `date`),
+/// an array with one item and an object with all properties. Only the first schema of `anyOf`
+/// and `oneOf` is used, and the objects of `allOf` are combined. A recursive reference generates
+/// `null` (or an empty array, as an item).
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::example::schema_example;
+/// use okapi::openapi3::OpenApi;
+/// use serde_json::json;
+///
+/// let schema = serde_json::from_value(json!({
+///     "type": "object",
+///     "properties": {
+///         "id": { "type": "integer", "minimum": 1 },
+///         "created": { "type": "string", "format": "date" },
+///         "tags": { "type": "array", "items": { "enum": ["new", "old"] } }
+///     }
+/// }))
+/// .unwrap();
+/// assert_eq!(
+///     schema_example(&OpenApi::default(), &schema),
+///     json!({ "id": 1, "created": "1970-01-01", "tags": ["new"] })
+/// );
+/// ```
+#[must_use]
+pub fn schema_example(spec: &OpenApi, schema: &SchemaObject) -> Value {
+    generate(spec, schema, &mut Vec::new())
+}
+
+/// Set the `example` of all schemas with a `default` (and no examples) to the default, so the
+/// documentation shows it as an example value too. `Schemars` writes the `default` of fields
+/// with `#[serde(default)]`, and of all fields of structs with `#[serde(default)]` (the value
+/// of their field in `Default::default()`).
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::example::add_default_examples;
+/// use okapi::openapi3::{Components, OpenApi};
+/// use schemars::JsonSchema;
+///
+/// fn page_size() -> u32 {
+///     20
+/// }
+///
+/// #[derive(JsonSchema)]
+/// struct Page {
+///     #[serde(default = "page_size")]
+///     size: u32,
+/// }
+///
+/// let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+/// gen.subschema_for::<Page>();
+/// let mut spec = OpenApi::new();
+/// spec.components = Some(Components {
+///     schemas: gen
+///         .take_definitions()
+///         .into_iter()
+///         .map(|(name, schema)| (name, schema.into_object()))
+///         .collect(),
+///     ..Components::default()
+/// });
+/// add_default_examples(&mut spec);
+///
+/// let schemas = serde_json::to_value(&spec.components.unwrap().schemas).unwrap();
+/// assert_eq!(schemas["Page"]["properties"]["size"]["default"], 20);
+/// assert_eq!(schemas["Page"]["properties"]["size"]["example"], 20);
+/// ```
+pub fn add_default_examples(spec: &mut OpenApi) {
+    visit_spec_schemas(spec, &mut DefaultExamples);
+}
+
+/// Copies the `default` of schemas to their `example`.
+struct DefaultExamples;
+
+impl Visitor for DefaultExamples {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        let default = match &schema.metadata {
+            Some(metadata) if metadata.examples.is_empty() => metadata.default.clone(),
+            _ => None,
+        };
+        if let Some(default) = default {
+            schema
+                .extensions
+                .entry("example".to_owned())
+                .or_insert(default);
+        }
+    }
+}
+
+/// Generate a value that matches `schema`. `refs` are the references that are being resolved.
+fn generate(spec: &OpenApi, schema: &SchemaObject, refs: &mut Vec<String>) -> Value {
+    if let Some(metadata) = &schema.metadata {
+        if let Some(example) = metadata.examples.first() {
+            return example.clone();
+        }
+        if let Some(default) = &metadata.default {
+            return default.clone();
+        }
+    }
+    if let Some(const_value) = &schema.const_value {
+        return const_value.clone();
+    }
+    if let Some(value) = schema
+        .enum_values
+        .as_ref()
+        .and_then(|values| values.first())
+    {
+        return value.clone();
+    }
+    if let Some(reference) = &schema.reference {
+        let target = reference
+            .strip_prefix("#/components/schemas/")
+            .and_then(|name| spec.components.as_ref()?.schemas.get(name));
+        return match target {
+            Some(target) if !refs.contains(reference) => {
+                refs.push(reference.clone());
+                let example = generate(spec, target, refs);
+                refs.pop();
+                example
+            }
+            _ => Value::Null,
+        };
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            // Combine the properties of all schemas.
+            let mut combined = serde_json::Map::new();
+            let mut last = Value::Null;
+            for subschema in all_of {
+                match subschema_example(spec, subschema, refs) {
+                    Value::Object(object) => combined.extend(object),
+                    value => last = value,
+                }
+            }
+            return if combined.is_empty() {
+                last
+            } else {
+                Value::Object(combined)
+            };
+        }
+        let first = subschemas
+            .any_of
+            .iter()
+            .chain(&subschemas.one_of)
+            .flatten()
+            .next();
+        if let Some(first) = first {
+            return subschema_example(spec, first, refs);
+        }
+    }
+    let instance_type = match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => **instance_type,
+        // Use the first type that is not `null`.
+        Some(SingleOrVec::Vec(types)) => types
+            .iter()
+            .copied()
+            .find(|instance_type| *instance_type != InstanceType::Null)
+            .unwrap_or(InstanceType::Null),
+        None if schema.object.is_some() => InstanceType::Object,
+        None if schema.array.is_some() => InstanceType::Array,
+        None => return Value::Null,
+    };
+    match instance_type {
+        InstanceType::Null => Value::Null,
+        InstanceType::Boolean => Value::Bool(true),
+        InstanceType::Integer | InstanceType::Number => {
+            let number = schema.number.as_ref();
+            let minimum = number.and_then(|number| number.minimum.or(number.exclusive_minimum));
+            let value = match minimum {
+                Some(minimum) if instance_type == InstanceType::Integer => minimum.ceil(),
+                Some(minimum) => minimum,
+                None => 0.0,
+            };
+            if instance_type == InstanceType::Integer {
+                Value::from(value as i64)
+            } else {
+                Value::from(value)
+            }
+        }
+        InstanceType::String => Value::String(string_example(schema)),
+        InstanceType::Array => {
+            let array = match &schema.array {
+                Some(array) => array,
+                None => return Value::Array(Vec::new()),
+            };
+            match &array.items {
+                Some(SingleOrVec::Single(items)) => {
+                    let item = subschema_example(spec, items, refs);
+                    let count = array.min_items.unwrap_or(1).max(1);
+                    if item.is_null() && array.min_items.unwrap_or(0) == 0 {
+                        // Don't repeat a recursive item.
+                        Value::Array(Vec::new())
+                    } else {
+                        Value::Array(vec![item; count as usize])
+                    }
+                }
+                Some(SingleOrVec::Vec(items)) => Value::Array(
+                    items
+                        .iter()
+                        .map(|item| subschema_example(spec, item, refs))
+                        .collect(),
+                ),
+                None => Value::Array(Vec::new()),
+            }
+        }
+        InstanceType::Object => {
+            let mut object = serde_json::Map::new();
+            if let Some(validation) = &schema.object {
+                for (name, property) in &validation.properties {
+                    let value = subschema_example(spec, property, refs);
+                    // Skip optional recursive properties.
+                    if value.is_null() && !validation.required.contains(name) {
+                        continue;
+                    }
+                    object.insert(name.clone(), value);
+                }
+            }
+            Value::Object(object)
+        }
+    }
+}
+
+fn subschema_example(spec: &OpenApi, schema: &Schema, refs: &mut Vec<String>) -> Value {
+    match schema {
+        Schema::Object(schema) => generate(spec, schema, refs),
+        Schema::Bool(_) => Value::Null,
+    }
+}
+
+fn string_example(schema: &SchemaObject) -> String {
+    let example = match schema.format.as_deref() {
+        Some("date-time") => "1970-01-01T00:00:00Z",
+        Some("date") => "1970-01-01",
+        Some("time") => "00:00:00",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("email") => "user@example.com",
+        Some("uri") | Some("url") => "https://example.com",
+        Some("ipv4") => "127.0.0.1",
+        Some("ipv6") => "::1",
+        Some("byte") => "AA==",
+        _ => "string",
+    };
+    let mut example = example.to_owned();
+    if let Some(string) = &schema.string {
+        if let Some(min_length) = string.min_length {
+            while (example.chars().count() as u32) < min_length {
+                example.push('x');
+            }
+        }
+        if let Some(max_length) = string.max_length {
+            example = example.chars().take(max_length as usize).collect();
+        }
+    }
+    example
+}
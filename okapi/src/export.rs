@@ -0,0 +1,1125 @@
+//! Export OpenAPI documents to the formats of other tools.
+
+use crate::example::{media_type_example, schema_example};
+use crate::merge::operations_mut;
+use crate::openapi3::{
+    OAuthFlows, OpenApi, Operation, ParameterValue, SecurityRequirement, SecurityScheme,
+    SecuritySchemeData,
+};
+use crate::validate::operations;
+use serde_json::{json, Map, Value};
+
+mod typescript;
+
+pub use typescript::typescript;
+
+/// The schema of the collections created by [`postman`].
+pub const POSTMAN_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// The URL that is used when the document has no servers.
+const DEFAULT_BASE_URL: &str = "http://localhost:8000";
+
+/// Create a [Postman](https://www.postman.com/) v2.1 collection with a request for every
+/// operation of the document.
+///
+/// The requests are put in a folder for their first tag (in the order of the `tags` of the
+/// document), requests without tags are not in a folder. The URLs start with the `{{baseUrl}}`
+/// variable, which is the URL of the first server of the document (or
+/// `http://localhost:8000`). Parameters and request bodies get the example values of
+/// [`crate::example`], and optional query parameters are disabled.
+///
+/// The security requirements of the document and of the operations are converted to the auth
+/// configuration of the collection and of the requests, using the first scheme that is required.
+/// Credentials are left as variables, like `{{bearerToken}}`, `{{apiKey}}` or
+/// `{{accessToken}}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+///
+/// let spec: OpenApi = serde_json::from_str(r#"{
+///     "openapi": "3.0.0",
+///     "info": { "title": "Users", "version": "1.0.0" },
+///     "paths": {
+///         "/users/{id}": {
+///             "get": {
+///                 "tags": ["users"],
+///                 "summary": "Get a user",
+///                 "parameters": [
+///                     { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+///                 ],
+///                 "responses": {}
+///             }
+///         }
+///     }
+/// }"#).unwrap();
+/// let collection = okapi::export::postman(&spec);
+/// let folder = &collection["item"][0];
+/// assert_eq!(folder["name"], "users");
+/// assert_eq!(folder["item"][0]["request"]["url"]["raw"], "{{baseUrl}}/users/:id");
+/// ```
+#[must_use]
+pub fn postman(spec: &OpenApi) -> Value {
+    let (folders, requests) = requests_by_tag(spec);
+    let folders = folders.into_iter().map(|folder| {
+        let items: Vec<Value> = folder.requests.iter().map(postman_item).collect();
+        let mut value = json!({ "name": folder.name, "item": items });
+        if let Some(description) = folder.description {
+            value["description"] = description.into();
+        }
+        value
+    });
+    let items = requests.iter().map(postman_item);
+
+    let mut info = json!({
+        "name": spec.info.title,
+        "schema": POSTMAN_SCHEMA,
+    });
+    if let Some(description) = &spec.info.description {
+        info["description"] = description.clone().into();
+    }
+    let mut collection = json!({
+        "info": info,
+        "item": folders.chain(items).collect::<Vec<_>>(),
+        "variable": [{ "key": "baseUrl", "value": base_url(spec) }],
+    });
+    if let Some(scheme) = security_scheme(spec, &spec.security) {
+        collection["auth"] = postman_auth(scheme);
+    }
+    collection
+}
+
+/// Create an [Insomnia](https://insomnia.rest/) v4 export with a workspace that contains a
+/// request for every operation of the document.
+///
+/// The requests are put in a folder for their first tag, like in [`postman`]. The URLs start
+/// with the `base_url` variable of the base environment, which is the URL of the first server
+/// of the document (or `http://localhost:8000`). Parameters and request bodies get the example
+/// values of [`crate::example`] (path parameters are part of the URL), and optional query
+/// parameters are disabled.
+///
+/// Insomnia has no auth configuration for the whole workspace, so every request gets the auth of
+/// the first security scheme that it requires. Credentials are left as variables, like
+/// `{{ _.bearerToken }}`, `{{ _.apiKey }}` or `{{ _.accessToken }}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+///
+/// let spec: OpenApi = serde_json::from_str(r#"{
+///     "openapi": "3.0.0",
+///     "info": { "title": "Users", "version": "1.0.0" },
+///     "paths": {
+///         "/users/{id}": {
+///             "get": {
+///                 "parameters": [
+///                     { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+///                 ],
+///                 "responses": {}
+///             }
+///         }
+///     }
+/// }"#).unwrap();
+/// let export = okapi::export::insomnia(&spec);
+/// let resources = export["resources"].as_array().unwrap();
+/// let request = resources.iter().find(|resource| resource["_type"] == "request").unwrap();
+/// assert_eq!(request["url"], "{{ _.base_url }}/users/0");
+/// ```
+#[must_use]
+pub fn insomnia(spec: &OpenApi) -> Value {
+    let workspace_id = "wrk_okapi";
+    let mut workspace = json!({
+        "_id": workspace_id,
+        "_type": "workspace",
+        "parentId": null,
+        "name": spec.info.title,
+        "scope": "collection",
+    });
+    if let Some(description) = &spec.info.description {
+        workspace["description"] = description.clone().into();
+    }
+    let mut resources = vec![
+        workspace,
+        json!({
+            "_id": "env_okapi",
+            "_type": "environment",
+            "parentId": workspace_id,
+            "name": "Base Environment",
+            "data": { "base_url": base_url(spec) },
+        }),
+    ];
+
+    let (folders, requests) = requests_by_tag(spec);
+    let mut request_count = 0;
+    let mut next_request = |parent_id: &str, request: &ExampleRequest| {
+        request_count += 1;
+        insomnia_request(&format!("req_okapi_{}", request_count), parent_id, request)
+    };
+    for (index, folder) in folders.into_iter().enumerate() {
+        let folder_id = format!("fld_okapi_{}", index + 1);
+        let mut value = json!({
+            "_id": folder_id,
+            "_type": "request_group",
+            "parentId": workspace_id,
+            "name": folder.name,
+        });
+        if let Some(description) = folder.description {
+            value["description"] = description.into();
+        }
+        resources.push(value);
+        for request in &folder.requests {
+            resources.push(next_request(&folder_id, request));
+        }
+    }
+    for request in &requests {
+        resources.push(next_request(workspace_id, request));
+    }
+
+    json!({
+        "_type": "export",
+        "__export_format": 4,
+        "__export_source": "okapi",
+        "resources": resources,
+    })
+}
+
+/// Create [HTTP request files](https://www.jetbrains.com/help/idea/http-client-in-product-code-editor.html)
+/// (`.http` files) with a request for every operation of the document, which can be run from
+/// the HTTP client of JetBrains IDEs and the REST Client extension of VS Code.
+///
+/// Returns a file for every tag, with the file name (like `users.http`) as key. The requests
+/// are put in the file of their first tag, requests without tags are in `untagged.http`.
+/// Every file starts with a `baseUrl` variable, which is the URL of the first server of the
+/// document (or `http://localhost:8000`). Parameters and request bodies get the example values
+/// of [`crate::example`], optional query parameters are left out.
+///
+/// The auth of the first security scheme a request requires is added as a header (or query
+/// parameter), with a variable for the credentials, like `{{bearerToken}}`, `{{apiKey}}` or
+/// `{{accessToken}}`.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+///
+/// let spec: OpenApi = serde_json::from_str(r#"{
+///     "openapi": "3.0.0",
+///     "info": { "title": "Users", "version": "1.0.0" },
+///     "paths": {
+///         "/users/{id}": {
+///             "get": {
+///                 "tags": ["users"],
+///                 "summary": "Get a user",
+///                 "parameters": [
+///                     { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+///                 ],
+///                 "responses": {}
+///             }
+///         }
+///     }
+/// }"#).unwrap();
+/// let files = okapi::export::http_files(&spec);
+/// assert_eq!(
+///     files["users.http"],
+///     "@baseUrl = http://localhost:8000\n\n### Get a user\nGET {{baseUrl}}/users/0\n"
+/// );
+/// ```
+#[must_use]
+pub fn http_files(spec: &OpenApi) -> crate::Map<String, String> {
+    let (folders, requests) = requests_by_tag(spec);
+    let mut files = crate::Map::new();
+    let folders = folders
+        .into_iter()
+        .map(|folder| (http_file_name(&folder.name), folder.requests));
+    let untagged =
+        Some(("untagged.http".to_owned(), requests)).filter(|(_, requests)| !requests.is_empty());
+    for (file_name, requests) in folders.chain(untagged) {
+        // Tags can have the same file name.
+        let file = files
+            .entry(file_name)
+            .or_insert_with(|| format!("@baseUrl = {}\n", base_url(spec)));
+        for request in &requests {
+            file.push('\n');
+            file.push_str(&http_request(request));
+        }
+    }
+    files
+}
+
+/// Add `x-codeSamples` to every operation of the document, with an example request in curl,
+/// Rust ([reqwest](https://docs.rs/reqwest)) and JavaScript (`fetch`). ReDoc shows them as tabs
+/// next to the operation.
+///
+/// The samples send the example values of [`crate::example`] to the URL of the first server of
+/// the document (or `http://localhost:8000`), and use placeholders like `<bearerToken>` for
+/// credentials. Operations that already have `x-codeSamples` are not changed.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+///
+/// let mut spec: OpenApi = serde_json::from_str(r#"{
+///     "openapi": "3.0.0",
+///     "info": { "title": "Users", "version": "1.0.0" },
+///     "paths": {
+///         "/users/{id}": {
+///             "delete": {
+///                 "parameters": [
+///                     { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+///                 ],
+///                 "responses": {}
+///             }
+///         }
+///     }
+/// }"#).unwrap();
+/// okapi::export::add_code_samples(&mut spec);
+/// let operation = spec.paths["/users/{id}"].delete.as_ref().unwrap();
+/// let samples = &operation.extensions["x-codeSamples"];
+/// assert_eq!(samples[0]["lang"], "Shell");
+/// assert_eq!(samples[0]["source"], "curl -X DELETE 'http://localhost:8000/users/0'");
+/// ```
+pub fn add_code_samples(spec: &mut OpenApi) {
+    let mut samples = Vec::new();
+    for (path, path_item) in &spec.paths {
+        for (method, operation) in operations(path_item) {
+            if operation.extensions.contains_key("x-codeSamples") {
+                continue;
+            }
+            let request = example_request(spec, path, method, operation);
+            let url = format!(
+                "{}{}",
+                base_url(spec).trim_end_matches('/'),
+                example_path(&request)
+            );
+            let value = json!([
+                { "lang": "Shell", "label": "curl", "source": curl_sample(&url, &request) },
+                { "lang": "Rust", "label": "reqwest", "source": reqwest_sample(&url, &request) },
+                { "lang": "JavaScript", "label": "fetch", "source": fetch_sample(&url, &request) },
+            ]);
+            samples.push((path.clone(), method, value));
+        }
+    }
+    for (path, method, value) in samples {
+        let operation = spec
+            .paths
+            .get_mut(&path)
+            .and_then(|path_item| {
+                operations_mut(path_item)
+                    .into_iter()
+                    .find(|(name, _)| *name == method)
+            })
+            .and_then(|(_, operation)| operation.as_mut());
+        if let Some(operation) = operation {
+            operation
+                .extensions
+                .insert("x-codeSamples".to_owned(), value);
+        }
+    }
+}
+
+/// An operation as a request with example values.
+struct ExampleRequest<'a> {
+    name: String,
+    description: Option<String>,
+    method: String,
+    /// The path template, like `/users/{id}`.
+    path: &'a str,
+    parameters: Vec<ExampleParameter>,
+    /// The media type and example value of the request body.
+    body: Option<(String, Value)>,
+    /// The first security scheme that is required, by the operation or else by the document.
+    security_scheme: Option<&'a SecurityScheme>,
+    /// Whether the operation replaces the security requirements of the document.
+    own_security: bool,
+}
+
+struct ExampleParameter {
+    name: String,
+    location: String,
+    value: String,
+    required: bool,
+    description: Option<String>,
+}
+
+/// The requests of a tag.
+struct Folder<'a> {
+    name: String,
+    description: Option<String>,
+    requests: Vec<ExampleRequest<'a>>,
+}
+
+/// Create the requests for all operations, grouped by their first tag (in the order of the
+/// `tags` of the document). Also returns the requests of operations without a tag.
+fn requests_by_tag(spec: &OpenApi) -> (Vec<Folder<'_>>, Vec<ExampleRequest<'_>>) {
+    let mut folders: Vec<Folder> = spec
+        .tags
+        .iter()
+        .map(|tag| Folder {
+            name: tag.name.clone(),
+            description: tag.description.clone(),
+            requests: Vec::new(),
+        })
+        .collect();
+    let mut requests = Vec::new();
+    for (path, path_item) in &spec.paths {
+        for (method, operation) in operations(path_item) {
+            let request = example_request(spec, path, method, operation);
+            let tag = match operation.tags.first() {
+                Some(tag) => tag,
+                None => {
+                    requests.push(request);
+                    continue;
+                }
+            };
+            match folders.iter_mut().find(|folder| &folder.name == tag) {
+                Some(folder) => folder.requests.push(request),
+                None => folders.push(Folder {
+                    name: tag.clone(),
+                    description: None,
+                    requests: vec![request],
+                }),
+            }
+        }
+    }
+    folders.retain(|folder| !folder.requests.is_empty());
+    (folders, requests)
+}
+
+fn example_request<'a>(
+    spec: &'a OpenApi,
+    path: &'a str,
+    method: &str,
+    operation: &'a Operation,
+) -> ExampleRequest<'a> {
+    let mut parameters = Vec::new();
+    for parameter in &operation.parameters {
+        let parameter = match spec.resolve_parameter(parameter) {
+            Some(parameter) => parameter,
+            None => continue,
+        };
+        let example = match &parameter.value {
+            ParameterValue::Schema {
+                example: Some(example),
+                ..
+            } => example.clone(),
+            ParameterValue::Schema { schema, .. } => schema_example(spec, schema),
+            ParameterValue::Content { content } => content
+                .values()
+                .next()
+                .map_or(Value::Null, |content| media_type_example(spec, content)),
+        };
+        parameters.push(ExampleParameter {
+            name: parameter.name.clone(),
+            location: parameter.location.clone(),
+            value: value_to_string(example),
+            required: parameter.required,
+            description: parameter.description.clone(),
+        });
+    }
+
+    let components = spec.components.clone().unwrap_or_default();
+    let body = operation
+        .request_body
+        .as_ref()
+        .and_then(|body| body.resolve(&components).ok())
+        .and_then(|body| {
+            let (media_type, content) = body
+                .content
+                .get_key_value("application/json")
+                .or_else(|| body.content.iter().next())?;
+            Some((media_type.clone(), media_type_example(spec, content)))
+        });
+
+    ExampleRequest {
+        name: operation
+            .summary
+            .clone()
+            .or_else(|| operation.operation_id.clone())
+            .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path)),
+        description: operation
+            .description
+            .clone()
+            .or_else(|| operation.summary.clone()),
+        method: method.to_uppercase(),
+        path,
+        parameters,
+        body,
+        security_scheme: security_scheme(
+            spec,
+            operation.security.as_ref().unwrap_or(&spec.security),
+        ),
+        own_security: operation.security.is_some(),
+    }
+}
+
+/// Write an example value as it is sent in a parameter or form field.
+fn value_to_string(value: Value) -> String {
+    match value {
+        Value::String(string) => string,
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Get the path of a request with the example values of its path parameters, like `/users/0`.
+fn example_path(request: &ExampleRequest) -> String {
+    let mut path = request.path.trim_end_matches('/').to_owned();
+    for parameter in &request.parameters {
+        if parameter.location == "path" {
+            path = path.replace(&format!("{{{}}}", parameter.name), &parameter.value);
+        }
+    }
+    path
+}
+
+/// The query parameters and headers of a request, including its auth (except for basic auth).
+struct RequestParts {
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    /// Whether the request uses basic auth, which every format writes in its own way.
+    basic_auth: bool,
+}
+
+/// Get the required query parameters and the headers of a request. Cookies are combined in a
+/// `Cookie` header. `variable` writes a placeholder for a credential, like `apiKey`.
+fn request_parts(request: &ExampleRequest, variable: impl Fn(&str) -> String) -> RequestParts {
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    let mut cookies = Vec::new();
+    for parameter in &request.parameters {
+        let entry = (parameter.name.clone(), parameter.value.clone());
+        match parameter.location.as_str() {
+            "query" if parameter.required => query.push(entry),
+            "header" => headers.push(entry),
+            "cookie" => cookies.push(format!("{}={}", entry.0, entry.1)),
+            _ => {}
+        }
+    }
+    let mut basic_auth = false;
+    if let Some(scheme) = request.security_scheme {
+        match &scheme.data {
+            SecuritySchemeData::ApiKey { name, location } => match location.as_str() {
+                "query" => query.push((name.clone(), variable("apiKey"))),
+                "cookie" => cookies.push(format!("{}={}", name, variable("apiKey"))),
+                _ => headers.push((name.clone(), variable("apiKey"))),
+            },
+            SecuritySchemeData::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => {
+                basic_auth = true;
+            }
+            SecuritySchemeData::Http { .. } => headers.push((
+                "Authorization".to_owned(),
+                format!("Bearer {}", variable("bearerToken")),
+            )),
+            SecuritySchemeData::OAuth2 { .. } | SecuritySchemeData::OpenIdConnect { .. } => headers
+                .push((
+                    "Authorization".to_owned(),
+                    format!("Bearer {}", variable("accessToken")),
+                )),
+        }
+    }
+    if !cookies.is_empty() {
+        headers.push(("Cookie".to_owned(), cookies.join("; ")));
+    }
+    RequestParts {
+        query,
+        headers,
+        basic_auth,
+    }
+}
+
+fn is_json(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Write the example value of a body, for the media types that are not forms.
+fn raw_body(media_type: &str, example: Value) -> String {
+    match example {
+        Value::String(string) if !is_json(media_type) => string,
+        Value::Null if !is_json(media_type) => String::new(),
+        example => serde_json::to_string_pretty(&example).unwrap_or_default(),
+    }
+}
+
+/// Get the fields of a form from its example value.
+fn form_fields(example: Value) -> Vec<(String, String)> {
+    match example {
+        Value::Object(object) => object
+            .into_iter()
+            .map(|(key, value)| (key, value_to_string(value)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn base_url(spec: &OpenApi) -> &str {
+    spec.servers
+        .first()
+        .map_or(DEFAULT_BASE_URL, |server| server.url.as_str())
+}
+
+/// Get the first scheme of the security requirements. Returns `None` if nothing is required.
+fn security_scheme<'a>(
+    spec: &'a OpenApi,
+    security: &[SecurityRequirement],
+) -> Option<&'a SecurityScheme> {
+    let name = security
+        .iter()
+        .flat_map(|requirement| requirement.keys())
+        .next()?;
+    let components = spec.components.as_ref()?;
+    components
+        .security_schemes
+        .get(name)?
+        .resolve(components)
+        .ok()
+}
+
+/// Create the item of a Postman collection for a request.
+fn postman_item(request: &ExampleRequest) -> Value {
+    let path_segments: Vec<String> = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            // `{id}` is written as `:id`.
+            segment.replace('{', ":").replace('}', "")
+        })
+        .collect();
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    let mut variables = Vec::new();
+    for parameter in &request.parameters {
+        let mut entry = json!({ "key": parameter.name, "value": parameter.value });
+        if let Some(description) = &parameter.description {
+            entry["description"] = description.clone().into();
+        }
+        match parameter.location.as_str() {
+            "path" => variables.push(entry),
+            "query" => {
+                if !parameter.required {
+                    entry["disabled"] = true.into();
+                }
+                query.push(entry);
+            }
+            "header" => headers.push(entry),
+            // Postman sets cookies per domain, not per request.
+            _ => {}
+        }
+    }
+
+    let mut value = json!({ "method": request.method });
+    if let Some((media_type, example)) = &request.body {
+        headers.push(json!({ "key": "Content-Type", "value": media_type }));
+        value["body"] = postman_body(media_type, example.clone());
+    }
+
+    let mut raw = format!("{{{{baseUrl}}}}/{}", path_segments.join("/"));
+    let enabled_query: Vec<String> = request
+        .parameters
+        .iter()
+        .filter(|parameter| parameter.location == "query" && parameter.required)
+        .map(|parameter| format!("{}={}", parameter.name, parameter.value))
+        .collect();
+    if !enabled_query.is_empty() {
+        raw.push('?');
+        raw.push_str(&enabled_query.join("&"));
+    }
+    let mut url = json!({
+        "raw": raw,
+        "host": ["{{baseUrl}}"],
+        "path": path_segments,
+    });
+    if !query.is_empty() {
+        url["query"] = query.into();
+    }
+    if !variables.is_empty() {
+        url["variable"] = variables.into();
+    }
+    value["url"] = url;
+    value["header"] = headers.into();
+    if let Some(description) = &request.description {
+        value["description"] = description.clone().into();
+    }
+    if request.own_security {
+        value["auth"] = match request.security_scheme {
+            Some(scheme) => postman_auth(scheme),
+            None => json!({ "type": "noauth" }),
+        };
+    }
+    json!({ "name": request.name, "request": value, "response": [] })
+}
+
+/// Create the body of a Postman request from an example value.
+fn postman_body(media_type: &str, example: Value) -> Value {
+    let fields = || -> Vec<Value> {
+        form_fields(example.clone())
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect()
+    };
+    match media_type {
+        "application/x-www-form-urlencoded" => {
+            json!({ "mode": "urlencoded", "urlencoded": fields() })
+        }
+        "multipart/form-data" => json!({ "mode": "formdata", "formdata": fields() }),
+        _ => {
+            let mut body = json!({ "mode": "raw", "raw": raw_body(media_type, example.clone()) });
+            if is_json(media_type) {
+                body["options"] = json!({ "raw": { "language": "json" } });
+            }
+            body
+        }
+    }
+}
+
+/// Create the Postman auth configuration for a security scheme.
+fn postman_auth(scheme: &SecurityScheme) -> Value {
+    let (kind, values): (&str, Vec<(&str, String)>) = match &scheme.data {
+        SecuritySchemeData::ApiKey { name, location } => (
+            "apikey",
+            vec![
+                ("key", name.clone()),
+                ("value", "{{apiKey}}".to_owned()),
+                ("in", location.clone()),
+            ],
+        ),
+        SecuritySchemeData::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => (
+            "basic",
+            vec![
+                ("username", "{{username}}".to_owned()),
+                ("password", "{{password}}".to_owned()),
+            ],
+        ),
+        SecuritySchemeData::Http { .. } => {
+            ("bearer", vec![("token", "{{bearerToken}}".to_owned())])
+        }
+        SecuritySchemeData::OAuth2 { .. } | SecuritySchemeData::OpenIdConnect { .. } => (
+            "oauth2",
+            vec![
+                ("accessToken", "{{accessToken}}".to_owned()),
+                ("addTokenTo", "header".to_owned()),
+            ],
+        ),
+    };
+    let values: Vec<Value> = values
+        .into_iter()
+        .map(|(key, value)| json!({ "key": key, "value": value, "type": "string" }))
+        .collect();
+    let mut auth = Map::new();
+    auth.insert("type".to_owned(), kind.into());
+    auth.insert(kind.to_owned(), values.into());
+    Value::Object(auth)
+}
+
+/// Create the resource of an Insomnia export for a request.
+fn insomnia_request(id: &str, parent_id: &str, request: &ExampleRequest) -> Value {
+    let url = format!("{{{{ _.base_url }}}}{}", example_path(request));
+    let mut parameters = Vec::new();
+    let mut headers = Vec::new();
+    for parameter in &request.parameters {
+        let mut entry = json!({ "name": parameter.name, "value": parameter.value });
+        if let Some(description) = &parameter.description {
+            entry["description"] = description.clone().into();
+        }
+        match parameter.location.as_str() {
+            "query" => {
+                if !parameter.required {
+                    entry["disabled"] = true.into();
+                }
+                parameters.push(entry);
+            }
+            "header" => headers.push(entry),
+            // Path parameters are part of the URL, cookies are set in the cookie jar.
+            _ => {}
+        }
+    }
+
+    let mut body = json!({});
+    if let Some((media_type, example)) = &request.body {
+        headers.push(json!({ "name": "Content-Type", "value": media_type }));
+        body = match media_type.as_str() {
+            "application/x-www-form-urlencoded" | "multipart/form-data" => {
+                let fields: Vec<Value> = form_fields(example.clone())
+                    .into_iter()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect();
+                json!({ "mimeType": media_type, "params": fields })
+            }
+            _ => json!({ "mimeType": media_type, "text": raw_body(media_type, example.clone()) }),
+        };
+    }
+
+    let authentication = match request.security_scheme {
+        Some(scheme) => insomnia_auth(scheme),
+        None => json!({}),
+    };
+    json!({
+        "_id": id,
+        "_type": "request",
+        "parentId": parent_id,
+        "name": request.name,
+        "description": request.description.clone().unwrap_or_default(),
+        "method": request.method,
+        "url": url,
+        "parameters": parameters,
+        "headers": headers,
+        "body": body,
+        "authentication": authentication,
+    })
+}
+
+/// Create the Insomnia authentication of a security scheme.
+fn insomnia_auth(scheme: &SecurityScheme) -> Value {
+    match &scheme.data {
+        SecuritySchemeData::ApiKey { name, location } => json!({
+            "type": "apikey",
+            "key": name,
+            "value": "{{ _.apiKey }}",
+            "addTo": if location == "query" { "queryParams" } else { "header" },
+        }),
+        SecuritySchemeData::Http { scheme, .. } if scheme.eq_ignore_ascii_case("basic") => json!({
+            "type": "basic",
+            "username": "{{ _.username }}",
+            "password": "{{ _.password }}",
+        }),
+        SecuritySchemeData::Http { .. } | SecuritySchemeData::OpenIdConnect { .. } => json!({
+            "type": "bearer",
+            "token": "{{ _.bearerToken }}",
+        }),
+        SecuritySchemeData::OAuth2 { flows } => {
+            let mut auth = json!({ "type": "oauth2", "accessToken": "{{ _.accessToken }}" });
+            let (grant_type, authorization_url, token_url) = match flows {
+                OAuthFlows::Implicit {
+                    authorization_url, ..
+                } => ("implicit", Some(authorization_url), None),
+                OAuthFlows::Password { token_url, .. } => ("password", None, Some(token_url)),
+                OAuthFlows::ClientCredentials { token_url, .. } => {
+                    ("client_credentials", None, Some(token_url))
+                }
+                OAuthFlows::AuthorizationCode {
+                    authorization_url,
+                    token_url,
+                    ..
+                } => (
+                    "authorization_code",
+                    Some(authorization_url),
+                    Some(token_url),
+                ),
+            };
+            auth["grantType"] = grant_type.into();
+            if let Some(authorization_url) = authorization_url {
+                auth["authorizationUrl"] = authorization_url.clone().into();
+            }
+            if let Some(token_url) = token_url {
+                auth["accessTokenUrl"] = token_url.clone().into();
+            }
+            auth
+        }
+    }
+}
+
+/// Get the name of the `.http` file of a tag, like `user-accounts.http` for `User accounts`.
+fn http_file_name(tag: &str) -> String {
+    let mut name = String::new();
+    for c in tag.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            name.extend(c.to_lowercase());
+        } else if !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "tag.http".to_owned()
+    } else {
+        format!("{}.http", name)
+    }
+}
+
+/// Write a request of an `.http` file.
+fn http_request(request: &ExampleRequest) -> String {
+    let parts = request_parts(request, |name| format!("{{{{{}}}}}", name));
+    let query: Vec<String> = parts
+        .query
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    let mut headers: Vec<String> = parts
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+    if parts.basic_auth {
+        headers.push("Authorization: Basic {{username}} {{password}}".to_owned());
+    }
+
+    let mut text = format!("### {}\n", request.name);
+    if let Some(description) = &request.description {
+        if description != &request.name {
+            for line in description.lines() {
+                text.push_str(format!("# {}", line).trim_end());
+                text.push('\n');
+            }
+        }
+    }
+    text.push_str(&format!(
+        "{} {{{{baseUrl}}}}{}",
+        request.method,
+        example_path(request)
+    ));
+    if !query.is_empty() {
+        text.push('?');
+        text.push_str(&query.join("&"));
+    }
+    text.push('\n');
+    let body = request.body.as_ref().map(|(media_type, example)| {
+        headers.push(format!("Content-Type: {}", media_type));
+        match media_type.as_str() {
+            "application/x-www-form-urlencoded" => form_fields(example.clone())
+                .into_iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("&"),
+            _ => raw_body(media_type, example.clone()),
+        }
+    });
+    for header in headers {
+        text.push_str(&header);
+        text.push('\n');
+    }
+    if let Some(body) = body {
+        text.push('\n');
+        text.push_str(&body);
+        text.push('\n');
+    }
+    text
+}
+
+/// Percent-encode a query parameter name or value.
+fn encode_query(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Get the URL of a request with its query parameters.
+fn url_with_query(url: &str, query: &[(String, String)]) -> String {
+    if query.is_empty() {
+        return url.to_owned();
+    }
+    let query: Vec<String> = query
+        .iter()
+        .map(|(name, value)| format!("{}={}", encode_query(name), encode_query(value)))
+        .collect();
+    format!("{}?{}", url, query.join("&"))
+}
+
+/// Quote a shell argument with single quotes.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Write a JSON string literal, which is also a string literal in Rust and JavaScript.
+fn string_literal(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+fn curl_sample(url: &str, request: &ExampleRequest) -> String {
+    let parts = request_parts(request, |name| format!("<{}>", name));
+    let mut lines = vec![format!(
+        "curl -X {} {}",
+        request.method,
+        shell_quote(&url_with_query(url, &parts.query))
+    )];
+    if parts.basic_auth {
+        lines.push(format!("-u {}", shell_quote("<username>:<password>")));
+    }
+    for (name, value) in &parts.headers {
+        lines.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some((media_type, example)) = &request.body {
+        lines.push(format!(
+            "-H {}",
+            shell_quote(&format!("Content-Type: {}", media_type))
+        ));
+        match media_type.as_str() {
+            "application/x-www-form-urlencoded" => {
+                for (name, value) in form_fields(example.clone()) {
+                    lines.push(format!(
+                        "--data-urlencode {}",
+                        shell_quote(&format!("{}={}", name, value))
+                    ));
+                }
+            }
+            "multipart/form-data" => {
+                // curl sets the `Content-Type` (with the boundary) itself.
+                lines.pop();
+                for (name, value) in form_fields(example.clone()) {
+                    lines.push(format!(
+                        "-F {}",
+                        shell_quote(&format!("{}={}", name, value))
+                    ));
+                }
+            }
+            _ => lines.push(format!(
+                "-d {}",
+                shell_quote(&raw_body(media_type, example.clone()))
+            )),
+        }
+    }
+    lines.join(" \\\n  ")
+}
+
+fn reqwest_sample(url: &str, request: &ExampleRequest) -> String {
+    let parts = request_parts(request, |name| format!("<{}>", name));
+    let method = request.method.to_lowercase();
+    let mut lines = vec!["let client = reqwest::Client::new();".to_owned()];
+    lines.push("let response = client".to_owned());
+    match method.as_str() {
+        "get" | "put" | "post" | "delete" | "patch" | "head" => {
+            lines.push(format!("    .{}({})", method, string_literal(url)))
+        }
+        _ => lines.push(format!(
+            "    .request(reqwest::Method::{}, {})",
+            request.method,
+            string_literal(url)
+        )),
+    }
+    if !parts.query.is_empty() {
+        let query: Vec<String> = parts
+            .query
+            .iter()
+            .map(|(name, value)| format!("({}, {})", string_literal(name), string_literal(value)))
+            .collect();
+        lines.push(format!("    .query(&[{}])", query.join(", ")));
+    }
+    if parts.basic_auth {
+        lines.push("    .basic_auth(\"<username>\", Some(\"<password>\"))".to_owned());
+    }
+    for (name, value) in &parts.headers {
+        lines.push(format!(
+            "    .header({}, {})",
+            string_literal(name),
+            string_literal(value)
+        ));
+    }
+    if let Some((media_type, example)) = &request.body {
+        match media_type.as_str() {
+            "application/x-www-form-urlencoded" => {
+                let fields: Vec<String> = form_fields(example.clone())
+                    .iter()
+                    .map(|(name, value)| {
+                        format!("({}, {})", string_literal(name), string_literal(value))
+                    })
+                    .collect();
+                lines.push(format!("    .form(&[{}])", fields.join(", ")));
+            }
+            _ if is_json(media_type) => {
+                let json = serde_json::to_string_pretty(example)
+                    .unwrap_or_default()
+                    .replace('\n', "\n    ");
+                if media_type != "application/json" {
+                    lines.push(format!(
+                        "    .header(\"Content-Type\", {})",
+                        string_literal(media_type)
+                    ));
+                }
+                lines.push(format!("    .json(&serde_json::json!({}))", json));
+            }
+            _ => {
+                lines.push(format!(
+                    "    .header(\"Content-Type\", {})",
+                    string_literal(media_type)
+                ));
+                lines.push(format!(
+                    "    .body({})",
+                    string_literal(&raw_body(media_type, example.clone()))
+                ));
+            }
+        }
+    }
+    lines.push("    .send()".to_owned());
+    lines.push("    .await?;".to_owned());
+    lines.join("\n")
+}
+
+fn fetch_sample(url: &str, request: &ExampleRequest) -> String {
+    let parts = request_parts(request, |name| format!("<{}>", name));
+    let mut headers: Vec<String> = parts
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", string_literal(name), string_literal(value)))
+        .collect();
+    if parts.basic_auth {
+        headers.push("\"Authorization\": \"Basic \" + btoa(\"<username>:<password>\")".to_owned());
+    }
+    let mut prelude = Vec::new();
+    let mut body = None;
+    if let Some((media_type, example)) = &request.body {
+        match media_type.as_str() {
+            "multipart/form-data" => {
+                // `fetch` sets the `Content-Type` (with the boundary) itself.
+                prelude.push("const form = new FormData();".to_owned());
+                for (name, value) in form_fields(example.clone()) {
+                    prelude.push(format!(
+                        "form.append({}, {});",
+                        string_literal(&name),
+                        string_literal(&value)
+                    ));
+                }
+                body = Some("form".to_owned());
+            }
+            "application/x-www-form-urlencoded" => {
+                let fields: Vec<String> = form_fields(example.clone())
+                    .iter()
+                    .map(|(name, value)| {
+                        format!("{}: {}", string_literal(name), string_literal(value))
+                    })
+                    .collect();
+                body = Some(format!("new URLSearchParams({{ {} }})", fields.join(", ")));
+            }
+            _ => {
+                headers.push(format!("\"Content-Type\": {}", string_literal(media_type)));
+                body = Some(if is_json(media_type) {
+                    let json = serde_json::to_string_pretty(example)
+                        .unwrap_or_default()
+                        .replace('\n', "\n  ");
+                    format!("JSON.stringify({})", json)
+                } else {
+                    string_literal(&raw_body(media_type, example.clone()))
+                });
+            }
+        }
+    }
+
+    let mut options = vec![format!("  method: {},", string_literal(&request.method))];
+    if !headers.is_empty() {
+        options.push(format!(
+            "  headers: {{\n    {},\n  }},",
+            headers.join(",\n    ")
+        ));
+    }
+    if let Some(body) = body {
+        options.push(format!("  body: {},", body));
+    }
+    prelude.push(format!(
+        "const response = await fetch({}, {{\n{}\n}});",
+        string_literal(&url_with_query(url, &parts.query)),
+        options.join("\n")
+    ));
+    prelude.join("\n")
+}
@@ -0,0 +1,305 @@
+use crate::openapi3::OpenApi;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+/// Create TypeScript declarations (a `.d.ts` file) for the `components.schemas` of the document.
+///
+/// Every schema becomes an exported `interface` (for objects with properties) or `type` (for
+/// everything else, like a union of the string literals of an `enum`). References to other
+/// schemas use their names (with characters that are not allowed in TypeScript names replaced
+/// by `_`), properties that are not `required` are optional, and `nullable` schemas include
+/// `null`. The `description` of schemas and properties becomes a doc comment.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+///
+/// let spec: OpenApi = serde_json::from_str(r##"{
+///     "openapi": "3.0.0",
+///     "info": { "title": "Users", "version": "1.0.0" },
+///     "paths": {},
+///     "components": {
+///         "schemas": {
+///             "Role": { "type": "string", "enum": ["admin", "user"] },
+///             "User": {
+///                 "type": "object",
+///                 "required": ["name", "role"],
+///                 "properties": {
+///                     "name": { "type": "string" },
+///                     "age": { "type": "integer", "nullable": true },
+///                     "role": { "$ref": "#/components/schemas/Role" }
+///                 }
+///             }
+///         }
+///     }
+/// }"##).unwrap();
+/// let declarations = okapi::export::typescript(&spec);
+/// assert!(declarations.contains(r#"export type Role = "admin" | "user";"#));
+/// assert!(declarations.contains("export interface User {\n  name: string;\n  age?: number | null;\n  role: Role;\n}"));
+/// ```
+#[must_use]
+pub fn typescript(spec: &OpenApi) -> String {
+    let mut declarations = format!(
+        "// Generated from the OpenAPI document \"{}\" (version {}).\n",
+        spec.info.title, spec.info.version
+    );
+    let schemas = spec
+        .components
+        .iter()
+        .flat_map(|components| &components.schemas);
+    for (name, schema) in schemas {
+        declarations.push('\n');
+        declarations.push_str(&doc_comment(schema, ""));
+        let name = type_name(name);
+        let is_interface = schema.reference.is_none()
+            && schema.subschemas.is_none()
+            && schema.enum_values.is_none()
+            && schema.const_value.is_none()
+            && !is_nullable(schema)
+            && schema
+                .object
+                .as_ref()
+                .is_some_and(|object| !object.properties.is_empty())
+            && matches!(
+                &schema.instance_type,
+                Some(SingleOrVec::Single(instance_type)) if **instance_type == InstanceType::Object
+            );
+        if is_interface {
+            declarations.push_str(&format!(
+                "export interface {} {}\n",
+                name,
+                object_type(schema, "")
+            ));
+        } else {
+            declarations.push_str(&format!(
+                "export type {} = {};\n",
+                name,
+                schema_type(schema, "")
+            ));
+        }
+    }
+    declarations
+}
+
+/// Get the TypeScript name of a schema: characters that are not allowed are replaced by `_`.
+fn type_name(name: &str) -> String {
+    let mut type_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if type_name.starts_with(|c: char| c.is_ascii_digit()) {
+        type_name.insert(0, '_');
+    }
+    type_name
+}
+
+fn is_nullable(schema: &SchemaObject) -> bool {
+    schema.extensions.get("nullable") == Some(&Value::Bool(true))
+}
+
+fn doc_comment(schema: &SchemaObject, indent: &str) -> String {
+    let description = schema
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.description.as_deref());
+    let description = match description {
+        Some(description) if !description.trim().is_empty() => description,
+        _ => return String::new(),
+    };
+    let mut comment = format!("{}/**\n", indent);
+    for line in description.replace("*/", "*\\/").lines() {
+        comment.push_str(format!("{} * {}", indent, line).trim_end());
+        comment.push('\n');
+    }
+    comment.push_str(&format!("{} */\n", indent));
+    comment
+}
+
+/// Get the TypeScript type of a schema. `indent` is the indentation of the line the type starts
+/// on, for inline objects.
+fn schema_type(schema: &SchemaObject, indent: &str) -> String {
+    let ty = non_null_type(schema, indent);
+    if is_nullable(schema) && ty != "null" && ty != "unknown" {
+        format!("{} | null", ty)
+    } else {
+        ty
+    }
+}
+
+fn non_null_type(schema: &SchemaObject, indent: &str) -> String {
+    if let Some(reference) = &schema.reference {
+        return match reference.strip_prefix("#/components/schemas/") {
+            Some(name) => type_name(name),
+            None => "unknown".to_owned(),
+        };
+    }
+    if let Some(value) = &schema.const_value {
+        return literal_type(value);
+    }
+    if let Some(values) = &schema.enum_values {
+        let types: Vec<String> = values.iter().map(literal_type).collect();
+        return union(types);
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            let types: Vec<String> = all_of
+                .iter()
+                .map(|schema| wrap(subschema_type(schema, indent)))
+                .collect();
+            return types.join(" & ");
+        }
+        if let Some(any_of) = subschemas.any_of.as_ref().or(subschemas.one_of.as_ref()) {
+            let types: Vec<String> = any_of
+                .iter()
+                .map(|schema| subschema_type(schema, indent))
+                .collect();
+            return union(types);
+        }
+    }
+    let instance_types: Vec<InstanceType> = match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => vec![**instance_type],
+        Some(SingleOrVec::Vec(instance_types)) => instance_types.clone(),
+        None if schema.object.is_some() => vec![InstanceType::Object],
+        None if schema.array.is_some() => vec![InstanceType::Array],
+        None => return "unknown".to_owned(),
+    };
+    let types: Vec<String> = instance_types
+        .into_iter()
+        .map(|instance_type| match instance_type {
+            InstanceType::Null => "null".to_owned(),
+            InstanceType::Boolean => "boolean".to_owned(),
+            InstanceType::Integer | InstanceType::Number => "number".to_owned(),
+            InstanceType::String => "string".to_owned(),
+            InstanceType::Array => array_type(schema, indent),
+            InstanceType::Object => object_type(schema, indent),
+        })
+        .collect();
+    union(types)
+}
+
+fn subschema_type(schema: &Schema, indent: &str) -> String {
+    match schema {
+        Schema::Object(schema) => schema_type(schema, indent),
+        Schema::Bool(true) => "unknown".to_owned(),
+        Schema::Bool(false) => "never".to_owned(),
+    }
+}
+
+fn array_type(schema: &SchemaObject, indent: &str) -> String {
+    match schema.array.as_ref().and_then(|array| array.items.as_ref()) {
+        Some(SingleOrVec::Single(items)) => format!("{}[]", wrap(subschema_type(items, indent))),
+        Some(SingleOrVec::Vec(items)) => {
+            let types: Vec<String> = items
+                .iter()
+                .map(|item| subschema_type(item, indent))
+                .collect();
+            format!("[{}]", types.join(", "))
+        }
+        None => "unknown[]".to_owned(),
+    }
+}
+
+fn object_type(schema: &SchemaObject, indent: &str) -> String {
+    let object = match &schema.object {
+        Some(object) => object,
+        None => return "Record<string, unknown>".to_owned(),
+    };
+    let inner_indent = format!("{}  ", indent);
+    let mut members = Vec::new();
+    for (name, property) in &object.properties {
+        let mut member = String::new();
+        if let Schema::Object(property) = property {
+            member.push_str(&doc_comment(property, &inner_indent));
+        }
+        let optional = if object.required.contains(name) {
+            ""
+        } else {
+            "?"
+        };
+        member.push_str(&format!(
+            "{}{}{}: {};",
+            inner_indent,
+            property_name(name),
+            optional,
+            subschema_type(property, &inner_indent)
+        ));
+        members.push(member);
+    }
+    match object.additional_properties.as_deref() {
+        Some(Schema::Bool(false)) | None => {}
+        Some(additional_properties) => {
+            // Other properties can have any of the types of the declared properties.
+            let mut types = vec![subschema_type(additional_properties, &inner_indent)];
+            if !object.properties.is_empty() && types[0] != "unknown" {
+                types.push("undefined".to_owned());
+                types.extend(
+                    object
+                        .properties
+                        .values()
+                        .map(|property| subschema_type(property, &inner_indent)),
+                );
+            }
+            if object.properties.is_empty() {
+                return format!("Record<string, {}>", types[0]);
+            }
+            members.push(format!("{}[key: string]: {};", inner_indent, union(types)));
+        }
+    }
+    if members.is_empty() {
+        return "Record<string, never>".to_owned();
+    }
+    format!("{{\n{}\n{}}}", members.join("\n"), indent)
+}
+
+/// Write a property name, quoted if it is not an identifier.
+fn property_name(name: &str) -> String {
+    let is_identifier = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_owned()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+fn literal_type(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => value.to_string(),
+        Value::Array(_) => "unknown[]".to_owned(),
+        Value::Object(_) => "Record<string, unknown>".to_owned(),
+    }
+}
+
+/// Combine types in a union, without duplicates.
+fn union(types: Vec<String>) -> String {
+    let mut unique: Vec<String> = Vec::new();
+    for ty in types {
+        if !unique.contains(&ty) {
+            unique.push(ty);
+        }
+    }
+    match unique.len() {
+        0 => "never".to_owned(),
+        _ => unique.join(" | "),
+    }
+}
+
+/// Put a union or intersection type in parentheses, so it can be used in an array or
+/// intersection type.
+fn wrap(ty: String) -> String {
+    if ty.contains(" | ") || ty.contains(" & ") {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
@@ -0,0 +1,323 @@
+use crate::merge::operations_mut;
+use crate::openapi3::{Components, OpenApi, Operation, PathItem, SecurityRequirement};
+use crate::validate::{escape_pointer, operations};
+use crate::Map;
+use serde_json::Value;
+use std::collections::HashSet;
+
+impl OpenApi {
+    /// Get the names of the tags that are used by operations: first the tags of the `tags` list
+    /// (in that order), then the other tags in the order they are used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{OpenApi, Operation, PathItem};
+    ///
+    /// let mut spec = OpenApi::new();
+    /// spec.paths.insert(
+    ///     "/users".to_owned(),
+    ///     PathItem {
+    ///         get: Some(Operation {
+    ///             tags: vec!["users".to_owned()],
+    ///             ..Operation::default()
+    ///         }),
+    ///         ..PathItem::default()
+    ///     },
+    /// );
+    /// assert_eq!(spec.operation_tags(), vec!["users".to_owned()]);
+    /// ```
+    pub fn operation_tags(&self) -> Vec<String> {
+        let mut used = Vec::new();
+        for path_item in self.paths.values() {
+            for (_, operation) in operations(path_item) {
+                for tag in &operation.tags {
+                    if !used.contains(tag) {
+                        used.push(tag.clone());
+                    }
+                }
+            }
+        }
+        let mut tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|tag| tag.name.clone())
+            .filter(|name| used.contains(name))
+            .collect();
+        for tag in used {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// Create a document with only the operations with the tag `tag`, and the components that
+    /// are (directly or indirectly) referenced by them. Paths without such operations are left
+    /// out, and the `tags` list only contains `tag`.
+    ///
+    /// This can be used to split an enormous document into smaller documents.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the components can not be serialized to JSON.
+    pub fn filter_tag(&self, tag: &str) -> OpenApi {
+        let mut spec =
+            self.filter_operations(|_, operation| operation.tags.iter().any(|t| t == tag));
+        spec.tags.retain(|t| t.name == tag);
+        spec
+    }
+
+    /// Create a document with only the operations for which `keep` returns `true` (it gets the
+    /// path and the operation), and the components that are (directly or indirectly) referenced
+    /// by them. Paths without operations are left out, and so are the tags of the `tags` list
+    /// that are no longer used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{OpenApi, Operation, PathItem};
+    ///
+    /// let mut spec = OpenApi::new();
+    /// for path in ["/users", "/internal/stats"] {
+    ///     spec.paths.insert(
+    ///         path.to_owned(),
+    ///         PathItem {
+    ///             get: Some(Operation::default()),
+    ///             ..PathItem::default()
+    ///         },
+    ///     );
+    /// }
+    /// let public = spec.filter_operations(|path, _| !path.starts_with("/internal"));
+    /// assert_eq!(public.paths.keys().collect::<Vec<_>>(), vec!["/users"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the components can not be serialized to JSON.
+    pub fn filter_operations(&self, keep: impl Fn(&str, &Operation) -> bool) -> OpenApi {
+        let mut paths = Map::new();
+        for (path, path_item) in &self.paths {
+            let mut path_item = path_item.clone();
+            let mut has_operations = false;
+            for (_, operation) in operations_mut(&mut path_item) {
+                if operation
+                    .as_ref()
+                    .is_some_and(|operation| !keep(path, operation))
+                {
+                    *operation = None;
+                }
+                has_operations |= operation.is_some();
+            }
+            if has_operations {
+                paths.insert(path.clone(), path_item);
+            }
+        }
+        let components = self
+            .components
+            .as_ref()
+            .map(|components| used_components(components, &paths, &self.security));
+        let mut spec = OpenApi {
+            openapi: self.openapi.clone(),
+            info: self.info.clone(),
+            servers: self.servers.clone(),
+            paths,
+            components,
+            security: self.security.clone(),
+            tags: Vec::new(),
+            external_docs: self.external_docs.clone(),
+            extensions: self.extensions.clone(),
+        };
+        let used_tags = spec.operation_tags();
+        spec.tags = self
+            .tags
+            .iter()
+            .filter(|tag| used_tags.contains(&tag.name))
+            .cloned()
+            .collect();
+        spec
+    }
+
+    /// Remove the specification extensions (`x-...` fields) with the given names from all objects
+    /// of the document, like internal annotations that should not be published.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    ///
+    /// let mut spec = OpenApi::new();
+    /// spec.extensions.insert("x-internal".to_owned(), true.into());
+    /// spec.remove_extensions(&["x-internal"]);
+    /// assert!(spec.extensions.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the document can not be serialized to JSON.
+    pub fn remove_extensions(&mut self, names: &[impl AsRef<str>]) {
+        let names: Vec<&str> = names.iter().map(AsRef::as_ref).collect();
+        if names.is_empty() {
+            return;
+        }
+        let mut document = serde_json::to_value(&*self).expect("Could not serialize document.");
+        remove_extensions(&mut document, &names);
+        *self = serde_json::from_value(document).expect("Could not deserialize document.");
+    }
+}
+
+/// The fields that contain maps with names (like property names or status codes), instead of
+/// objects with fields. Their keys are never extensions.
+const NAME_MAPS: &[&str] = &[
+    "paths",
+    "properties",
+    "patternProperties",
+    "definitions",
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "content",
+    "encoding",
+    "variables",
+    "mapping",
+    "scopes",
+];
+
+/// The fields that contain example values or other data, which are kept as is.
+const DATA_FIELDS: &[&str] = &["example", "default", "enum", "const", "value"];
+
+fn remove_extensions(value: &mut Value, names: &[&str]) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !(key.starts_with("x-") && names.contains(&key.as_str())));
+            for (key, item) in object.iter_mut() {
+                match item {
+                    _ if DATA_FIELDS.contains(&key.as_str()) => {}
+                    // The `examples` of schemas.
+                    Value::Array(_) if key == "examples" => {}
+                    Value::Object(map) if NAME_MAPS.contains(&key.as_str()) => {
+                        for item in map.values_mut() {
+                            remove_extensions(item, names);
+                        }
+                    }
+                    _ => remove_extensions(item, names),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for item in array {
+                remove_extensions(item, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keep only the components that are used by the `paths` or `security` requirements.
+fn used_components(
+    components: &Components,
+    paths: &Map<String, PathItem>,
+    security: &[SecurityRequirement],
+) -> Components {
+    let document = serde_json::to_value(components).expect("Could not serialize components.");
+    let mut used = HashSet::new();
+    let mut pending = Vec::new();
+    collect_refs(
+        &serde_json::to_value(paths).expect("Could not serialize paths."),
+        &mut pending,
+    );
+    // Follow the references of the used components, until all used components are found.
+    while let Some(pointer) = pending.pop() {
+        if used.insert(pointer.clone()) {
+            if let Some(value) = document.pointer(&pointer) {
+                collect_refs(value, &mut pending);
+            }
+        }
+    }
+    // Security schemes are referenced by name in security requirements.
+    let mut security_names: HashSet<&String> = security
+        .iter()
+        .flat_map(|requirement| requirement.keys())
+        .collect();
+    for path_item in paths.values() {
+        for (_, operation) in operations(path_item) {
+            for requirement in operation.security.iter().flatten() {
+                security_names.extend(requirement.keys());
+            }
+        }
+    }
+    let is_used = |section: &str, name: &str| {
+        used.contains(&format!("/{}/{}", section, escape_pointer(name)))
+    };
+    Components {
+        schemas: retain_used(&components.schemas, "schemas", &is_used),
+        responses: retain_used(&components.responses, "responses", &is_used),
+        parameters: retain_used(&components.parameters, "parameters", &is_used),
+        examples: retain_used(&components.examples, "examples", &is_used),
+        request_bodies: retain_used(&components.request_bodies, "requestBodies", &is_used),
+        headers: retain_used(&components.headers, "headers", &is_used),
+        security_schemes: components
+            .security_schemes
+            .iter()
+            .filter(|(name, _)| security_names.contains(name) || is_used("securitySchemes", name))
+            .map(|(name, scheme)| (name.clone(), scheme.clone()))
+            .collect(),
+        links: retain_used(&components.links, "links", &is_used),
+        callbacks: retain_used(&components.callbacks, "callbacks", &is_used),
+        extensions: components.extensions.clone(),
+    }
+}
+
+fn retain_used<T: Clone>(
+    map: &Map<String, T>,
+    section: &str,
+    is_used: &impl Fn(&str, &str) -> bool,
+) -> Map<String, T> {
+    map.iter()
+        .filter(|(name, _)| is_used(section, name))
+        .map(|(name, object)| (name.clone(), object.clone()))
+        .collect()
+}
+
+/// Collect the components that are referenced in `value`, as JSON Pointers into the
+/// `components` (like `/schemas/User`). This includes the references of discriminator mappings.
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object {
+                match (key.as_str(), item) {
+                    ("$ref", Value::String(reference)) => push_ref(reference, refs),
+                    ("mapping", Value::Object(mapping)) => {
+                        for reference in mapping.values().filter_map(Value::as_str) {
+                            push_ref(reference, refs);
+                        }
+                        collect_refs(item, refs);
+                    }
+                    _ => collect_refs(item, refs),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for item in array {
+                collect_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_ref(reference: &str, refs: &mut Vec<String>) {
+    if let Some(pointer) = reference.strip_prefix("#/components/") {
+        // Only the section and name, a reference can point into a component.
+        let mut segments = pointer.splitn(3, '/');
+        if let (Some(section), Some(name)) = (segments.next(), segments.next()) {
+            refs.push(format!("/{}/{}", section, name));
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::openapi3::OpenApi;
+use crate::validate::escape_pointer;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use std::fmt;
+
+/// The maximum number of nested schemas that is followed, to stop at recursive schemas that
+/// never end (like a schema that only contains a `$ref` to itself).
+const MAX_DEPTH: usize = 64;
+
+/// A part of a value that does not match its schema, as returned by [`validate_instance`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceError {
+    /// JSON Pointer to the part of the value that does not match, like `/users/0/name`.
+    /// Empty if the value itself does not match.
+    pub pointer: String,
+    /// Describes why the value does not match, like "Expected a value of type `string`".
+    pub message: String,
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}.", self.message)
+        } else {
+            write!(f, "{} at `{}`.", self.message, self.pointer)
+        }
+    }
+}
+
+/// Validate a JSON value (like a request body) against a schema of the document.
+/// References to `#/components/schemas/` are looked up in `spec`.
+///
+/// Most keywords are checked, except for `pattern`, `patternProperties` and `format`.
+/// Objects with `patternProperties` accept any additional property.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::OpenApi;
+/// use schemars::schema::{InstanceType, SchemaObject};
+/// use serde_json::json;
+///
+/// let schema = SchemaObject {
+///     instance_type: Some(InstanceType::Integer.into()),
+///     ..SchemaObject::default()
+/// };
+/// let spec = OpenApi::new();
+///
+/// assert!(okapi::instance::validate_instance(&spec, &schema, &json!(5)).is_ok());
+/// let errors = okapi::instance::validate_instance(&spec, &schema, &json!("5")).unwrap_err();
+/// assert_eq!(errors[0].message, "Expected a value of type `integer`");
+/// ```
+pub fn validate_instance(
+    spec: &OpenApi,
+    schema: &SchemaObject,
+    value: &Value,
+) -> Result<(), Vec<InstanceError>> {
+    let mut validator = Validator {
+        spec,
+        errors: Vec::new(),
+        depth: 0,
+    };
+    validator.validate(schema, value, "");
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+struct Validator<'a> {
+    spec: &'a OpenApi,
+    errors: Vec<InstanceError>,
+    depth: usize,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, pointer: &str, message: String) {
+        self.errors.push(InstanceError {
+            pointer: pointer.to_owned(),
+            message,
+        });
+    }
+
+    /// Returns `true` if `value` matches `schema`, without reporting errors.
+    fn is_valid(&self, schema: &Schema, value: &Value) -> bool {
+        let mut validator = Validator {
+            spec: self.spec,
+            errors: Vec::new(),
+            depth: self.depth,
+        };
+        validator.validate_schema(schema, value, "");
+        validator.errors.is_empty()
+    }
+
+    fn validate_schema(&mut self, schema: &Schema, value: &Value, pointer: &str) {
+        match schema {
+            Schema::Bool(true) => {}
+            Schema::Bool(false) => self.error(pointer, "No value is allowed".to_owned()),
+            Schema::Object(schema) => self.validate(schema, value, pointer),
+        }
+    }
+
+    fn validate(&mut self, schema: &SchemaObject, value: &Value, pointer: &str) {
+        if self.depth >= MAX_DEPTH {
+            return;
+        }
+        self.depth += 1;
+        self.validate_keywords(schema, value, pointer);
+        self.depth -= 1;
+    }
+
+    fn validate_keywords(&mut self, schema: &SchemaObject, value: &Value, pointer: &str) {
+        if value.is_null() && schema.extensions.get("nullable") == Some(&Value::Bool(true)) {
+            return;
+        }
+        if let Some(reference) = &schema.reference {
+            let spec = self.spec;
+            let target = reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| spec.components.as_ref()?.schemas.get(name));
+            if let Some(target) = target {
+                self.validate(target, value, pointer);
+            }
+        }
+        if let Some(instance_type) = &schema.instance_type {
+            let types: &[InstanceType] = match instance_type {
+                SingleOrVec::Single(instance_type) => std::slice::from_ref(&**instance_type),
+                SingleOrVec::Vec(types) => types,
+            };
+            if !types
+                .iter()
+                .any(|instance_type| has_type(value, instance_type))
+            {
+                let names: Vec<String> = types
+                    .iter()
+                    .map(|instance_type| format!("`{}`", type_name(instance_type)))
+                    .collect();
+                let message = format!("Expected a value of type {}", names.join(" or "));
+                self.error(pointer, message);
+                return;
+            }
+        }
+        if let Some(enum_values) = &schema.enum_values {
+            if !enum_values.contains(value) {
+                let values: Vec<String> = enum_values.iter().map(Value::to_string).collect();
+                let message = format!("Expected one of {}", values.join(", "));
+                self.error(pointer, message);
+            }
+        }
+        if let Some(const_value) = &schema.const_value {
+            if const_value != value {
+                self.error(pointer, format!("Expected {}", const_value));
+            }
+        }
+        if let Some(subschemas) = &schema.subschemas {
+            for subschema in subschemas.all_of.iter().flatten() {
+                self.validate_schema(subschema, value, pointer);
+            }
+            if let Some(any_of) = &subschemas.any_of {
+                if !any_of
+                    .iter()
+                    .any(|subschema| self.is_valid(subschema, value))
+                {
+                    let message = "Expected a value that matches one of the `anyOf` schemas";
+                    self.error(pointer, message.to_owned());
+                }
+            }
+            if let Some(one_of) = &subschemas.one_of {
+                let valid = one_of
+                    .iter()
+                    .filter(|subschema| self.is_valid(subschema, value))
+                    .count();
+                if valid != 1 {
+                    let message = format!(
+                        "Expected a value that matches exactly one of the `oneOf` schemas, \
+                         but it matches {}",
+                        valid
+                    );
+                    self.error(pointer, message);
+                }
+            }
+            if let Some(not) = &subschemas.not {
+                if self.is_valid(not, value) {
+                    let message = "Expected a value that does not match the `not` schema";
+                    self.error(pointer, message.to_owned());
+                }
+            }
+            if let Some(if_schema) = &subschemas.if_schema {
+                let then_or_else = if self.is_valid(if_schema, value) {
+                    &subschemas.then_schema
+                } else {
+                    &subschemas.else_schema
+                };
+                if let Some(then_or_else) = then_or_else {
+                    self.validate_schema(then_or_else, value, pointer);
+                }
+            }
+        }
+        match value {
+            Value::Number(number) => self.validate_number(schema, number.as_f64(), pointer),
+            Value::String(string) => self.validate_string(schema, string, pointer),
+            Value::Array(items) => self.validate_array(schema, items, pointer),
+            Value::Object(object) => self.validate_object(schema, object, pointer),
+            _ => {}
+        }
+    }
+
+    fn validate_number(&mut self, schema: &SchemaObject, number: Option<f64>, pointer: &str) {
+        let (validation, number) = match (&schema.number, number) {
+            (Some(validation), Some(number)) => (validation, number),
+            _ => return,
+        };
+        // OpenAPI 3.0 uses booleans for `exclusiveMinimum` and `exclusiveMaximum`.
+        let is_exclusive = |key: &str| schema.extensions.get(key) == Some(&Value::Bool(true));
+        if let Some(multiple_of) = validation.multiple_of {
+            if multiple_of > 0.0 && (number / multiple_of).fract() != 0.0 {
+                self.error(pointer, format!("Expected a multiple of {}", multiple_of));
+            }
+        }
+        if let Some(maximum) = validation.maximum {
+            if number > maximum || (is_exclusive("exclusiveMaximum") && number >= maximum) {
+                self.error(pointer, format!("Expected at most {}", maximum));
+            }
+        }
+        if let Some(maximum) = validation.exclusive_maximum {
+            if number >= maximum {
+                self.error(pointer, format!("Expected less than {}", maximum));
+            }
+        }
+        if let Some(minimum) = validation.minimum {
+            if number < minimum || (is_exclusive("exclusiveMinimum") && number <= minimum) {
+                self.error(pointer, format!("Expected at least {}", minimum));
+            }
+        }
+        if let Some(minimum) = validation.exclusive_minimum {
+            if number <= minimum {
+                self.error(pointer, format!("Expected more than {}", minimum));
+            }
+        }
+    }
+
+    fn validate_string(&mut self, schema: &SchemaObject, string: &str, pointer: &str) {
+        let validation = match &schema.string {
+            Some(validation) => validation,
+            None => return,
+        };
+        let length = string.chars().count() as u32;
+        if let Some(max_length) = validation.max_length {
+            if length > max_length {
+                let message = format!("Expected at most {} characters", max_length);
+                self.error(pointer, message);
+            }
+        }
+        if let Some(min_length) = validation.min_length {
+            if length < min_length {
+                let message = format!("Expected at least {} characters", min_length);
+                self.error(pointer, message);
+            }
+        }
+    }
+
+    fn validate_array(&mut self, schema: &SchemaObject, items: &[Value], pointer: &str) {
+        let validation = match &schema.array {
+            Some(validation) => validation,
+            None => return,
+        };
+        match &validation.items {
+            Some(SingleOrVec::Single(item_schema)) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.validate_schema(item_schema, item, &format!("{}/{}", pointer, index));
+                }
+            }
+            Some(SingleOrVec::Vec(item_schemas)) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_pointer = format!("{}/{}", pointer, index);
+                    match item_schemas.get(index) {
+                        Some(item_schema) => self.validate_schema(item_schema, item, &item_pointer),
+                        None => {
+                            if let Some(additional_items) = &validation.additional_items {
+                                self.validate_schema(additional_items, item, &item_pointer);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+        let length = items.len() as u32;
+        if let Some(max_items) = validation.max_items {
+            if length > max_items {
+                self.error(pointer, format!("Expected at most {} items", max_items));
+            }
+        }
+        if let Some(min_items) = validation.min_items {
+            if length < min_items {
+                self.error(pointer, format!("Expected at least {} items", min_items));
+            }
+        }
+        if validation.unique_items == Some(true) {
+            let has_duplicates = items
+                .iter()
+                .enumerate()
+                .any(|(index, item)| items[..index].contains(item));
+            if has_duplicates {
+                self.error(pointer, "Expected unique items".to_owned());
+            }
+        }
+        if let Some(contains) = &validation.contains {
+            if !items.iter().any(|item| self.is_valid(contains, item)) {
+                let message = "Expected an item that matches the `contains` schema";
+                self.error(pointer, message.to_owned());
+            }
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &SchemaObject,
+        object: &serde_json::Map<String, Value>,
+        pointer: &str,
+    ) {
+        let validation = match &schema.object {
+            Some(validation) => validation,
+            None => return,
+        };
+        for name in &validation.required {
+            if !object.contains_key(name) {
+                self.error(pointer, format!("Missing required property `{}`", name));
+            }
+        }
+        for (name, item) in object {
+            let item_pointer = format!("{}/{}", pointer, escape_pointer(name));
+            match validation.properties.get(name) {
+                Some(property) => self.validate_schema(property, item, &item_pointer),
+                None if validation.pattern_properties.is_empty() => {
+                    if let Some(additional_properties) = &validation.additional_properties {
+                        if **additional_properties == Schema::Bool(false) {
+                            let message = format!("Unexpected property `{}`", name);
+                            self.error(pointer, message);
+                        } else {
+                            self.validate_schema(additional_properties, item, &item_pointer);
+                        }
+                    }
+                }
+                None => {}
+            }
+            if let Some(property_names) = &validation.property_names {
+                let name_value = Value::String(name.clone());
+                self.validate_schema(property_names, &name_value, &item_pointer);
+            }
+        }
+        let length = object.len() as u32;
+        if let Some(max_properties) = validation.max_properties {
+            if length > max_properties {
+                let message = format!("Expected at most {} properties", max_properties);
+                self.error(pointer, message);
+            }
+        }
+        if let Some(min_properties) = validation.min_properties {
+            if length < min_properties {
+                let message = format!("Expected at least {} properties", min_properties);
+                self.error(pointer, message);
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, instance_type: &InstanceType) -> bool {
+    match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+    }
+}
+
+fn type_name(instance_type: &InstanceType) -> &'static str {
+    match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "boolean",
+        InstanceType::Object => "object",
+        InstanceType::Array => "array",
+        InstanceType::Number => "number",
+        InstanceType::String => "string",
+        InstanceType::Integer => "integer",
+    }
+}
@@ -0,0 +1,182 @@
+//! Change how the schemas of integers are written. `Schemars` documents the Rust integer types
+//! with their own formats (like `uint64` for `u64` and `uint` for `usize`), which are not part of
+//! OpenAPI, and JavaScript clients can not handle 64-bit integers documented as numbers.
+//! See [`OpenApi::convert_integers`].
+
+use crate::convert::visit_spec_schemas;
+use crate::openapi3::OpenApi;
+use schemars::schema::{InstanceType, SchemaObject, SingleOrVec, StringValidation};
+use schemars::visit::{visit_schema_object, Visitor};
+
+/// How the schemas of integers are written. The default leaves them as `Schemars` generates them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerRepresentation {
+    /// Use the formats of OpenAPI (`int32` and `int64`) instead of the formats of `Schemars`:
+    /// the integers that fit in an `int32` (like `u16` and `i32`) get `int32`, the other integers
+    /// up to 64 bits (like `u32` and `u64`) get `int64` and larger integers get no format.
+    /// `usize` and `isize` have the size of the target the documentation is generated on.
+    /// The default is `false`.
+    pub standard_formats: bool,
+    /// How integers with 64 bits or more (like `u64`, `i128` and `usize` on 64-bit targets) are
+    /// written.
+    pub large_integers: LargeIntegers,
+    /// Write `minimum: 0` for unsigned integers. The default is `true`.
+    pub unsigned_minimum: bool,
+}
+
+impl Default for IntegerRepresentation {
+    fn default() -> Self {
+        IntegerRepresentation {
+            standard_formats: false,
+            large_integers: LargeIntegers::default(),
+            unsigned_minimum: true,
+        }
+    }
+}
+
+/// How integers with 64 bits or more are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LargeIntegers {
+    /// As `type: integer`, as `Schemars` generates them.
+    #[default]
+    Integer,
+    /// As `type: string` with a `pattern` of digits (and their format), for APIs that serialize
+    /// them as strings (like with `serde_with::DisplayFromStr`), because JavaScript numbers can
+    /// only represent integers up to 2^53 exactly.
+    String,
+}
+
+impl OpenApi {
+    /// Rewrite the schemas of integers (in the whole document), as configured by
+    /// `representation`. Only integer schemas with a format of `Schemars` (like `uint8` or
+    /// `int64`) are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::integers::{IntegerRepresentation, LargeIntegers};
+    /// use okapi::openapi3::{Components, OpenApi};
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Counter {
+    ///     step: u16,
+    ///     total: u64,
+    /// }
+    ///
+    /// let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+    /// gen.subschema_for::<Counter>();
+    /// let mut spec = OpenApi::new();
+    /// spec.components = Some(Components {
+    ///     schemas: gen
+    ///         .take_definitions()
+    ///         .into_iter()
+    ///         .map(|(name, schema)| (name, schema.into_object()))
+    ///         .collect(),
+    ///     ..Components::default()
+    /// });
+    /// spec.convert_integers(&IntegerRepresentation {
+    ///     standard_formats: true,
+    ///     large_integers: LargeIntegers::String,
+    ///     unsigned_minimum: false,
+    /// });
+    ///
+    /// let schemas = serde_json::to_value(&spec.components.unwrap().schemas).unwrap();
+    /// assert_eq!(
+    ///     schemas["Counter"]["properties"]["step"],
+    ///     serde_json::json!({ "type": "integer", "format": "int32" })
+    /// );
+    /// assert_eq!(
+    ///     schemas["Counter"]["properties"]["total"],
+    ///     serde_json::json!({ "type": "string", "format": "int64", "pattern": "^[0-9]+$" })
+    /// );
+    /// ```
+    pub fn convert_integers(&mut self, representation: &IntegerRepresentation) {
+        if *representation != IntegerRepresentation::default() {
+            visit_spec_schemas(self, &mut ConvertIntegers(*representation));
+        }
+    }
+}
+
+/// Rewrites the schemas of integers.
+struct ConvertIntegers(IntegerRepresentation);
+
+impl Visitor for ConvertIntegers {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        if !has_instance_type(schema, InstanceType::Integer) {
+            return;
+        }
+        let (signed, bits) = match schema.format.as_deref().and_then(integer_size) {
+            Some(size) => size,
+            None => return,
+        };
+        if self.0.standard_formats {
+            schema.format = if bits < 32 || (signed && bits == 32) {
+                Some("int32".to_owned())
+            } else if bits <= 64 {
+                Some("int64".to_owned())
+            } else {
+                None
+            };
+        }
+        if self.0.large_integers == LargeIntegers::String && bits >= 64 {
+            set_instance_type(schema, InstanceType::String);
+            schema.number = None;
+            schema.string = Some(Box::new(StringValidation {
+                pattern: Some(if signed { "^-?[0-9]+$" } else { "^[0-9]+$" }.to_owned()),
+                ..StringValidation::default()
+            }));
+        } else if !signed {
+            if self.0.unsigned_minimum {
+                schema.number().minimum.get_or_insert(0.0);
+            } else if let Some(number) = &mut schema.number {
+                if number.minimum == Some(0.0) {
+                    number.minimum = None;
+                }
+                if **number == Default::default() {
+                    schema.number = None;
+                }
+            }
+        }
+    }
+}
+
+/// Get if the integers of a `Schemars` format are signed, and their number of bits.
+fn integer_size(format: &str) -> Option<(bool, u32)> {
+    let (signed, bits) = match format.strip_prefix('u') {
+        Some(bits) => (false, bits.strip_prefix("int")?),
+        None => (true, format.strip_prefix("int")?),
+    };
+    let bits = match bits {
+        // `usize` and `isize`.
+        "" => usize::BITS,
+        "8" | "16" | "32" | "64" | "128" => bits.parse().ok()?,
+        _ => return None,
+    };
+    Some((signed, bits))
+}
+
+/// If the schema allows values of the type, like `"type": ["integer", "null"]` for an integer.
+fn has_instance_type(schema: &SchemaObject, instance_type: InstanceType) -> bool {
+    match &schema.instance_type {
+        Some(SingleOrVec::Single(single)) => **single == instance_type,
+        Some(SingleOrVec::Vec(types)) => types.contains(&instance_type),
+        None => false,
+    }
+}
+
+/// Replace `integer` in the type of the schema by `instance_type`.
+fn set_instance_type(schema: &mut SchemaObject, instance_type: InstanceType) {
+    match &mut schema.instance_type {
+        Some(SingleOrVec::Single(single)) => **single = instance_type,
+        Some(SingleOrVec::Vec(types)) => {
+            for single in types {
+                if *single == InstanceType::Integer {
+                    *single = instance_type;
+                }
+            }
+        }
+        None => {}
+    }
+}
@@ -4,11 +4,35 @@
 pub type Map<K, V> = schemars::Map<K, V>;
 pub type MapEntry<'a, K, V> = schemars::MapEntry<'a, K, V>;
 
+pub mod builder;
+pub mod convert;
+pub mod deref;
+pub mod diff;
+mod discriminator;
+pub mod enums;
+pub mod example;
+pub mod export;
+mod filter;
+pub mod instance;
+pub mod integers;
 pub mod merge;
 pub mod openapi3;
+#[cfg(feature = "openapiv3-compat")]
+pub mod openapiv3_compat;
+#[cfg(feature = "schemars1")]
+pub mod schemars_compat;
+mod slim;
+mod sort;
+mod strict;
+pub mod validate;
 
+/// Compare two documents, see [`diff::diff`].
+pub use diff::diff;
 /// Re-export the current version of `Schemars` used by `Okapi`.
 pub use schemars;
+/// Re-export `Schemars` 1.0, used by the conversions in `schemars_compat`.
+#[cfg(feature = "schemars1")]
+pub use schemars1;
 
 /// Macro to crate an `okapi::Map` with a number of key-value pairs in it.
 ///
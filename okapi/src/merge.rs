@@ -1,4 +1,7 @@
-use crate::openapi3::{Components, Info, OpenApi, PathItem, Responses, Tag};
+use crate::openapi3::{
+    Components, Info, OpenApi, Operation, PathItem, Responses, SecurityRequirement, Tag,
+};
+use crate::validate::escape_pointer;
 use crate::{Map, MapEntry};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -30,6 +33,444 @@ impl OpenApi {
     }
 }
 
+/// How to handle conflicts when merging two documents with [`OpenApi::merge`].
+/// A conflict is an item with the same name (or the same path and method) but a different value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Return an error on the first conflict.
+    Reject,
+    /// Keep the item of the document that is merged into.
+    PreferSelf,
+    /// Use the item of the document that is merged in.
+    PreferOther,
+    /// Add conflicting components and tags of the other document under a new name (`Name2`,
+    /// `Name3`, ...) and update all references to them.
+    /// Operations can not be renamed, so a conflicting operation returns an error.
+    RenameOnConflict,
+}
+
+impl OpenApi {
+    /// Merge `other` into this document, handling conflicts as specified by `policy`.
+    /// This covers the paths, components, tags and security schemes (part of the components).
+    /// The other fields are merged like [`merge_specs`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::merge::MergePolicy;
+    /// use okapi::openapi3::{OpenApi, Tag};
+    ///
+    /// let mut spec = OpenApi::new();
+    /// spec.tags.push(Tag { name: "users".to_owned(), ..Tag::default() });
+    /// let mut other = OpenApi::new();
+    /// other.tags.push(Tag {
+    ///     name: "users".to_owned(),
+    ///     description: Some("Other users".to_owned()),
+    ///     ..Tag::default()
+    /// });
+    ///
+    /// assert!(spec.clone().merge(&other, MergePolicy::Reject).is_err());
+    ///
+    /// spec.merge(&other, MergePolicy::RenameOnConflict).unwrap();
+    /// assert_eq!(spec.tags[1].name, "users2");
+    /// ```
+    pub fn merge(&mut self, other: &OpenApi, policy: MergePolicy) -> Result<(), MergeError> {
+        if self.openapi != other.openapi {
+            return Err(MergeError::new("OpenAPI specs version do not match."));
+        }
+        let renamed;
+        let other = if policy == MergePolicy::RenameOnConflict {
+            renamed = rename_conflicts(self, other)?;
+            &renamed
+        } else {
+            other
+        };
+        merge_spec_info(&mut self.info, &other.info)?;
+        merge_vec(&mut self.servers, &other.servers);
+        merge_paths_with_policy(&mut self.paths, &other.paths, policy)?;
+        match (&mut self.components, &other.components) {
+            (Some(s1), Some(s2)) => merge_components_with_policy(s1, s2, policy)?,
+            (s1 @ None, s2) => *s1 = s2.clone(),
+            (Some(_), None) => {}
+        }
+        for requirement in &other.security {
+            if !self.security.contains(requirement) {
+                self.security.push(requirement.clone());
+            }
+        }
+        merge_tags_with_policy(&mut self.tags, &other.tags, policy)?;
+        merge_option(&mut self.external_docs, &other.external_docs);
+        merge_map(&mut self.extensions, &other.extensions, "extensions");
+        Ok(())
+    }
+}
+
+impl OpenApi {
+    /// Apply a JSON Merge Patch ([RFC 7386](https://tools.ietf.org/html/rfc7386)) to this
+    /// document. Objects in the `patch` are merged recursively, other values replace the value
+    /// in the document, and `null` removes it.
+    ///
+    /// Returns an error if the patched document is not a valid OpenAPI document.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    ///
+    /// let mut spec = OpenApi::new();
+    /// let patch = serde_json::json!({"info": {"description": "Written by a human."}});
+    /// spec.apply_merge_patch(&patch).unwrap();
+    /// assert_eq!(spec.info.description.as_deref(), Some("Written by a human."));
+    /// ```
+    pub fn apply_merge_patch(&mut self, patch: &serde_json::Value) -> Result<(), MergeError> {
+        let mut document = serde_json::to_value(&*self)
+            .map_err(|err| MergeError::new(format!("Could not serialize document: {}", err)))?;
+        apply_merge_patch(&mut document, patch);
+        *self = serde_json::from_value(document)
+            .map_err(|err| MergeError::new(format!("Invalid document after patch: {}", err)))?;
+        Ok(())
+    }
+}
+
+/// Apply a JSON Merge Patch ([RFC 7386](https://tools.ietf.org/html/rfc7386)) to `target`.
+pub fn apply_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let patch = match patch {
+        serde_json::Value::Object(patch) => patch,
+        _ => {
+            *target = patch.clone();
+            return;
+        }
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                apply_merge_patch(
+                    target.entry(key.clone()).or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+/// Merge the value `s2` into `s1` for the item `name`, according to the `policy`.
+fn merge_value_with_policy<T: Clone + PartialEq>(
+    s1: &mut T,
+    s2: &T,
+    name: &str,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    if s1 == s2 {
+        return Ok(());
+    }
+    match policy {
+        MergePolicy::PreferSelf => Ok(()),
+        MergePolicy::PreferOther => {
+            *s1 = s2.clone();
+            Ok(())
+        }
+        MergePolicy::Reject | MergePolicy::RenameOnConflict => Err(MergeError::new(format!(
+            "Found conflicting values for {} while merging.",
+            name
+        ))),
+    }
+}
+
+fn merge_map_with_policy<T: Clone + PartialEq>(
+    s1: &mut Map<String, T>,
+    s2: &Map<String, T>,
+    name: &str,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for (key, value) in s2 {
+        match s1.entry(key.clone()) {
+            MapEntry::Occupied(mut entry) => merge_value_with_policy(
+                entry.get_mut(),
+                value,
+                &format!("{} `{}`", name, key),
+                policy,
+            )?,
+            MapEntry::Vacant(entry) => {
+                entry.insert(value.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn merge_paths_with_policy(
+    s1: &mut Map<String, PathItem>,
+    s2: &Map<String, PathItem>,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for (path, s2_item) in s2 {
+        let s1_item = match s1.entry(path.clone()) {
+            MapEntry::Occupied(entry) => entry.into_mut(),
+            MapEntry::Vacant(entry) => {
+                entry.insert(s2_item.clone());
+                continue;
+            }
+        };
+        let mut s2_operations = s2_item.clone();
+        for ((method, s1_op), (_, s2_op)) in operations_mut(s1_item)
+            .into_iter()
+            .zip(operations_mut(&mut s2_operations))
+        {
+            match (s1_op.as_mut(), s2_op.take()) {
+                (Some(s1_op), Some(s2_op)) => merge_value_with_policy(
+                    s1_op,
+                    &s2_op,
+                    &format!("operation `{} {}`", method, path),
+                    policy,
+                )?,
+                (None, s2_op) => *s1_op = s2_op,
+                (Some(_), None) => {}
+            }
+        }
+        merge_opt_string(&mut s1_item.reference, &s2_item.reference);
+        merge_opt_string(&mut s1_item.summary, &s2_item.summary);
+        merge_opt_string(&mut s1_item.description, &s2_item.description);
+        merge_option(&mut s1_item.servers, &s2_item.servers);
+        for parameter in &s2_item.parameters {
+            if !s1_item.parameters.contains(parameter) {
+                s1_item.parameters.push(parameter.clone());
+            }
+        }
+        merge_map(&mut s1_item.extensions, &s2_item.extensions, "extensions");
+    }
+    Ok(())
+}
+
+fn merge_components_with_policy(
+    s1: &mut Components,
+    s2: &Components,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    merge_map_with_policy(&mut s1.schemas, &s2.schemas, "schema", policy)?;
+    merge_map_with_policy(&mut s1.responses, &s2.responses, "response", policy)?;
+    merge_map_with_policy(&mut s1.parameters, &s2.parameters, "parameter", policy)?;
+    merge_map_with_policy(&mut s1.examples, &s2.examples, "example", policy)?;
+    merge_map_with_policy(
+        &mut s1.request_bodies,
+        &s2.request_bodies,
+        "request body",
+        policy,
+    )?;
+    merge_map_with_policy(&mut s1.headers, &s2.headers, "header", policy)?;
+    merge_map_with_policy(
+        &mut s1.security_schemes,
+        &s2.security_schemes,
+        "security scheme",
+        policy,
+    )?;
+    merge_map_with_policy(&mut s1.links, &s2.links, "link", policy)?;
+    merge_map_with_policy(&mut s1.callbacks, &s2.callbacks, "callback", policy)?;
+    merge_map(&mut s1.extensions, &s2.extensions, "extensions");
+    Ok(())
+}
+
+fn merge_tags_with_policy(
+    s1: &mut Vec<Tag>,
+    s2: &[Tag],
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for tag in s2 {
+        match s1.iter_mut().find(|s1_tag| s1_tag.name == tag.name) {
+            Some(s1_tag) => {
+                merge_value_with_policy(s1_tag, tag, &format!("tag `{}`", tag.name), policy)?
+            }
+            None => s1.push(tag.clone()),
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn operations_mut(
+    path_item: &mut PathItem,
+) -> Vec<(&'static str, &mut Option<Operation>)> {
+    vec![
+        ("get", &mut path_item.get),
+        ("put", &mut path_item.put),
+        ("post", &mut path_item.post),
+        ("delete", &mut path_item.delete),
+        ("options", &mut path_item.options),
+        ("head", &mut path_item.head),
+        ("patch", &mut path_item.patch),
+        ("trace", &mut path_item.trace),
+    ]
+}
+
+/// Find a name for `name` that is not used in `s1` and `s2`: `name2`, `name3`, ...
+fn new_name<T>(name: &str, s1: &Map<String, T>, s2: &Map<String, T>) -> String {
+    (2..)
+        .map(|i| format!("{}{}", name, i))
+        .find(|new_name| !s1.contains_key(new_name) && !s2.contains_key(new_name))
+        .unwrap()
+}
+
+/// Rename the items in `s2` that conflict with `s1`. Returns the `(old, new)` names.
+fn rename_map_conflicts<T: PartialEq>(
+    s1: &Map<String, T>,
+    s2: &mut Map<String, T>,
+) -> Vec<(String, String)> {
+    let conflicts: Vec<String> = s2
+        .iter()
+        .filter(|(key, value)| s1.get(*key).is_some_and(|s1_value| s1_value != *value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    let mut renames = Vec::new();
+    for key in conflicts {
+        let new_key = new_name(&key, s1, s2);
+        if let Some(value) = s2.remove(&key) {
+            s2.insert(new_key.clone(), value);
+        }
+        renames.push((key, new_key));
+    }
+    renames
+}
+
+/// Return a copy of `s2` where all components and tags that conflict with `s1` are renamed.
+fn rename_conflicts(s1: &OpenApi, s2: &OpenApi) -> Result<OpenApi, MergeError> {
+    let mut s2 = s2.clone();
+    // Tags
+    let s1_tags: Map<String, Tag> = s1
+        .tags
+        .iter()
+        .map(|t| (t.name.clone(), t.clone()))
+        .collect();
+    let mut s2_tags: Map<String, Tag> = s2
+        .tags
+        .iter()
+        .map(|t| (t.name.clone(), t.clone()))
+        .collect();
+    let tag_renames = rename_map_conflicts(&s1_tags, &mut s2_tags);
+    for (old, new) in &tag_renames {
+        for tag in s2.tags.iter_mut().filter(|tag| &tag.name == old) {
+            tag.name = new.clone();
+        }
+        for path_item in s2.paths.values_mut() {
+            for (_, operation) in operations_mut(path_item) {
+                for tag in operation.iter_mut().flat_map(|op| op.tags.iter_mut()) {
+                    if tag == old {
+                        *tag = new.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    let (s1_components, s2_components) = match (&s1.components, &mut s2.components) {
+        (Some(s1), Some(s2)) => (s1, s2),
+        _ => return Ok(s2),
+    };
+    // Components, with the prefix used in references.
+    let mut ref_renames = Vec::new();
+    let mut add_renames = |prefix: &str, renames: Vec<(String, String)>| {
+        for (old, new) in renames {
+            ref_renames.push((
+                format!("#/components/{}/{}", prefix, escape_pointer(&old)),
+                format!("#/components/{}/{}", prefix, escape_pointer(&new)),
+            ));
+        }
+    };
+    let c1 = s1_components;
+    let c2 = s2_components;
+    add_renames(
+        "schemas",
+        rename_map_conflicts(&c1.schemas, &mut c2.schemas),
+    );
+    add_renames(
+        "responses",
+        rename_map_conflicts(&c1.responses, &mut c2.responses),
+    );
+    add_renames(
+        "parameters",
+        rename_map_conflicts(&c1.parameters, &mut c2.parameters),
+    );
+    add_renames(
+        "examples",
+        rename_map_conflicts(&c1.examples, &mut c2.examples),
+    );
+    add_renames(
+        "requestBodies",
+        rename_map_conflicts(&c1.request_bodies, &mut c2.request_bodies),
+    );
+    add_renames(
+        "headers",
+        rename_map_conflicts(&c1.headers, &mut c2.headers),
+    );
+    add_renames("links", rename_map_conflicts(&c1.links, &mut c2.links));
+    add_renames(
+        "callbacks",
+        rename_map_conflicts(&c1.callbacks, &mut c2.callbacks),
+    );
+    let scheme_renames = rename_map_conflicts(&c1.security_schemes, &mut c2.security_schemes);
+    add_renames("securitySchemes", scheme_renames.clone());
+
+    // Security requirements use the name of the security scheme.
+    if !scheme_renames.is_empty() {
+        let rename_requirements = |requirements: &mut Vec<SecurityRequirement>| {
+            for requirement in requirements.iter_mut() {
+                for (old, new) in &scheme_renames {
+                    if let Some(scopes) = requirement.remove(old) {
+                        requirement.insert(new.clone(), scopes);
+                    }
+                }
+            }
+        };
+        rename_requirements(&mut s2.security);
+        for path_item in s2.paths.values_mut() {
+            for (_, operation) in operations_mut(path_item) {
+                if let Some(security) = operation.as_mut().and_then(|op| op.security.as_mut()) {
+                    rename_requirements(security);
+                }
+            }
+        }
+    }
+
+    if ref_renames.is_empty() {
+        return Ok(s2);
+    }
+    let mut document = serde_json::to_value(&s2)
+        .map_err(|err| MergeError::new(format!("Could not serialize document: {}", err)))?;
+    rename_refs(&mut document, &ref_renames);
+    serde_json::from_value(document)
+        .map_err(|err| MergeError::new(format!("Could not deserialize document: {}", err)))
+}
+
+/// Update all `$ref`s that point to (a part of) a renamed component.
+fn rename_refs(value: &mut serde_json::Value, renames: &[(String, String)]) {
+    use serde_json::Value;
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object.iter_mut() {
+                match (key.as_str(), item) {
+                    ("$ref", Value::String(reference)) => {
+                        for (old, new) in renames {
+                            if reference == old || reference.starts_with(&format!("{}/", old)) {
+                                *reference = format!("{}{}", new, &reference[old.len()..]);
+                                break;
+                            }
+                        }
+                    }
+                    (_, item) => rename_refs(item, renames),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for item in array {
+                rename_refs(item, renames);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Marge the list of all specs together into on big OpenApi object.
 pub fn marge_spec_list<S: Display>(spec_list: &[(S, OpenApi)]) -> Result<OpenApi, MergeError> {
     let mut openapi_docs = OpenApi::new();
@@ -84,6 +525,9 @@ pub fn merge_paths<S: Display>(
     path_prefix: &S,
     s2: &Map<String, PathItem>,
 ) -> Result<(), MergeError> {
+    // A prefix of `/` (or one that ends with `/`) would result in paths like `//users`.
+    let path_prefix = path_prefix.to_string();
+    let path_prefix = path_prefix.trim_end_matches('/');
     // Add all s2 values
     // (if key does not already exists)
     for (key, value) in s2 {
@@ -1,8 +1,10 @@
+use crate::convert::schema_from_value;
 use crate::Map;
 pub use schemars::schema::SchemaObject;
 #[cfg(feature = "impl_json_schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 pub type Object = Map<String, Value>;
@@ -199,14 +201,13 @@ pub struct Operation {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
-#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(default, rename_all = "camelCase")]
 pub struct Responses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<RefOr<Response>>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_without_extensions")]
     pub responses: Map<String, RefOr<Response>>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -214,7 +215,11 @@ pub struct Responses {
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(default, rename_all = "camelCase")]
 pub struct Components {
-    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "Map::is_empty",
+        deserialize_with = "deserialize_schemas"
+    )]
     pub schemas: Map<String, SchemaObject>,
     #[serde(default, skip_serializing_if = "Map::is_empty")]
     pub responses: Map<String, RefOr<Response>>,
@@ -269,7 +274,7 @@ pub struct Parameter {
     pub allow_empty_value: bool,
     #[serde(flatten)]
     pub value: ParameterValue,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -277,14 +282,16 @@ pub struct Parameter {
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(untagged, rename_all = "camelCase")]
+#[allow(clippy::large_enum_variant)]
 pub enum ParameterValue {
     Schema {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         style: Option<ParameterStyle>,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         explode: Option<bool>,
-        #[serde(default, skip_serializing_if = "is_false")]
+        #[serde(default, rename = "allowReserved", skip_serializing_if = "is_false")]
         allow_reserved: bool,
+        #[serde(deserialize_with = "deserialize_schema")]
         schema: SchemaObject,
         #[serde(default, skip_serializing_if = "Option::is_none")]
         example: Option<Value>,
@@ -319,7 +326,7 @@ pub struct Example {
     pub description: Option<String>,
     #[serde(flatten)]
     pub value: ExampleValue,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -358,7 +365,7 @@ pub struct Header {
     pub allow_empty_value: bool,
     #[serde(flatten)]
     pub value: ParameterValue,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -371,7 +378,7 @@ pub struct SecurityScheme {
     // This also sets `type`
     #[serde(flatten)]
     pub data: SecuritySchemeData,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -393,7 +400,10 @@ pub enum SecuritySchemeData {
         bearer_format: Option<String>,
     },
     #[serde(rename = "oauth2", rename_all = "camelCase")]
-    OAuth2 { flows: OAuthFlows },
+    OAuth2 {
+        #[serde(deserialize_with = "deserialize_oauth_flows")]
+        flows: OAuthFlows,
+    },
     #[serde(rename_all = "camelCase")]
     OpenIdConnect { open_id_connect_url: String },
 }
@@ -463,12 +473,11 @@ pub struct Link {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
-#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Callback {
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_without_extensions")]
     pub callbacks: Map<String, PathItem>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -476,7 +485,10 @@ pub struct Callback {
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(default, rename_all = "camelCase")]
 pub struct MediaType {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_schema"
+    )]
     pub schema: Option<SchemaObject>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<Value>,
@@ -530,6 +542,262 @@ pub struct Encoding {
     pub extensions: Object,
 }
 
+/// Metadata for the XML representation of a schema, stored in the `xml` field of the schema.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct Xml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "is_false")]
+    pub attribute: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub wrapped: bool,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+impl Xml {
+    /// Get the `Xml` object of the given schema, if it has one.
+    pub fn from_schema(schema: &SchemaObject) -> Option<Self> {
+        schema.get_extension("xml").ok().flatten()
+    }
+
+    /// Set the `Xml` object of the given schema, replacing the existing one.
+    pub fn set_on(&self, schema: &mut SchemaObject) {
+        // Serializing `Xml` can not fail.
+        let _ = schema.set_extension("xml", self);
+    }
+}
+
+/// Typed access to the specification extensions (`x-*` fields) of an OpenAPI object.
+///
+/// # Examples
+///
+/// ```rust
+/// use okapi::openapi3::{Extensions, Operation};
+///
+/// let mut operation = Operation::default();
+/// operation.set_extension("x-internal", true).unwrap();
+///
+/// assert_eq!(operation.get_extension::<bool>("x-internal").unwrap(), Some(true));
+/// assert_eq!(operation.get_extension::<bool>("x-other").unwrap(), None);
+/// assert!(operation.get_extension::<String>("x-internal").is_err());
+/// ```
+pub trait Extensions {
+    /// The extensions of the object.
+    fn extensions(&self) -> &Object;
+
+    /// The extensions of the object, mutable.
+    fn extensions_mut(&mut self) -> &mut Object;
+
+    /// Get the extension with the given `name` and deserialize it as `T`.
+    /// Returns `Ok(None)` if the extension is not set.
+    fn get_extension<T: DeserializeOwned>(&self, name: &str) -> serde_json::Result<Option<T>> {
+        self.extensions()
+            .get(name)
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+    }
+
+    /// Serialize `value` and set it as the extension with the given `name`,
+    /// replacing the existing value.
+    fn set_extension<T: Serialize>(
+        &mut self,
+        name: impl Into<String>,
+        value: T,
+    ) -> serde_json::Result<()> {
+        let value = serde_json::to_value(value)?;
+        self.extensions_mut().insert(name.into(), value);
+        Ok(())
+    }
+
+    /// Remove the extension with the given `name`, returning its value if it was set.
+    fn remove_extension(&mut self, name: &str) -> Option<Value> {
+        self.extensions_mut().remove(name)
+    }
+}
+
+macro_rules! impl_extensions {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Extensions for $ty {
+                fn extensions(&self) -> &Object {
+                    &self.extensions
+                }
+
+                fn extensions_mut(&mut self) -> &mut Object {
+                    &mut self.extensions
+                }
+            }
+        )*
+    };
+}
+
+impl_extensions!(
+    OpenApi,
+    Info,
+    Contact,
+    License,
+    Server,
+    ServerVariable,
+    PathItem,
+    Operation,
+    Responses,
+    Components,
+    Response,
+    Parameter,
+    Example,
+    RequestBody,
+    Header,
+    SecurityScheme,
+    Link,
+    Callback,
+    MediaType,
+    Tag,
+    ExternalDocs,
+    Encoding,
+    Xml,
+    SchemaObject,
+);
+
+impl Extensions for OAuthFlows {
+    fn extensions(&self) -> &Object {
+        match self {
+            OAuthFlows::Implicit { extensions, .. }
+            | OAuthFlows::Password { extensions, .. }
+            | OAuthFlows::ClientCredentials { extensions, .. }
+            | OAuthFlows::AuthorizationCode { extensions, .. } => extensions,
+        }
+    }
+
+    fn extensions_mut(&mut self) -> &mut Object {
+        match self {
+            OAuthFlows::Implicit { extensions, .. }
+            | OAuthFlows::Password { extensions, .. }
+            | OAuthFlows::ClientCredentials { extensions, .. }
+            | OAuthFlows::AuthorizationCode { extensions, .. } => extensions,
+        }
+    }
+}
+
+/// Deserialize the specification extensions (fields starting with `x-`) of an object.
+/// Used for objects that also contain a flattened enum or map, otherwise the fields of the enum
+/// or map are also added to the extensions.
+fn deserialize_extensions<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Object, D::Error> {
+    let object = Object::deserialize(deserializer)?;
+    Ok(object
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("x-"))
+        .collect())
+}
+
+/// `OAuthFlows` only contains one flow, so the extensions of the flows object itself are skipped.
+fn deserialize_oauth_flows<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<OAuthFlows, D::Error> {
+    let object: serde_json::Map<String, Value> = Object::deserialize(deserializer)?
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("x-"))
+        .collect();
+    serde_json::from_value(Value::Object(object)).map_err(D::Error::custom)
+}
+
+// The derived schemas would allow any field for the flattened maps.
+#[cfg(feature = "impl_json_schema")]
+impl JsonSchema for Responses {
+    fn schema_name() -> String {
+        "Responses".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let response = gen.subschema_for::<RefOr<Response>>();
+        let mut schema = SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            ..SchemaObject::default()
+        };
+        let object = schema.object();
+        object
+            .properties
+            .insert("default".to_owned(), response.clone());
+        object
+            .pattern_properties
+            .insert("^[1-5](?:[0-9]{2}|XX)$".to_owned(), response);
+        object
+            .pattern_properties
+            .insert("^x-".to_owned(), schemars::schema::Schema::Bool(true));
+        object.additional_properties = Some(Box::new(schemars::schema::Schema::Bool(false)));
+        schema.into()
+    }
+}
+
+#[cfg(feature = "impl_json_schema")]
+impl JsonSchema for Callback {
+    fn schema_name() -> String {
+        "Callback".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            ..SchemaObject::default()
+        };
+        let object = schema.object();
+        object
+            .pattern_properties
+            .insert("^x-".to_owned(), schemars::schema::Schema::Bool(true));
+        object.additional_properties = Some(Box::new(gen.subschema_for::<PathItem>()));
+        schema.into()
+    }
+}
+
+/// Deserialize all fields of a flattened map, except for the specification extensions.
+fn deserialize_without_extensions<'de, D, T>(deserializer: D) -> Result<Map<String, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let object = Object::deserialize(deserializer)?;
+    object
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("x-"))
+        .map(|(key, value)| {
+            let value = serde_json::from_value(value).map_err(D::Error::custom)?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Deserialize a schema, allowing the boolean `exclusiveMinimum`/`exclusiveMaximum` of
+/// OpenAPI 3.0.
+fn deserialize_schema<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<SchemaObject, D::Error> {
+    schema_from_value(Value::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+fn deserialize_optional_schema<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SchemaObject>, D::Error> {
+    Option::<Value>::deserialize(deserializer)?
+        .map(schema_from_value)
+        .transpose()
+        .map_err(D::Error::custom)
+}
+
+fn deserialize_schemas<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Map<String, SchemaObject>, D::Error> {
+    Map::<String, Value>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(name, schema)| Ok((name, schema_from_value(schema).map_err(D::Error::custom)?)))
+        .collect()
+}
+
 fn is_false(b: impl std::borrow::Borrow<bool>) -> bool {
     !b.borrow()
 }
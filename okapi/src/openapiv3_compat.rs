@@ -0,0 +1,1378 @@
+//! Conversions between the [`openapi3`](crate::openapi3) structures and the types of the
+//! [`openapiv3`](https://docs.rs/openapiv3) crate, so a generated document can be used by tools
+//! built on that crate.
+//!
+//! The objects are converted field by field. Schemas are the exception: `openapiv3` has its own
+//! representation of a JSON Schema, so they are converted using their JSON value.
+//!
+//! Not everything can be represented by both, in that case the conversion returns a
+//! [`ConversionError`]. For example `openapiv3` has no references to callbacks, and Okapi
+//! only supports a single flow per OAuth2 security scheme.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use okapi::openapi3::OpenApi;
+//! use std::convert::TryFrom;
+//!
+//! let spec = OpenApi::new();
+//! let converted = openapiv3::OpenAPI::try_from(spec.clone()).unwrap();
+//! assert_eq!(converted.openapi, spec.openapi);
+//! assert_eq!(OpenApi::try_from(converted).unwrap(), spec);
+//! ```
+
+use crate::openapi3;
+use schemars::schema::{Schema, SchemaObject};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::iter::FromIterator;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+pub struct ConversionError {
+    pub msg: String,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl ConversionError {
+    fn new<S: AsRef<str>>(msg: S) -> Self {
+        ConversionError {
+            msg: msg.as_ref().to_owned(),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, ConversionError>;
+
+fn try_map<K, A, B, M>(
+    map: impl IntoIterator<Item = (K, A)>,
+    f: impl Fn(A) -> Result<B>,
+) -> Result<M>
+where
+    M: FromIterator<(K, B)>,
+{
+    map.into_iter()
+        .map(|(key, value)| Ok((key, f(value)?)))
+        .collect()
+}
+
+fn map_into<K, A, B: From<A>, M>(map: impl IntoIterator<Item = (K, A)>) -> M
+where
+    M: FromIterator<(K, B)>,
+{
+    map.into_iter()
+        .map(|(key, value)| (key, value.into()))
+        .collect()
+}
+
+fn ref_or_to_v3<T, U>(
+    ref_or: openapi3::RefOr<T>,
+    f: impl Fn(T) -> Result<U>,
+) -> Result<openapiv3::ReferenceOr<U>> {
+    Ok(match ref_or {
+        openapi3::RefOr::Ref(reference) => openapiv3::ReferenceOr::Reference {
+            reference: reference.reference,
+        },
+        openapi3::RefOr::Object(object) => openapiv3::ReferenceOr::Item(f(object)?),
+    })
+}
+
+fn ref_or_from_v3<T, U>(
+    ref_or: openapiv3::ReferenceOr<T>,
+    f: impl Fn(T) -> Result<U>,
+) -> Result<openapi3::RefOr<U>> {
+    Ok(match ref_or {
+        openapiv3::ReferenceOr::Reference { reference } => {
+            openapi3::RefOr::Ref(openapi3::Ref { reference })
+        }
+        openapiv3::ReferenceOr::Item(object) => openapi3::RefOr::Object(f(object)?),
+    })
+}
+
+fn schema_to_v3(schema: SchemaObject) -> Result<openapiv3::ReferenceOr<openapiv3::Schema>> {
+    serde_json::to_value(schema)
+        .and_then(serde_json::from_value)
+        .map_err(|err| ConversionError::new(format!("Could not convert schema: {}", err)))
+}
+
+fn schema_from_v3(schema: openapiv3::ReferenceOr<openapiv3::Schema>) -> Result<SchemaObject> {
+    let schema: Schema = serde_json::to_value(schema)
+        .and_then(serde_json::from_value)
+        .map_err(|err| ConversionError::new(format!("Could not convert schema: {}", err)))?;
+    Ok(schema.into_object())
+}
+
+impl TryFrom<openapi3::OpenApi> for openapiv3::OpenAPI {
+    type Error = ConversionError;
+
+    fn try_from(spec: openapi3::OpenApi) -> Result<Self> {
+        Ok(openapiv3::OpenAPI {
+            openapi: spec.openapi,
+            info: spec.info.into(),
+            servers: spec.servers.into_iter().map(Into::into).collect(),
+            paths: openapiv3::Paths {
+                paths: try_map(spec.paths, |path_item| match path_item.reference {
+                    Some(reference) => Ok(openapiv3::ReferenceOr::Reference { reference }),
+                    None => path_item.try_into().map(openapiv3::ReferenceOr::Item),
+                })?,
+                extensions: Default::default(),
+            },
+            components: spec.components.map(TryInto::try_into).transpose()?,
+            security: Some(security_to_v3(spec.security)).filter(|security| !security.is_empty()),
+            tags: spec.tags.into_iter().map(Into::into).collect(),
+            external_docs: spec.external_docs.map(Into::into),
+            extensions: spec.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::OpenAPI> for openapi3::OpenApi {
+    type Error = ConversionError;
+
+    fn try_from(spec: openapiv3::OpenAPI) -> Result<Self> {
+        Ok(openapi3::OpenApi {
+            openapi: spec.openapi,
+            info: spec.info.into(),
+            servers: spec.servers.into_iter().map(Into::into).collect(),
+            paths: try_map(spec.paths, |path_item| match path_item {
+                openapiv3::ReferenceOr::Reference { reference } => Ok(openapi3::PathItem {
+                    reference: Some(reference),
+                    ..openapi3::PathItem::default()
+                }),
+                openapiv3::ReferenceOr::Item(path_item) => path_item.try_into(),
+            })?,
+            components: spec.components.map(TryInto::try_into).transpose()?,
+            security: spec.security.map(security_from_v3).unwrap_or_default(),
+            tags: spec.tags.into_iter().map(Into::into).collect(),
+            external_docs: spec.external_docs.map(Into::into),
+            extensions: spec.extensions.into_iter().collect(),
+        })
+    }
+}
+
+fn security_to_v3(
+    security: Vec<openapi3::SecurityRequirement>,
+) -> Vec<openapiv3::SecurityRequirement> {
+    security
+        .into_iter()
+        .map(|requirement| requirement.into_iter().collect())
+        .collect()
+}
+
+fn security_from_v3(
+    security: Vec<openapiv3::SecurityRequirement>,
+) -> Vec<openapi3::SecurityRequirement> {
+    security
+        .into_iter()
+        .map(|requirement| requirement.into_iter().collect())
+        .collect()
+}
+
+impl From<openapi3::Info> for openapiv3::Info {
+    fn from(info: openapi3::Info) -> Self {
+        openapiv3::Info {
+            title: info.title,
+            description: info.description,
+            terms_of_service: info.terms_of_service,
+            contact: info.contact.map(Into::into),
+            license: info.license.map(Into::into),
+            version: info.version,
+            extensions: info.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::Info> for openapi3::Info {
+    fn from(info: openapiv3::Info) -> Self {
+        openapi3::Info {
+            title: info.title,
+            description: info.description,
+            terms_of_service: info.terms_of_service,
+            contact: info.contact.map(Into::into),
+            license: info.license.map(Into::into),
+            version: info.version,
+            extensions: info.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::Contact> for openapiv3::Contact {
+    fn from(contact: openapi3::Contact) -> Self {
+        openapiv3::Contact {
+            name: contact.name,
+            url: contact.url,
+            email: contact.email,
+            extensions: contact.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::Contact> for openapi3::Contact {
+    fn from(contact: openapiv3::Contact) -> Self {
+        openapi3::Contact {
+            name: contact.name,
+            url: contact.url,
+            email: contact.email,
+            extensions: contact.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::License> for openapiv3::License {
+    fn from(license: openapi3::License) -> Self {
+        openapiv3::License {
+            name: license.name,
+            url: license.url,
+            extensions: license.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::License> for openapi3::License {
+    fn from(license: openapiv3::License) -> Self {
+        openapi3::License {
+            name: license.name,
+            url: license.url,
+            extensions: license.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::Server> for openapiv3::Server {
+    fn from(server: openapi3::Server) -> Self {
+        openapiv3::Server {
+            url: server.url,
+            description: server.description,
+            variables: if server.variables.is_empty() {
+                None
+            } else {
+                Some(map_into(server.variables))
+            },
+            extensions: server.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::Server> for openapi3::Server {
+    fn from(server: openapiv3::Server) -> Self {
+        openapi3::Server {
+            url: server.url,
+            description: server.description,
+            variables: server.variables.map(map_into).unwrap_or_default(),
+            extensions: server.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::ServerVariable> for openapiv3::ServerVariable {
+    fn from(variable: openapi3::ServerVariable) -> Self {
+        openapiv3::ServerVariable {
+            enumeration: variable.enumeration.unwrap_or_default(),
+            default: variable.default,
+            description: variable.description,
+            extensions: variable.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::ServerVariable> for openapi3::ServerVariable {
+    fn from(variable: openapiv3::ServerVariable) -> Self {
+        openapi3::ServerVariable {
+            enumeration: Some(variable.enumeration).filter(|enumeration| !enumeration.is_empty()),
+            default: variable.default,
+            description: variable.description,
+            extensions: variable.extensions.into_iter().collect(),
+        }
+    }
+}
+
+/// Fails if the `reference` is set, `openapiv3` only supports this in the `paths`.
+impl TryFrom<openapi3::PathItem> for openapiv3::PathItem {
+    type Error = ConversionError;
+
+    fn try_from(path_item: openapi3::PathItem) -> Result<Self> {
+        if let Some(reference) = path_item.reference {
+            return Err(ConversionError::new(format!(
+                "Path item reference `{}` can only be used in `paths`.",
+                reference
+            )));
+        }
+        Ok(openapiv3::PathItem {
+            summary: path_item.summary,
+            description: path_item.description,
+            get: path_item.get.map(TryInto::try_into).transpose()?,
+            put: path_item.put.map(TryInto::try_into).transpose()?,
+            post: path_item.post.map(TryInto::try_into).transpose()?,
+            delete: path_item.delete.map(TryInto::try_into).transpose()?,
+            options: path_item.options.map(TryInto::try_into).transpose()?,
+            head: path_item.head.map(TryInto::try_into).transpose()?,
+            patch: path_item.patch.map(TryInto::try_into).transpose()?,
+            trace: path_item.trace.map(TryInto::try_into).transpose()?,
+            servers: path_item
+                .servers
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            parameters: parameters_to_v3(path_item.parameters)?,
+            extensions: path_item.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::PathItem> for openapi3::PathItem {
+    type Error = ConversionError;
+
+    fn try_from(path_item: openapiv3::PathItem) -> Result<Self> {
+        Ok(openapi3::PathItem {
+            reference: None,
+            summary: path_item.summary,
+            description: path_item.description,
+            get: path_item.get.map(TryInto::try_into).transpose()?,
+            put: path_item.put.map(TryInto::try_into).transpose()?,
+            post: path_item.post.map(TryInto::try_into).transpose()?,
+            delete: path_item.delete.map(TryInto::try_into).transpose()?,
+            options: path_item.options.map(TryInto::try_into).transpose()?,
+            head: path_item.head.map(TryInto::try_into).transpose()?,
+            patch: path_item.patch.map(TryInto::try_into).transpose()?,
+            trace: path_item.trace.map(TryInto::try_into).transpose()?,
+            servers: servers_from_v3(path_item.servers),
+            parameters: parameters_from_v3(path_item.parameters)?,
+            extensions: path_item.extensions.into_iter().collect(),
+        })
+    }
+}
+
+fn servers_from_v3(servers: Vec<openapiv3::Server>) -> Option<Vec<openapi3::Server>> {
+    Some(servers.into_iter().map(Into::into).collect())
+        .filter(|servers: &Vec<_>| !servers.is_empty())
+}
+
+fn parameters_to_v3(
+    parameters: Vec<openapi3::RefOr<openapi3::Parameter>>,
+) -> Result<Vec<openapiv3::ReferenceOr<openapiv3::Parameter>>> {
+    parameters
+        .into_iter()
+        .map(|parameter| ref_or_to_v3(parameter, TryInto::try_into))
+        .collect()
+}
+
+fn parameters_from_v3(
+    parameters: Vec<openapiv3::ReferenceOr<openapiv3::Parameter>>,
+) -> Result<Vec<openapi3::RefOr<openapi3::Parameter>>> {
+    parameters
+        .into_iter()
+        .map(|parameter| ref_or_from_v3(parameter, TryInto::try_into))
+        .collect()
+}
+
+/// Fails if one of the `callbacks` is a reference, `openapiv3` does not support this.
+impl TryFrom<openapi3::Operation> for openapiv3::Operation {
+    type Error = ConversionError;
+
+    fn try_from(operation: openapi3::Operation) -> Result<Self> {
+        Ok(openapiv3::Operation {
+            tags: operation.tags,
+            summary: operation.summary,
+            description: operation.description,
+            external_docs: operation.external_docs.map(Into::into),
+            operation_id: operation.operation_id,
+            parameters: parameters_to_v3(operation.parameters)?,
+            request_body: operation
+                .request_body
+                .map(|request_body| ref_or_to_v3(request_body, TryInto::try_into))
+                .transpose()?,
+            responses: operation.responses.try_into()?,
+            callbacks: try_map(operation.callbacks, |callback| match callback {
+                openapi3::RefOr::Ref(reference) => Err(ConversionError::new(format!(
+                    "Callback reference `{}` is not supported by `openapiv3`.",
+                    reference.reference
+                ))),
+                openapi3::RefOr::Object(callback) => callback_to_v3(callback),
+            })?,
+            deprecated: operation.deprecated,
+            security: operation.security.map(security_to_v3),
+            servers: operation
+                .servers
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            extensions: operation.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Operation> for openapi3::Operation {
+    type Error = ConversionError;
+
+    fn try_from(operation: openapiv3::Operation) -> Result<Self> {
+        Ok(openapi3::Operation {
+            tags: operation.tags,
+            summary: operation.summary,
+            description: operation.description,
+            external_docs: operation.external_docs.map(Into::into),
+            operation_id: operation.operation_id,
+            parameters: parameters_from_v3(operation.parameters)?,
+            request_body: operation
+                .request_body
+                .map(|request_body| ref_or_from_v3(request_body, TryInto::try_into))
+                .transpose()?,
+            responses: operation.responses.try_into()?,
+            callbacks: try_map(operation.callbacks, |callback| {
+                callback_from_v3(callback).map(openapi3::RefOr::Object)
+            })?,
+            deprecated: operation.deprecated,
+            security: operation.security.map(security_from_v3),
+            servers: servers_from_v3(operation.servers),
+            extensions: operation.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// The extensions of the callback are lost, `openapiv3` does not support them.
+fn callback_to_v3(callback: openapi3::Callback) -> Result<openapiv3::Callback> {
+    try_map(callback.callbacks, TryInto::try_into)
+}
+
+fn callback_from_v3(callback: openapiv3::Callback) -> Result<openapi3::Callback> {
+    Ok(openapi3::Callback {
+        callbacks: try_map(callback, TryInto::try_into)?,
+        extensions: Default::default(),
+    })
+}
+
+/// Fails if a status code is not a number or a range like `2XX`.
+impl TryFrom<openapi3::Responses> for openapiv3::Responses {
+    type Error = ConversionError;
+
+    fn try_from(responses: openapi3::Responses) -> Result<Self> {
+        Ok(openapiv3::Responses {
+            default: responses
+                .default
+                .map(|response| ref_or_to_v3(response, TryInto::try_into))
+                .transpose()?,
+            responses: responses
+                .responses
+                .into_iter()
+                .map(|(status, response)| {
+                    Ok((
+                        status_code_to_v3(&status)?,
+                        ref_or_to_v3(response, TryInto::try_into)?,
+                    ))
+                })
+                .collect::<Result<_>>()?,
+            extensions: responses.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Responses> for openapi3::Responses {
+    type Error = ConversionError;
+
+    fn try_from(responses: openapiv3::Responses) -> Result<Self> {
+        Ok(openapi3::Responses {
+            default: responses
+                .default
+                .map(|response| ref_or_from_v3(response, TryInto::try_into))
+                .transpose()?,
+            responses: responses
+                .responses
+                .into_iter()
+                .map(|(status, response)| {
+                    Ok((
+                        status.to_string(),
+                        ref_or_from_v3(response, TryInto::try_into)?,
+                    ))
+                })
+                .collect::<Result<_>>()?,
+            extensions: responses.extensions.into_iter().collect(),
+        })
+    }
+}
+
+fn status_code_to_v3(status: &str) -> Result<openapiv3::StatusCode> {
+    match status.as_bytes() {
+        [range @ b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => {
+            Ok(openapiv3::StatusCode::Range(u16::from(range - b'0')))
+        }
+        _ => status
+            .parse()
+            .map(openapiv3::StatusCode::Code)
+            .map_err(|_| ConversionError::new(format!("Invalid status code `{}`.", status))),
+    }
+}
+
+impl TryFrom<openapi3::Response> for openapiv3::Response {
+    type Error = ConversionError;
+
+    fn try_from(response: openapi3::Response) -> Result<Self> {
+        Ok(openapiv3::Response {
+            description: response.description,
+            headers: try_map(response.headers, |header| {
+                ref_or_to_v3(header, TryInto::try_into)
+            })?,
+            content: try_map(response.content, TryInto::try_into)?,
+            links: try_map(response.links, |link| ref_or_to_v3(link, TryInto::try_into))?,
+            extensions: response.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Response> for openapi3::Response {
+    type Error = ConversionError;
+
+    fn try_from(response: openapiv3::Response) -> Result<Self> {
+        Ok(openapi3::Response {
+            description: response.description,
+            headers: try_map(response.headers, |header| {
+                ref_or_from_v3(header, TryInto::try_into)
+            })?,
+            content: try_map(response.content, TryInto::try_into)?,
+            links: try_map(response.links, |link| {
+                ref_or_from_v3(link, |link| Ok(link.into()))
+            })?,
+            extensions: response.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// The parts of a `ParameterValue` that `openapiv3` stores in different places.
+struct ParameterFormat {
+    format: openapiv3::ParameterSchemaOrContent,
+    style: Option<openapi3::ParameterStyle>,
+    explode: Option<bool>,
+    allow_reserved: bool,
+    example: Option<Value>,
+    examples: Vec<(String, openapiv3::ReferenceOr<openapiv3::Example>)>,
+}
+
+fn parameter_value_to_v3(value: openapi3::ParameterValue) -> Result<ParameterFormat> {
+    Ok(match value {
+        openapi3::ParameterValue::Schema {
+            style,
+            explode,
+            allow_reserved,
+            schema,
+            example,
+            examples,
+        } => ParameterFormat {
+            format: openapiv3::ParameterSchemaOrContent::Schema(schema_to_v3(schema)?),
+            style,
+            explode,
+            allow_reserved,
+            example,
+            examples: examples
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, example)| (name, openapiv3::ReferenceOr::Item(example.into())))
+                .collect(),
+        },
+        openapi3::ParameterValue::Content { content } => ParameterFormat {
+            format: openapiv3::ParameterSchemaOrContent::Content(try_map(
+                content,
+                TryInto::try_into,
+            )?),
+            style: None,
+            explode: None,
+            allow_reserved: false,
+            example: None,
+            examples: Vec::new(),
+        },
+    })
+}
+
+/// Fails if one of the `examples` is a reference, Okapi does not support this.
+fn parameter_value_from_v3(
+    format: openapiv3::ParameterSchemaOrContent,
+    style: Option<openapi3::ParameterStyle>,
+    explode: Option<bool>,
+    allow_reserved: bool,
+    example: Option<Value>,
+    examples: impl IntoIterator<Item = (String, openapiv3::ReferenceOr<openapiv3::Example>)>,
+) -> Result<openapi3::ParameterValue> {
+    Ok(match format {
+        openapiv3::ParameterSchemaOrContent::Schema(schema) => openapi3::ParameterValue::Schema {
+            style,
+            explode,
+            allow_reserved,
+            schema: schema_from_v3(schema)?,
+            example,
+            examples: Some(try_map(examples, examples_from_v3)?)
+                .filter(|examples: &crate::Map<_, _>| !examples.is_empty()),
+        },
+        openapiv3::ParameterSchemaOrContent::Content(content) => {
+            openapi3::ParameterValue::Content {
+                content: try_map(content, TryInto::try_into)?,
+            }
+        }
+    })
+}
+
+fn examples_from_v3(
+    example: openapiv3::ReferenceOr<openapiv3::Example>,
+) -> Result<openapi3::Example> {
+    match example {
+        openapiv3::ReferenceOr::Reference { reference } => Err(ConversionError::new(format!(
+            "Example reference `{}` is not supported by Okapi.",
+            reference
+        ))),
+        openapiv3::ReferenceOr::Item(example) => Ok(example.into()),
+    }
+}
+
+/// Fails if the `location` is unknown, or the `style` can not be used for that location.
+impl TryFrom<openapi3::Parameter> for openapiv3::Parameter {
+    type Error = ConversionError;
+
+    fn try_from(parameter: openapi3::Parameter) -> Result<Self> {
+        use openapi3::ParameterStyle;
+
+        let openapi3::Parameter {
+            name,
+            location,
+            description,
+            required,
+            deprecated,
+            allow_empty_value,
+            value,
+            extensions,
+        } = parameter;
+        let ParameterFormat {
+            format,
+            style,
+            explode,
+            allow_reserved,
+            example,
+            examples,
+        } = parameter_value_to_v3(value)?;
+        let style_error = |style: ParameterStyle| {
+            ConversionError::new(format!(
+                "Style `{:?}` can not be used for the {} parameter `{}`.",
+                style, location, name
+            ))
+        };
+        let parameter_data = openapiv3::ParameterData {
+            name: name.clone(),
+            description,
+            required,
+            deprecated: deprecated.then_some(true),
+            format,
+            example,
+            examples: examples.into_iter().collect(),
+            explode,
+            extensions: extensions.into_iter().collect(),
+        };
+        Ok(match location.as_str() {
+            "query" => openapiv3::Parameter::Query {
+                parameter_data,
+                allow_reserved,
+                style: match style {
+                    None | Some(ParameterStyle::Form) => openapiv3::QueryStyle::Form,
+                    Some(ParameterStyle::SpaceDelimited) => openapiv3::QueryStyle::SpaceDelimited,
+                    Some(ParameterStyle::PipeDelimited) => openapiv3::QueryStyle::PipeDelimited,
+                    Some(ParameterStyle::DeepObject) => openapiv3::QueryStyle::DeepObject,
+                    Some(style) => return Err(style_error(style)),
+                },
+                allow_empty_value: allow_empty_value.then_some(true),
+            },
+            "header" => openapiv3::Parameter::Header {
+                parameter_data,
+                style: match style {
+                    None | Some(ParameterStyle::Simple) => openapiv3::HeaderStyle::Simple,
+                    Some(style) => return Err(style_error(style)),
+                },
+            },
+            "path" => openapiv3::Parameter::Path {
+                parameter_data,
+                style: match style {
+                    None | Some(ParameterStyle::Simple) => openapiv3::PathStyle::Simple,
+                    Some(ParameterStyle::Matrix) => openapiv3::PathStyle::Matrix,
+                    Some(ParameterStyle::Label) => openapiv3::PathStyle::Label,
+                    Some(style) => return Err(style_error(style)),
+                },
+            },
+            "cookie" => openapiv3::Parameter::Cookie {
+                parameter_data,
+                style: match style {
+                    None | Some(ParameterStyle::Form) => openapiv3::CookieStyle::Form,
+                    Some(style) => return Err(style_error(style)),
+                },
+            },
+            location => {
+                return Err(ConversionError::new(format!(
+                    "Unknown location `{}` of parameter `{}`.",
+                    location, name
+                )))
+            }
+        })
+    }
+}
+
+/// The `style` is only set if it is not the default style for the location of the parameter.
+impl TryFrom<openapiv3::Parameter> for openapi3::Parameter {
+    type Error = ConversionError;
+
+    fn try_from(parameter: openapiv3::Parameter) -> Result<Self> {
+        use openapi3::ParameterStyle;
+
+        let (location, parameter_data, style, allow_reserved, allow_empty_value) = match parameter {
+            openapiv3::Parameter::Query {
+                parameter_data,
+                allow_reserved,
+                style,
+                allow_empty_value,
+            } => {
+                let style = match style {
+                    openapiv3::QueryStyle::Form => None,
+                    openapiv3::QueryStyle::SpaceDelimited => Some(ParameterStyle::SpaceDelimited),
+                    openapiv3::QueryStyle::PipeDelimited => Some(ParameterStyle::PipeDelimited),
+                    openapiv3::QueryStyle::DeepObject => Some(ParameterStyle::DeepObject),
+                };
+                let allow_empty_value = allow_empty_value.unwrap_or_default();
+                (
+                    "query",
+                    parameter_data,
+                    style,
+                    allow_reserved,
+                    allow_empty_value,
+                )
+            }
+            openapiv3::Parameter::Header {
+                parameter_data,
+                style: openapiv3::HeaderStyle::Simple,
+            } => ("header", parameter_data, None, false, false),
+            openapiv3::Parameter::Path {
+                parameter_data,
+                style,
+            } => {
+                let style = match style {
+                    openapiv3::PathStyle::Simple => None,
+                    openapiv3::PathStyle::Matrix => Some(ParameterStyle::Matrix),
+                    openapiv3::PathStyle::Label => Some(ParameterStyle::Label),
+                };
+                ("path", parameter_data, style, false, false)
+            }
+            openapiv3::Parameter::Cookie {
+                parameter_data,
+                style: openapiv3::CookieStyle::Form,
+            } => ("cookie", parameter_data, None, false, false),
+        };
+        Ok(openapi3::Parameter {
+            name: parameter_data.name,
+            location: location.to_owned(),
+            description: parameter_data.description,
+            required: parameter_data.required,
+            deprecated: parameter_data.deprecated.unwrap_or_default(),
+            allow_empty_value,
+            value: parameter_value_from_v3(
+                parameter_data.format,
+                style,
+                parameter_data.explode,
+                allow_reserved,
+                parameter_data.example,
+                parameter_data.examples,
+            )?,
+            extensions: parameter_data.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// The `explode` and `allowReserved` fields are lost, `openapiv3` does not support them for
+/// headers.
+impl TryFrom<openapi3::Header> for openapiv3::Header {
+    type Error = ConversionError;
+
+    fn try_from(header: openapi3::Header) -> Result<Self> {
+        let ParameterFormat {
+            format,
+            style,
+            example,
+            examples,
+            ..
+        } = parameter_value_to_v3(header.value)?;
+        if let Some(style) = style.filter(|style| *style != openapi3::ParameterStyle::Simple) {
+            return Err(ConversionError::new(format!(
+                "Style `{:?}` can not be used for a header.",
+                style
+            )));
+        }
+        Ok(openapiv3::Header {
+            description: header.description,
+            style: openapiv3::HeaderStyle::Simple,
+            required: header.required,
+            deprecated: header.deprecated.then_some(true),
+            format,
+            example,
+            examples: examples.into_iter().collect(),
+            extensions: header.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Header> for openapi3::Header {
+    type Error = ConversionError;
+
+    fn try_from(header: openapiv3::Header) -> Result<Self> {
+        Ok(openapi3::Header {
+            description: header.description,
+            required: header.required,
+            deprecated: header.deprecated.unwrap_or_default(),
+            allow_empty_value: false,
+            value: parameter_value_from_v3(
+                header.format,
+                None,
+                None,
+                false,
+                header.example,
+                header.examples,
+            )?,
+            extensions: header.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl From<openapi3::Example> for openapiv3::Example {
+    fn from(example: openapi3::Example) -> Self {
+        let (value, external_value) = match example.value {
+            openapi3::ExampleValue::Value(value) => (Some(value), None),
+            openapi3::ExampleValue::ExternalValue(url) => (None, Some(url)),
+        };
+        openapiv3::Example {
+            summary: example.summary,
+            description: example.description,
+            value,
+            external_value,
+            extensions: example.extensions.into_iter().collect(),
+        }
+    }
+}
+
+/// An example without a `value` or `externalValue` gets a `null` value.
+impl From<openapiv3::Example> for openapi3::Example {
+    fn from(example: openapiv3::Example) -> Self {
+        let value = match (example.value, example.external_value) {
+            (Some(value), _) => openapi3::ExampleValue::Value(value),
+            (None, Some(url)) => openapi3::ExampleValue::ExternalValue(url),
+            (None, None) => openapi3::ExampleValue::Value(Value::Null),
+        };
+        openapi3::Example {
+            summary: example.summary,
+            description: example.description,
+            value,
+            extensions: example.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<openapi3::RequestBody> for openapiv3::RequestBody {
+    type Error = ConversionError;
+
+    fn try_from(request_body: openapi3::RequestBody) -> Result<Self> {
+        Ok(openapiv3::RequestBody {
+            description: request_body.description,
+            content: try_map(request_body.content, TryInto::try_into)?,
+            required: request_body.required,
+            extensions: request_body.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::RequestBody> for openapi3::RequestBody {
+    type Error = ConversionError;
+
+    fn try_from(request_body: openapiv3::RequestBody) -> Result<Self> {
+        Ok(openapi3::RequestBody {
+            description: request_body.description,
+            content: try_map(request_body.content, TryInto::try_into)?,
+            required: request_body.required,
+            extensions: request_body.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapi3::MediaType> for openapiv3::MediaType {
+    type Error = ConversionError;
+
+    fn try_from(media_type: openapi3::MediaType) -> Result<Self> {
+        Ok(openapiv3::MediaType {
+            schema: media_type.schema.map(schema_to_v3).transpose()?,
+            example: media_type.example,
+            examples: media_type
+                .examples
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, example)| (name, openapiv3::ReferenceOr::Item(example.into())))
+                .collect(),
+            encoding: try_map(media_type.encoding, TryInto::try_into)?,
+            extensions: media_type.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// Fails if one of the `examples` is a reference, Okapi does not support this.
+impl TryFrom<openapiv3::MediaType> for openapi3::MediaType {
+    type Error = ConversionError;
+
+    fn try_from(media_type: openapiv3::MediaType) -> Result<Self> {
+        Ok(openapi3::MediaType {
+            schema: media_type.schema.map(schema_from_v3).transpose()?,
+            example: media_type.example,
+            examples: Some(try_map(media_type.examples, examples_from_v3)?)
+                .filter(|examples: &crate::Map<_, _>| !examples.is_empty()),
+            encoding: try_map(media_type.encoding, TryInto::try_into)?,
+            extensions: media_type.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// Fails if the `style` is not `form`, `spaceDelimited`, `pipeDelimited` or `deepObject`.
+impl TryFrom<openapi3::Encoding> for openapiv3::Encoding {
+    type Error = ConversionError;
+
+    fn try_from(encoding: openapi3::Encoding) -> Result<Self> {
+        let style = match encoding.style.as_deref() {
+            None => None,
+            Some("form") => Some(openapiv3::QueryStyle::Form),
+            Some("spaceDelimited") => Some(openapiv3::QueryStyle::SpaceDelimited),
+            Some("pipeDelimited") => Some(openapiv3::QueryStyle::PipeDelimited),
+            Some("deepObject") => Some(openapiv3::QueryStyle::DeepObject),
+            Some(style) => {
+                return Err(ConversionError::new(format!(
+                    "Unknown encoding style `{}`.",
+                    style
+                )))
+            }
+        };
+        // `explode` defaults to `true` for the `form` style.
+        let explode = encoding
+            .explode
+            .unwrap_or(matches!(style, None | Some(openapiv3::QueryStyle::Form)));
+        Ok(openapiv3::Encoding {
+            content_type: encoding.content_type,
+            headers: try_map(encoding.headers, |header| {
+                ref_or_to_v3(header, TryInto::try_into)
+            })?,
+            style,
+            explode,
+            allow_reserved: encoding.allow_reserved,
+            extensions: encoding.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Encoding> for openapi3::Encoding {
+    type Error = ConversionError;
+
+    fn try_from(encoding: openapiv3::Encoding) -> Result<Self> {
+        let style = encoding.style.map(|style| {
+            match style {
+                openapiv3::QueryStyle::Form => "form",
+                openapiv3::QueryStyle::SpaceDelimited => "spaceDelimited",
+                openapiv3::QueryStyle::PipeDelimited => "pipeDelimited",
+                openapiv3::QueryStyle::DeepObject => "deepObject",
+            }
+            .to_owned()
+        });
+        Ok(openapi3::Encoding {
+            content_type: encoding.content_type,
+            headers: try_map(encoding.headers, |header| {
+                ref_or_from_v3(header, TryInto::try_into)
+            })?,
+            style,
+            explode: Some(encoding.explode),
+            allow_reserved: encoding.allow_reserved,
+            extensions: encoding.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapi3::Components> for openapiv3::Components {
+    type Error = ConversionError;
+
+    fn try_from(components: openapi3::Components) -> Result<Self> {
+        Ok(openapiv3::Components {
+            schemas: try_map(components.schemas, schema_to_v3)?,
+            responses: try_map(components.responses, |response| {
+                ref_or_to_v3(response, TryInto::try_into)
+            })?,
+            parameters: try_map(components.parameters, |parameter| {
+                ref_or_to_v3(parameter, TryInto::try_into)
+            })?,
+            examples: try_map(components.examples, |example| {
+                ref_or_to_v3(example, |example| Ok(example.into()))
+            })?,
+            request_bodies: try_map(components.request_bodies, |request_body| {
+                ref_or_to_v3(request_body, TryInto::try_into)
+            })?,
+            headers: try_map(components.headers, |header| {
+                ref_or_to_v3(header, TryInto::try_into)
+            })?,
+            security_schemes: try_map(components.security_schemes, |scheme| {
+                ref_or_to_v3(scheme, TryInto::try_into)
+            })?,
+            links: try_map(components.links, |link| {
+                ref_or_to_v3(link, TryInto::try_into)
+            })?,
+            callbacks: try_map(components.callbacks, |callback| {
+                ref_or_to_v3(callback, callback_to_v3)
+            })?,
+            extensions: components.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<openapiv3::Components> for openapi3::Components {
+    type Error = ConversionError;
+
+    fn try_from(components: openapiv3::Components) -> Result<Self> {
+        Ok(openapi3::Components {
+            schemas: try_map(components.schemas, schema_from_v3)?,
+            responses: try_map(components.responses, |response| {
+                ref_or_from_v3(response, TryInto::try_into)
+            })?,
+            parameters: try_map(components.parameters, |parameter| {
+                ref_or_from_v3(parameter, TryInto::try_into)
+            })?,
+            examples: try_map(components.examples, |example| {
+                ref_or_from_v3(example, |example| Ok(example.into()))
+            })?,
+            request_bodies: try_map(components.request_bodies, |request_body| {
+                ref_or_from_v3(request_body, TryInto::try_into)
+            })?,
+            headers: try_map(components.headers, |header| {
+                ref_or_from_v3(header, TryInto::try_into)
+            })?,
+            security_schemes: try_map(components.security_schemes, |scheme| {
+                ref_or_from_v3(scheme, TryInto::try_into)
+            })?,
+            links: try_map(components.links, |link| {
+                ref_or_from_v3(link, |link| Ok(link.into()))
+            })?,
+            callbacks: try_map(components.callbacks, |callback| {
+                ref_or_from_v3(callback, callback_from_v3)
+            })?,
+            extensions: components.extensions.into_iter().collect(),
+        })
+    }
+}
+
+/// Fails if the location of an `apiKey` scheme is not `query`, `header` or `cookie`.
+impl TryFrom<openapi3::SecurityScheme> for openapiv3::SecurityScheme {
+    type Error = ConversionError;
+
+    fn try_from(scheme: openapi3::SecurityScheme) -> Result<Self> {
+        let description = scheme.description;
+        let extensions = scheme.extensions.into_iter().collect();
+        Ok(match scheme.data {
+            openapi3::SecuritySchemeData::ApiKey { name, location } => {
+                openapiv3::SecurityScheme::APIKey {
+                    location: match location.as_str() {
+                        "query" => openapiv3::APIKeyLocation::Query,
+                        "header" => openapiv3::APIKeyLocation::Header,
+                        "cookie" => openapiv3::APIKeyLocation::Cookie,
+                        _ => {
+                            return Err(ConversionError::new(format!(
+                                "Unknown location `{}` of API key `{}`.",
+                                location, name
+                            )))
+                        }
+                    },
+                    name,
+                    description,
+                    extensions,
+                }
+            }
+            openapi3::SecuritySchemeData::Http {
+                scheme,
+                bearer_format,
+            } => openapiv3::SecurityScheme::HTTP {
+                scheme,
+                bearer_format,
+                description,
+                extensions,
+            },
+            openapi3::SecuritySchemeData::OAuth2 { flows } => openapiv3::SecurityScheme::OAuth2 {
+                flows: flows.into(),
+                description,
+                extensions,
+            },
+            openapi3::SecuritySchemeData::OpenIdConnect {
+                open_id_connect_url,
+            } => openapiv3::SecurityScheme::OpenIDConnect {
+                open_id_connect_url,
+                description,
+                extensions,
+            },
+        })
+    }
+}
+
+/// Fails if an `oauth2` scheme has more than one flow, Okapi only supports a single flow.
+impl TryFrom<openapiv3::SecurityScheme> for openapi3::SecurityScheme {
+    type Error = ConversionError;
+
+    fn try_from(scheme: openapiv3::SecurityScheme) -> Result<Self> {
+        let (data, description, extensions) = match scheme {
+            openapiv3::SecurityScheme::APIKey {
+                location,
+                name,
+                description,
+                extensions,
+            } => {
+                let location = match location {
+                    openapiv3::APIKeyLocation::Query => "query",
+                    openapiv3::APIKeyLocation::Header => "header",
+                    openapiv3::APIKeyLocation::Cookie => "cookie",
+                };
+                let data = openapi3::SecuritySchemeData::ApiKey {
+                    name,
+                    location: location.to_owned(),
+                };
+                (data, description, extensions)
+            }
+            openapiv3::SecurityScheme::HTTP {
+                scheme,
+                bearer_format,
+                description,
+                extensions,
+            } => {
+                let data = openapi3::SecuritySchemeData::Http {
+                    scheme,
+                    bearer_format,
+                };
+                (data, description, extensions)
+            }
+            openapiv3::SecurityScheme::OAuth2 {
+                flows,
+                description,
+                extensions,
+            } => {
+                let data = openapi3::SecuritySchemeData::OAuth2 {
+                    flows: flows.try_into()?,
+                };
+                (data, description, extensions)
+            }
+            openapiv3::SecurityScheme::OpenIDConnect {
+                open_id_connect_url,
+                description,
+                extensions,
+            } => {
+                let data = openapi3::SecuritySchemeData::OpenIdConnect {
+                    open_id_connect_url,
+                };
+                (data, description, extensions)
+            }
+        };
+        Ok(openapi3::SecurityScheme {
+            description,
+            data,
+            extensions: extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl From<openapi3::OAuthFlows> for openapiv3::OAuth2Flows {
+    fn from(flows: openapi3::OAuthFlows) -> Self {
+        let mut converted = openapiv3::OAuth2Flows::default();
+        match flows {
+            openapi3::OAuthFlows::Implicit {
+                authorization_url,
+                refresh_url,
+                scopes,
+                extensions,
+            } => {
+                converted.implicit = Some(openapiv3::ImplicitOAuth2Flow {
+                    authorization_url,
+                    refresh_url,
+                    scopes: scopes.into_iter().collect(),
+                    extensions: extensions.into_iter().collect(),
+                })
+            }
+            openapi3::OAuthFlows::Password {
+                token_url,
+                refresh_url,
+                scopes,
+                extensions,
+            } => {
+                converted.password = Some(openapiv3::PasswordOAuth2Flow {
+                    refresh_url,
+                    token_url,
+                    scopes: scopes.into_iter().collect(),
+                    extensions: extensions.into_iter().collect(),
+                })
+            }
+            openapi3::OAuthFlows::ClientCredentials {
+                token_url,
+                refresh_url,
+                scopes,
+                extensions,
+            } => {
+                converted.client_credentials = Some(openapiv3::ClientCredentialsOAuth2Flow {
+                    refresh_url,
+                    token_url,
+                    scopes: scopes.into_iter().collect(),
+                    extensions: extensions.into_iter().collect(),
+                })
+            }
+            openapi3::OAuthFlows::AuthorizationCode {
+                authorization_url,
+                token_url,
+                refresh_url,
+                scopes,
+                extensions,
+            } => {
+                converted.authorization_code = Some(openapiv3::AuthorizationCodeOAuth2Flow {
+                    authorization_url,
+                    token_url,
+                    refresh_url,
+                    scopes: scopes.into_iter().collect(),
+                    extensions: extensions.into_iter().collect(),
+                })
+            }
+        }
+        converted
+    }
+}
+
+/// Fails if there is not exactly one flow, Okapi only supports a single flow.
+impl TryFrom<openapiv3::OAuth2Flows> for openapi3::OAuthFlows {
+    type Error = ConversionError;
+
+    fn try_from(flows: openapiv3::OAuth2Flows) -> Result<Self> {
+        let mut converted = Vec::new();
+        if let Some(flow) = flows.implicit {
+            converted.push(openapi3::OAuthFlows::Implicit {
+                authorization_url: flow.authorization_url,
+                refresh_url: flow.refresh_url,
+                scopes: flow.scopes.into_iter().collect(),
+                extensions: flow.extensions.into_iter().collect(),
+            });
+        }
+        if let Some(flow) = flows.password {
+            converted.push(openapi3::OAuthFlows::Password {
+                token_url: flow.token_url,
+                refresh_url: flow.refresh_url,
+                scopes: flow.scopes.into_iter().collect(),
+                extensions: flow.extensions.into_iter().collect(),
+            });
+        }
+        if let Some(flow) = flows.client_credentials {
+            converted.push(openapi3::OAuthFlows::ClientCredentials {
+                token_url: flow.token_url,
+                refresh_url: flow.refresh_url,
+                scopes: flow.scopes.into_iter().collect(),
+                extensions: flow.extensions.into_iter().collect(),
+            });
+        }
+        if let Some(flow) = flows.authorization_code {
+            converted.push(openapi3::OAuthFlows::AuthorizationCode {
+                authorization_url: flow.authorization_url,
+                token_url: flow.token_url,
+                refresh_url: flow.refresh_url,
+                scopes: flow.scopes.into_iter().collect(),
+                extensions: flow.extensions.into_iter().collect(),
+            });
+        }
+        if converted.len() != 1 {
+            return Err(ConversionError::new(format!(
+                "Expected exactly one OAuth2 flow, found {}.",
+                converted.len()
+            )));
+        }
+        Ok(converted.remove(0))
+    }
+}
+
+/// Fails if neither the `operationRef` nor the `operationId` is set.
+impl TryFrom<openapi3::Link> for openapiv3::Link {
+    type Error = ConversionError;
+
+    fn try_from(link: openapi3::Link) -> Result<Self> {
+        let operation = match (link.operation_ref, link.operation_id) {
+            (Some(operation_ref), _) => openapiv3::LinkOperation::OperationRef(operation_ref),
+            (None, Some(operation_id)) => openapiv3::LinkOperation::OperationId(operation_id),
+            (None, None) => {
+                return Err(ConversionError::new(
+                    "A link needs an `operationRef` or `operationId`.",
+                ))
+            }
+        };
+        Ok(openapiv3::Link {
+            description: link.description,
+            operation,
+            request_body: link.request_body,
+            parameters: link.parameters.into_iter().collect(),
+            server: link.server.map(Into::into),
+            extensions: link.extensions.into_iter().collect(),
+        })
+    }
+}
+
+impl From<openapiv3::Link> for openapi3::Link {
+    fn from(link: openapiv3::Link) -> Self {
+        let (operation_ref, operation_id) = match link.operation {
+            openapiv3::LinkOperation::OperationRef(operation_ref) => (Some(operation_ref), None),
+            openapiv3::LinkOperation::OperationId(operation_id) => (None, Some(operation_id)),
+        };
+        openapi3::Link {
+            operation_ref,
+            operation_id,
+            parameters: link.parameters.into_iter().collect(),
+            request_body: link.request_body,
+            description: link.description,
+            server: link.server.map(Into::into),
+            extensions: link.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::Tag> for openapiv3::Tag {
+    fn from(tag: openapi3::Tag) -> Self {
+        openapiv3::Tag {
+            name: tag.name,
+            description: tag.description,
+            external_docs: tag.external_docs.map(Into::into),
+            extensions: tag.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::Tag> for openapi3::Tag {
+    fn from(tag: openapiv3::Tag) -> Self {
+        openapi3::Tag {
+            name: tag.name,
+            description: tag.description,
+            external_docs: tag.external_docs.map(Into::into),
+            extensions: tag.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapi3::ExternalDocs> for openapiv3::ExternalDocumentation {
+    fn from(docs: openapi3::ExternalDocs) -> Self {
+        openapiv3::ExternalDocumentation {
+            description: docs.description,
+            url: docs.url,
+            extensions: docs.extensions.into_iter().collect(),
+        }
+    }
+}
+
+impl From<openapiv3::ExternalDocumentation> for openapi3::ExternalDocs {
+    fn from(docs: openapiv3::ExternalDocumentation) -> Self {
+        openapi3::ExternalDocs {
+            description: docs.description,
+            url: docs.url,
+            extensions: docs.extensions.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Conversions between the [`Schemars`](https://docs.rs/schemars) 0.8 schema types used throughout
+//! the `openapi3` structures and the new [`Schema`](schemars1::Schema) representation
+//! of `Schemars` 1.0.
+//!
+//! Both versions represent a JSON Schema, so the conversion is done using their JSON value.
+
+use crate::Map;
+use schemars::schema::{Schema, SchemaObject};
+
+/// Convert a `Schemars` 1.0 [`Schema`](schemars1::Schema) into a `Schemars` 0.8 [`SchemaObject`].
+///
+/// Returns an error if the schema is not valid according to the 0.8 representation.
+pub fn from_schemars1(schema: schemars1::Schema) -> Result<SchemaObject, serde_json::Error> {
+    let schema: Schema = serde_json::from_value(schema.to_value())?;
+    Ok(schema.into_object())
+}
+
+/// Convert a `Schemars` 0.8 [`SchemaObject`] into a `Schemars` 1.0 [`Schema`](schemars1::Schema).
+pub fn to_schemars1(schema: &SchemaObject) -> Result<schemars1::Schema, serde_json::Error> {
+    serde_json::from_value(serde_json::to_value(schema)?)
+}
+
+/// Convert a list of definitions, as returned by
+/// [`SchemaGenerator::take_definitions`](schemars1::SchemaGenerator::take_definitions)
+/// in `Schemars` 1.0, into `Schemars` 0.8 `SchemaObject`s.
+pub fn definitions_from_schemars1(
+    definitions: serde_json::Map<String, serde_json::Value>,
+) -> Result<Map<String, SchemaObject>, serde_json::Error> {
+    definitions
+        .into_iter()
+        .map(|(name, value)| {
+            let schema: Schema = serde_json::from_value(value)?;
+            Ok((name, schema.into_object()))
+        })
+        .collect()
+}
@@ -0,0 +1,213 @@
+use crate::merge::operations_mut;
+use crate::openapi3::{
+    Callback, Components, Header, Link, MediaType, OpenApi, Operation, Parameter, ParameterValue,
+    PathItem, RefOr, RequestBody, Response, Server,
+};
+use crate::Map;
+use schemars::schema::SchemaObject;
+use schemars::visit::{visit_schema_object, Visitor};
+
+impl OpenApi {
+    /// Remove everything that is only meant for humans, to make the document smaller for
+    /// clients that only need the machine-readable parts (like code generators). This removes:
+    /// - All descriptions (the required `description` of responses becomes empty).
+    /// - All examples (`example`, `examples` and the `examples` of `components`).
+    /// - The `externalDocs` and the `x-codeSamples` of operations.
+    ///
+    /// Titles, summaries and everything that describes the API itself are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    ///
+    /// let mut spec = OpenApi::new();
+    /// spec.info.description = Some("A long description.".to_owned());
+    /// spec.strip_docs();
+    /// assert_eq!(spec.info.description, None);
+    /// ```
+    pub fn strip_docs(&mut self) {
+        self.info.description = None;
+        strip_servers(&mut self.servers);
+        for path_item in self.paths.values_mut() {
+            strip_path_item(path_item);
+        }
+        if let Some(components) = &mut self.components {
+            strip_components(components);
+        }
+        for tag in &mut self.tags {
+            tag.description = None;
+            tag.external_docs = None;
+        }
+        self.external_docs = None;
+    }
+}
+
+/// Removes the descriptions and examples of schemas.
+struct StripDocs;
+
+impl Visitor for StripDocs {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema);
+        if let Some(metadata) = &mut schema.metadata {
+            metadata.description = None;
+            metadata.examples = Vec::new();
+        }
+        schema.extensions.remove("example");
+        schema.extensions.remove("externalDocs");
+    }
+}
+
+fn strip_ref_or<T>(ref_or: &mut RefOr<T>, f: fn(&mut T)) {
+    if let RefOr::Object(object) = ref_or {
+        f(object);
+    }
+}
+
+fn strip_servers(servers: &mut [Server]) {
+    for server in servers {
+        server.description = None;
+        for variable in server.variables.values_mut() {
+            variable.description = None;
+        }
+    }
+}
+
+fn strip_components(components: &mut Components) {
+    for schema in components.schemas.values_mut() {
+        StripDocs.visit_schema_object(schema);
+    }
+    for response in components.responses.values_mut() {
+        strip_ref_or(response, strip_response);
+    }
+    for parameter in components.parameters.values_mut() {
+        strip_ref_or(parameter, strip_parameter);
+    }
+    components.examples = Map::new();
+    for request_body in components.request_bodies.values_mut() {
+        strip_ref_or(request_body, strip_request_body);
+    }
+    for header in components.headers.values_mut() {
+        strip_ref_or(header, strip_header);
+    }
+    for security_scheme in components.security_schemes.values_mut() {
+        strip_ref_or(security_scheme, |security_scheme| {
+            security_scheme.description = None;
+        });
+    }
+    for link in components.links.values_mut() {
+        strip_ref_or(link, strip_link);
+    }
+    for callback in components.callbacks.values_mut() {
+        strip_ref_or(callback, strip_callback);
+    }
+}
+
+fn strip_path_item(path_item: &mut PathItem) {
+    path_item.description = None;
+    if let Some(servers) = &mut path_item.servers {
+        strip_servers(servers);
+    }
+    for parameter in &mut path_item.parameters {
+        strip_ref_or(parameter, strip_parameter);
+    }
+    for (_, operation) in operations_mut(path_item) {
+        if let Some(operation) = operation {
+            strip_operation(operation);
+        }
+    }
+}
+
+fn strip_operation(operation: &mut Operation) {
+    operation.description = None;
+    operation.external_docs = None;
+    operation.extensions.remove("x-codeSamples");
+    for parameter in &mut operation.parameters {
+        strip_ref_or(parameter, strip_parameter);
+    }
+    if let Some(request_body) = &mut operation.request_body {
+        strip_ref_or(request_body, strip_request_body);
+    }
+    if let Some(response) = &mut operation.responses.default {
+        strip_ref_or(response, strip_response);
+    }
+    for response in operation.responses.responses.values_mut() {
+        strip_ref_or(response, strip_response);
+    }
+    for callback in operation.callbacks.values_mut() {
+        strip_ref_or(callback, strip_callback);
+    }
+    if let Some(servers) = &mut operation.servers {
+        strip_servers(servers);
+    }
+}
+
+fn strip_parameter(parameter: &mut Parameter) {
+    parameter.description = None;
+    strip_parameter_value(&mut parameter.value);
+}
+
+fn strip_header(header: &mut Header) {
+    header.description = None;
+    strip_parameter_value(&mut header.value);
+}
+
+fn strip_parameter_value(value: &mut ParameterValue) {
+    match value {
+        ParameterValue::Schema {
+            schema,
+            example,
+            examples,
+            ..
+        } => {
+            StripDocs.visit_schema_object(schema);
+            *example = None;
+            *examples = None;
+        }
+        ParameterValue::Content { content } => strip_content(content),
+    }
+}
+
+fn strip_request_body(request_body: &mut RequestBody) {
+    request_body.description = None;
+    strip_content(&mut request_body.content);
+}
+
+fn strip_response(response: &mut Response) {
+    response.description = String::new();
+    for header in response.headers.values_mut() {
+        strip_ref_or(header, strip_header);
+    }
+    strip_content(&mut response.content);
+    for link in response.links.values_mut() {
+        strip_ref_or(link, strip_link);
+    }
+}
+
+fn strip_link(link: &mut Link) {
+    link.description = None;
+    if let Some(server) = &mut link.server {
+        strip_servers(std::slice::from_mut(server));
+    }
+}
+
+fn strip_callback(callback: &mut Callback) {
+    for path_item in callback.callbacks.values_mut() {
+        strip_path_item(path_item);
+    }
+}
+
+fn strip_content(content: &mut Map<String, MediaType>) {
+    for media_type in content.values_mut() {
+        if let Some(schema) = &mut media_type.schema {
+            StripDocs.visit_schema_object(schema);
+        }
+        media_type.example = None;
+        media_type.examples = None;
+        for encoding in media_type.encoding.values_mut() {
+            for header in encoding.headers.values_mut() {
+                strip_ref_or(header, strip_header);
+            }
+        }
+    }
+}
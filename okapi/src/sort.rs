@@ -0,0 +1,75 @@
+use crate::deref::resolve;
+use crate::merge::operations_mut;
+use crate::openapi3::{Components, OpenApi, Parameter, RefOr};
+use crate::Map;
+
+impl OpenApi {
+    /// Sort the document, so it is serialized the same way regardless of the order in which
+    /// routes, schemas and tags were added. This sorts:
+    /// - The `paths` and the maps in `components` (like `schemas`), by key.
+    /// - The `tags`, by name.
+    /// - The `parameters` of path items and operations, by location (`in`) and name.
+    ///
+    /// Without the `preserve_order` feature, maps are always sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{OpenApi, Tag};
+    ///
+    /// let mut spec = OpenApi::new();
+    /// for name in ["users", "items"] {
+    ///     spec.tags.push(Tag {
+    ///         name: name.to_owned(),
+    ///         ..Tag::default()
+    ///     });
+    /// }
+    /// spec.sort();
+    /// assert_eq!(spec.tags[0].name, "items");
+    /// ```
+    pub fn sort(&mut self) {
+        sort_map(&mut self.paths);
+        self.tags.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(components) = &mut self.components {
+            sort_map(&mut components.schemas);
+            sort_map(&mut components.responses);
+            sort_map(&mut components.parameters);
+            sort_map(&mut components.examples);
+            sort_map(&mut components.request_bodies);
+            sort_map(&mut components.headers);
+            sort_map(&mut components.security_schemes);
+            sort_map(&mut components.links);
+            sort_map(&mut components.callbacks);
+        }
+        let components = self.components.as_ref();
+        for path_item in self.paths.values_mut() {
+            sort_parameters(&mut path_item.parameters, components);
+            for (_, operation) in operations_mut(path_item) {
+                if let Some(operation) = operation {
+                    sort_parameters(&mut operation.parameters, components);
+                }
+            }
+        }
+    }
+}
+
+fn sort_map<V>(map: &mut Map<String, V>) {
+    let mut entries: Vec<(String, V)> = std::mem::take(map).into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    *map = entries.into_iter().collect();
+}
+
+/// Sort the parameters by location and name. References that can not be resolved are sorted
+/// last, by their `$ref`.
+fn sort_parameters(parameters: &mut [RefOr<Parameter>], components: Option<&Components>) {
+    let key = |parameter: &RefOr<Parameter>| {
+        let resolved = components.and_then(|components| resolve(parameter, components).ok());
+        match (resolved, parameter) {
+            (Some(object), _) | (None, RefOr::Object(object)) => {
+                (false, object.location.clone(), object.name.clone())
+            }
+            (None, RefOr::Ref(reference)) => (true, String::new(), reference.reference.clone()),
+        }
+    };
+    parameters.sort_by_cached_key(key);
+}
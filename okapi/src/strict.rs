@@ -0,0 +1,130 @@
+use crate::convert::visit_spec_schemas;
+use crate::openapi3::OpenApi;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use schemars::visit::{visit_schema_object, Visitor};
+
+impl OpenApi {
+    /// Set `additionalProperties: false` on the object schemas of structs (in the whole
+    /// document), for contracts that require closed schemas.
+    ///
+    /// Only schemas with `type: object` and `properties` are changed. These are left as they are:
+    /// - Schemas that already have `additionalProperties`, like the schemas of structs with
+    ///   `#[serde(deny_unknown_fields)]` and of maps.
+    /// - Schemas with `allOf`, `oneOf` or `anyOf`, like structs with a `#[serde(flatten)]`ed
+    ///   enum, because `additionalProperties` does not see the properties of the subschemas. The
+    ///   variants of the flattened enum are left open too, to allow the fields of the struct.
+    ///
+    /// Because Schemars copies the fields of `#[serde(flatten)]`ed structs into the struct they
+    /// are flattened into, these are closed together. Schemars drops the `additionalProperties`
+    /// of a `#[serde(flatten)]`ed map though, so the struct it is flattened into is closed too,
+    /// and has to be opened again afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Components, OpenApi};
+    /// use schemars::JsonSchema;
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct User {
+    ///     name: String,
+    ///     #[serde(flatten)]
+    ///     address: Address,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Address {
+    ///     city: String,
+    ///     lines: HashMap<String, String>,
+    /// }
+    ///
+    /// let mut gen = schemars::gen::SchemaSettings::openapi3().into_generator();
+    /// gen.subschema_for::<User>();
+    /// let mut spec = OpenApi::new();
+    /// spec.components = Some(Components {
+    ///     schemas: gen
+    ///         .take_definitions()
+    ///         .into_iter()
+    ///         .map(|(name, schema)| (name, schema.into_object()))
+    ///         .collect(),
+    ///     ..Components::default()
+    /// });
+    /// spec.close_object_schemas();
+    ///
+    /// let schemas = serde_json::to_value(&spec.components.unwrap().schemas).unwrap();
+    /// assert_eq!(schemas["User"]["additionalProperties"], false);
+    /// assert_eq!(
+    ///     schemas["User"]["properties"]["lines"]["additionalProperties"],
+    ///     serde_json::json!({ "type": "string" })
+    /// );
+    /// ```
+    pub fn close_object_schemas(&mut self) {
+        visit_spec_schemas(self, &mut CloseObjectSchemas::default());
+    }
+}
+
+/// Sets `additionalProperties: false` on object schemas.
+#[derive(Default)]
+struct CloseObjectSchemas {
+    /// If the next schema is a variant of a flattened enum, which must allow the other
+    /// properties of the struct.
+    flattened_variant: bool,
+}
+
+impl Visitor for CloseObjectSchemas {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        let flattened_variant = std::mem::take(&mut self.flattened_variant);
+        let has_properties = schema
+            .object
+            .as_ref()
+            .is_some_and(|object| !object.properties.is_empty());
+        match schema.subschemas.take() {
+            Some(mut subschemas) if has_properties => {
+                visit_schema_object(self, schema);
+                for variants in [
+                    &mut subschemas.all_of,
+                    &mut subschemas.one_of,
+                    &mut subschemas.any_of,
+                ] {
+                    for variant in variants.iter_mut().flatten() {
+                        self.flattened_variant = true;
+                        self.visit_schema(variant);
+                        self.flattened_variant = false;
+                    }
+                }
+                for subschema in [
+                    &mut subschemas.not,
+                    &mut subschemas.if_schema,
+                    &mut subschemas.then_schema,
+                    &mut subschemas.else_schema,
+                ]
+                .iter_mut()
+                .filter_map(|subschema| subschema.as_deref_mut())
+                {
+                    self.visit_schema(subschema);
+                }
+                schema.subschemas = Some(subschemas);
+                return;
+            }
+            subschemas => {
+                schema.subschemas = subschemas;
+                visit_schema_object(self, schema);
+            }
+        }
+        if flattened_variant
+            || schema.subschemas.is_some()
+            || schema.instance_type != Some(SingleOrVec::Single(Box::new(InstanceType::Object)))
+        {
+            return;
+        }
+        if let Some(object) = &mut schema.object {
+            if has_properties
+                && object.additional_properties.is_none()
+                && object.pattern_properties.is_empty()
+            {
+                object.additional_properties = Some(Box::new(Schema::Bool(false)));
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use crate::openapi3::{OpenApi, Operation, Parameter, PathItem, RefOr, Responses};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// A problem found in an OpenAPI document by [`OpenApi::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// JSON Pointer to the location of the problem in the document, like
+    /// `/paths/~1users/get/responses`.
+    pub location: String,
+    /// The kind of problem that was found.
+    pub kind: ValidationErrorKind,
+}
+
+/// The different kinds of problems [`OpenApi::validate`] can find.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// An operation does not have an `operationId`.
+    MissingOperationId,
+    /// The same `operationId` is used by more than one operation.
+    DuplicateOperationId(String),
+    /// A local `$ref` points to something that does not exist in the document.
+    DanglingRef(String),
+    /// A parameter with the same `name` and `in` location is defined more than once.
+    DuplicateParameter {
+        /// The name of the parameter.
+        name: String,
+        /// The location of the parameter (`query`, `path`, `header` or `cookie`).
+        location: String,
+    },
+    /// A `{param}` in the path template has no matching `in: path` parameter.
+    MissingPathParameter(String),
+    /// An `in: path` parameter does not appear as `{param}` in the path template.
+    UnusedPathParameter(String),
+    /// A response key is not an HTTP status code (`200`), a range of them (`2XX`) or `default`.
+    InvalidStatusCode(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::MissingOperationId => write!(f, "Missing `operationId`")?,
+            ValidationErrorKind::DuplicateOperationId(id) => {
+                write!(f, "Duplicate `operationId` `{}`", id)?
+            }
+            ValidationErrorKind::DanglingRef(reference) => {
+                write!(f, "Reference `{}` does not exist", reference)?
+            }
+            ValidationErrorKind::DuplicateParameter { name, location } => {
+                write!(f, "Duplicate parameter `{}` in `{}`", name, location)?
+            }
+            ValidationErrorKind::MissingPathParameter(name) => {
+                write!(f, "Path parameter `{}` is not documented", name)?
+            }
+            ValidationErrorKind::UnusedPathParameter(name) => {
+                write!(f, "Path parameter `{}` is not part of the path", name)?
+            }
+            ValidationErrorKind::InvalidStatusCode(code) => {
+                write!(f, "Invalid status code `{}`", code)?
+            }
+        }
+        write!(f, " at `{}`.", self.location)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl OpenApi {
+    /// Check the document for common problems: operations without (or with duplicate)
+    /// `operationId`s, local `$ref`s that do not resolve, duplicate parameters, path parameters
+    /// that do not match the path template and invalid response status codes.
+    ///
+    /// Returns all problems that were found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{OpenApi, Operation, PathItem};
+    /// use okapi::validate::ValidationErrorKind;
+    ///
+    /// let mut spec = OpenApi::new();
+    /// spec.paths.insert(
+    ///     "/users".to_owned(),
+    ///     PathItem {
+    ///         get: Some(Operation::default()),
+    ///         ..PathItem::default()
+    ///     },
+    /// );
+    ///
+    /// let errors = spec.validate().unwrap_err();
+    /// assert_eq!(errors[0].location, "/paths/~1users/get");
+    /// assert_eq!(errors[0].kind, ValidationErrorKind::MissingOperationId);
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let mut operation_ids = HashSet::new();
+        for (path, path_item) in &self.paths {
+            let location = format!("/paths/{}", escape_pointer(path));
+            check_parameters(&path_item.parameters, &location, &mut errors);
+            for (method, operation) in operations(path_item) {
+                let location = format!("{}/{}", location, method);
+                match &operation.operation_id {
+                    Some(id) if !operation_ids.insert(id.as_str()) => {
+                        errors.push(ValidationError {
+                            location: format!("{}/operationId", location),
+                            kind: ValidationErrorKind::DuplicateOperationId(id.clone()),
+                        })
+                    }
+                    Some(_) => {}
+                    None => errors.push(ValidationError {
+                        location: location.clone(),
+                        kind: ValidationErrorKind::MissingOperationId,
+                    }),
+                }
+                check_parameters(&operation.parameters, &location, &mut errors);
+                self.check_path_parameters(path, path_item, operation, &location, &mut errors);
+                check_status_codes(&operation.responses, &location, &mut errors);
+            }
+        }
+        match serde_json::to_value(self) {
+            Ok(document) => check_refs(&document, &document, "", &mut errors),
+            Err(err) => log::error!("Could not serialize OpenAPI document: {}", err),
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl OpenApi {
+    /// Check that every `{param}` in the `path` has an `in: path` parameter, and the other way
+    /// around. The parameters of the operation and the path item are used.
+    fn check_path_parameters(
+        &self,
+        path: &str,
+        path_item: &PathItem,
+        operation: &Operation,
+        location: &str,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let template: HashSet<&str> = path_template_parameters(path).collect();
+        let documented: HashSet<&str> = path_item
+            .parameters
+            .iter()
+            .chain(&operation.parameters)
+            .filter_map(|parameter| self.resolve_parameter(parameter))
+            .filter(|parameter| parameter.location == "path")
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+        for name in path_template_parameters(path) {
+            if !documented.contains(name) {
+                errors.push(ValidationError {
+                    location: location.to_owned(),
+                    kind: ValidationErrorKind::MissingPathParameter(name.to_owned()),
+                });
+            }
+        }
+        for name in documented.difference(&template) {
+            errors.push(ValidationError {
+                location: format!("{}/parameters", location),
+                kind: ValidationErrorKind::UnusedPathParameter((*name).to_owned()),
+            });
+        }
+    }
+
+    /// Get the parameter, looking it up in `components.parameters` if it is a `$ref`.
+    pub(crate) fn resolve_parameter<'a>(
+        &'a self,
+        parameter: &'a RefOr<Parameter>,
+    ) -> Option<&'a Parameter> {
+        match parameter {
+            RefOr::Object(parameter) => Some(parameter),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .reference
+                    .strip_prefix("#/components/parameters/")?;
+                match self.components.as_ref()?.parameters.get(name)? {
+                    RefOr::Object(parameter) => Some(parameter),
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    }
+}
+
+/// The names of the `{param}` parts of a path template.
+fn path_template_parameters(path: &str) -> impl Iterator<Item = &str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+}
+
+pub(crate) fn operations(path_item: &PathItem) -> impl Iterator<Item = (&'static str, &Operation)> {
+    vec![
+        ("get", &path_item.get),
+        ("put", &path_item.put),
+        ("post", &path_item.post),
+        ("delete", &path_item.delete),
+        ("options", &path_item.options),
+        ("head", &path_item.head),
+        ("patch", &path_item.patch),
+        ("trace", &path_item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.as_ref().map(|op| (method, op)))
+}
+
+fn check_parameters(
+    parameters: &[RefOr<Parameter>],
+    location: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let mut seen = HashSet::new();
+    for (index, parameter) in parameters.iter().enumerate() {
+        if let RefOr::Object(parameter) = parameter {
+            if !seen.insert((parameter.name.as_str(), parameter.location.as_str())) {
+                errors.push(ValidationError {
+                    location: format!("{}/parameters/{}", location, index),
+                    kind: ValidationErrorKind::DuplicateParameter {
+                        name: parameter.name.clone(),
+                        location: parameter.location.clone(),
+                    },
+                });
+            }
+        }
+    }
+}
+
+fn check_status_codes(responses: &Responses, location: &str, errors: &mut Vec<ValidationError>) {
+    for code in responses.responses.keys() {
+        if !is_valid_status_code(code) {
+            errors.push(ValidationError {
+                location: format!("{}/responses/{}", location, escape_pointer(code)),
+                kind: ValidationErrorKind::InvalidStatusCode(code.clone()),
+            });
+        }
+    }
+}
+
+/// Status codes are `100` to `599`, a range like `2XX` or `default`.
+fn is_valid_status_code(code: &str) -> bool {
+    if code == "default" {
+        return true;
+    }
+    let bytes = code.as_bytes();
+    if bytes.len() != 3 || !(b'1'..=b'5').contains(&bytes[0]) {
+        return false;
+    }
+    bytes[1..].iter().all(u8::is_ascii_digit) || &bytes[1..] == b"XX"
+}
+
+/// Check that all local references (`#/...`) can be resolved in the `document`.
+fn check_refs(document: &Value, value: &Value, location: &str, errors: &mut Vec<ValidationError>) {
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object {
+                let item_location = format!("{}/{}", location, escape_pointer(key));
+                match (key.as_str(), item) {
+                    ("$ref", Value::String(reference)) => {
+                        if let Some(pointer) = reference.strip_prefix('#') {
+                            if document.pointer(pointer).is_none() {
+                                errors.push(ValidationError {
+                                    location: item_location,
+                                    kind: ValidationErrorKind::DanglingRef(reference.clone()),
+                                });
+                            }
+                        }
+                    }
+                    _ => check_refs(document, item, &item_location, errors),
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (index, item) in array.iter().enumerate() {
+                check_refs(document, item, &format!("{}/{}", location, index), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Escape a key for use in a JSON Pointer.
+pub(crate) fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
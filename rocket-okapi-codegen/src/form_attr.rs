@@ -0,0 +1,97 @@
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, Field, Fields, Lit, Token};
+
+/// Add a `#[schemars(rename = "...")]` attribute to every field, with the name Rocket parses:
+/// the name of `#[field(name = ...)]`, or else the name of the field (Rocket ignores serde
+/// renames).
+pub fn parse(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let fields =
+        match &mut input.data {
+            Data::Struct(data) => match &mut data.fields {
+                Fields::Named(fields) => &mut fields.named,
+                _ => return quote! {
+                    compile_error!("`form_schema` can only be used on structs with named fields.");
+                }
+                .into(),
+            },
+            _ => {
+                return quote! {
+                    compile_error!("`form_schema` can only be used on structs.");
+                }
+                .into()
+            }
+        };
+    for field in fields.iter_mut() {
+        if has_schemars_rename(&field.attrs) {
+            continue;
+        }
+        let name = match field_name(field) {
+            Ok(name) => name,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        field
+            .attrs
+            .push(syn::parse_quote!(#[schemars(rename = #name)]));
+    }
+    input.into_token_stream().into()
+}
+
+/// The (first) name of `#[field(name = "...")]` or `#[field(name = uncased("..."))]`, or the
+/// name of the field.
+fn field_name(field: &Field) -> syn::Result<String> {
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("field"))
+    {
+        let args = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+        for arg in args {
+            let (left, right) = match arg {
+                Expr::Assign(assign) => (assign.left, assign.right),
+                _ => continue,
+            };
+            if !matches!(&*left, Expr::Path(path) if path.path.is_ident("name")) {
+                continue;
+            }
+            let value = match *right {
+                // `uncased("name")`
+                Expr::Call(call) if call.args.len() == 1 => call.args.into_iter().next().unwrap(),
+                value => value,
+            };
+            match value {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(name),
+                    ..
+                }) => return Ok(name.value()),
+                value => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "Expected a string literal as field name.",
+                    ))
+                }
+            }
+        }
+    }
+    let ident = field.ident.as_ref().unwrap().to_string();
+    Ok(ident.trim_start_matches("r#").to_owned())
+}
+
+/// If the field already has a `#[schemars(rename = "...")]` attribute.
+fn has_schemars_rename(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("schemars"))
+        .any(|attr| {
+            attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .map(|args| {
+                    args.iter().any(|arg| {
+                        matches!(arg, Expr::Assign(assign)
+                            if matches!(&*assign.left, Expr::Path(path) if path.path.is_ident("rename")))
+                    })
+                })
+                .unwrap_or(false)
+        })
+}
@@ -0,0 +1,108 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta, Result};
+
+/// Implements `JsonSchema` for a type that implements `FromFormField`, as a string. For an enum,
+/// the string is one of the values of its variants (like `#[derive(FromFormField)]` uses them).
+pub fn derive(input: TokenStream) -> Result<TokenStream2> {
+    let ast: DeriveInput = syn::parse(input)?;
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let format = match get_format(&ast.attrs)? {
+        Some(format) => quote! { Some(#format.to_owned()) },
+        None => quote! { None },
+    };
+    let enum_values = match &ast.data {
+        Data::Enum(data) => {
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "Only enums with unit variants can derive `OpenApiFromFormField`.",
+                    ));
+                }
+                let variant_values = get_field_values(&variant.attrs)?;
+                if variant_values.is_empty() {
+                    values.push(variant.ident.to_string());
+                } else {
+                    values.extend(variant_values);
+                }
+            }
+            quote! { Some(vec![#(#values.into()),*]) }
+        }
+        _ => quote! { None },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::JsonSchema for #name #ty_generics #where_clause {
+            fn schema_name() -> String {
+                stringify!(#name).to_owned()
+            }
+
+            fn is_referenceable() -> bool {
+                false
+            }
+
+            fn json_schema(
+                _gen: &mut ::rocket_okapi::okapi::schemars::gen::SchemaGenerator,
+            ) -> ::rocket_okapi::okapi::schemars::schema::Schema {
+                ::rocket_okapi::okapi::schemars::schema::SchemaObject {
+                    instance_type: Some(
+                        ::rocket_okapi::okapi::schemars::schema::InstanceType::String.into(),
+                    ),
+                    format: #format,
+                    enum_values: #enum_values,
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    })
+}
+
+/// The `format` of `#[form_field(format = "email")]`.
+fn get_format(attrs: &[Attribute]) -> Result<Option<String>> {
+    let mut format = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("form_field")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "Expected `form_field(format = \"...\")`.",
+                ))
+            }
+        };
+        for item in list.nested {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("format") => {
+                    match value.lit {
+                        Lit::Str(s) => format = Some(s.value()),
+                        lit => return Err(Error::new_spanned(lit, "Expected a string literal.")),
+                    }
+                }
+                item => return Err(Error::new_spanned(item, "Unknown `form_field` option.")),
+            }
+        }
+    }
+    Ok(format)
+}
+
+/// The values of the `#[field(value = "...")]` attributes of Rocket on an enum variant.
+fn get_field_values(attrs: &[Attribute]) -> Result<Vec<String>> {
+    let mut values = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("field")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for item in list.nested {
+                if let NestedMeta::Meta(Meta::NameValue(value)) = item {
+                    if let (true, Lit::Str(s)) = (value.path.is_ident("value"), value.lit) {
+                        values.push(s.value());
+                    }
+                }
+            }
+        }
+    }
+    Ok(values)
+}
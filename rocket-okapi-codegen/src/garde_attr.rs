@@ -0,0 +1,135 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, Lit, LitStr, Meta, MetaNameValue,
+    NestedMeta,
+};
+
+/// Mirror all `#[garde(...)]` field attributes as `#[schemars(...)]` attributes so the
+/// `JsonSchema` derive adds the matching JSON Schema keywords.
+pub fn parse(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    match &mut input.data {
+        Data::Struct(data) => add_schemars_attrs(&mut data.fields),
+        Data::Enum(data) => {
+            for variant in &mut data.variants {
+                add_schemars_attrs(&mut variant.fields);
+            }
+        }
+        Data::Union(_) => {
+            return quote! {
+                compile_error!("`garde_schema` can not be used on unions.");
+            }
+            .into();
+        }
+    }
+    input.into_token_stream().into()
+}
+
+fn add_schemars_attrs(fields: &mut Fields) {
+    for field in fields.iter_mut() {
+        let rules: Vec<NestedMeta> = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("garde"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) => Some(list.nested.into_iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(convert_rule)
+            .collect();
+        if !rules.is_empty() {
+            field.attrs.push(schemars_attr(&rules));
+        }
+    }
+}
+
+fn schemars_attr(rules: &[NestedMeta]) -> Attribute {
+    syn::parse_quote!(#[schemars(#(#rules),*)])
+}
+
+/// Convert a single `garde` rule into the equivalent `schemars` validation attribute.
+/// Rules that have no JSON Schema counterpart (`ascii`, `custom`, `dive`, ...) are ignored.
+fn convert_rule(rule: NestedMeta) -> Option<NestedMeta> {
+    let meta = match rule {
+        NestedMeta::Meta(meta) => meta,
+        NestedMeta::Lit(_) => return None,
+    };
+    let name = meta.path().get_ident()?.to_string();
+    let converted: TokenStream2 = match (name.as_str(), &meta) {
+        ("email", Meta::Path(_)) => quote!(email),
+        ("url", Meta::Path(_)) => quote!(url),
+        ("phone_number", Meta::Path(_)) => quote!(phone),
+        ("required", Meta::Path(_)) => quote!(required),
+        ("length", Meta::List(list)) | ("range", Meta::List(list)) => {
+            let ident = syn::Ident::new(&name, Span::call_site());
+            // `length` can contain a mode (`chars`, `bytes`, ...), only keep the bounds.
+            let bounds: Vec<TokenStream2> = list
+                .nested
+                .iter()
+                .filter_map(|nested| match nested {
+                    NestedMeta::Meta(Meta::NameValue(nv))
+                        if nv.path.is_ident("min")
+                            || nv.path.is_ident("max")
+                            || nv.path.is_ident("equal") =>
+                    {
+                        bound_to_tokens(nv)
+                    }
+                    _ => None,
+                })
+                .collect();
+            if bounds.is_empty() {
+                return None;
+            }
+            quote!(#ident(#(#bounds),*))
+        }
+        ("pattern", Meta::List(list)) => match list.nested.first()? {
+            NestedMeta::Lit(Lit::Str(pattern)) => quote!(regex(pattern = #pattern)),
+            NestedMeta::Meta(Meta::Path(path)) => {
+                let path = LitStr::new(&path_to_string(path), Span::call_site());
+                quote!(regex(path = #path))
+            }
+            _ => return None,
+        },
+        ("contains", Meta::List(list)) => match list.nested.first()? {
+            NestedMeta::Lit(Lit::Str(value)) => quote!(contains = #value),
+            _ => return None,
+        },
+        ("inner", Meta::List(list)) => {
+            let inner: Vec<NestedMeta> = list
+                .nested
+                .iter()
+                .cloned()
+                .filter_map(convert_rule)
+                .collect();
+            if inner.is_empty() {
+                return None;
+            }
+            quote!(inner(#(#inner),*))
+        }
+        _ => return None,
+    };
+    syn::parse2(converted).ok()
+}
+
+/// `schemars` only accepts number literals (or a string containing a path) as bounds.
+fn bound_to_tokens(nv: &MetaNameValue) -> Option<TokenStream2> {
+    let name = &nv.path;
+    match &nv.lit {
+        Lit::Int(_) | Lit::Float(_) | Lit::Str(_) => {
+            let lit = &nv.lit;
+            Some(quote!(#name = #lit))
+        }
+        _ => None,
+    }
+}
+
+fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
@@ -7,10 +7,18 @@
 //! - `openapi_routes![...]`: Returns a closure for generating routes.
 //! - `openapi_spec![...]`: Returns a closure for generating OpenApi objects.
 //! - `#[derive(OpenApiFromRequest)]`: Implement `OpenApiFromRequest` trait for a given struct.
+//! - `#[derive(OpenApiFromParam)]`: Document a newtype path parameter like its inner type.
+//! - `#[garde_schema]`: Document `garde` validation rules in the `JsonSchema` of a type.
+//!   (Requires the `garde` feature.)
 //!
 
+mod form_attr;
+mod form_field_derive;
+#[cfg(feature = "garde")]
+mod garde_attr;
 mod openapi_attr;
 mod openapi_spec;
+mod param_derive;
 mod parse_routes;
 
 use proc_macro::TokenStream;
@@ -31,6 +39,101 @@ use syn::Ident;
 ///     format!("Hello world number {}", number)
 /// }
 /// ```
+///
+/// If the data guard accepts multiple formats, list all accepted media types with `consumes`.
+/// The request body is then documented for each of them:
+/// ```rust,ignore
+/// #[openapi(consumes("application/json", "application/msgpack"))]
+/// #[post("/user", data = "<user>")]
+/// fn create_user(user: JsonOrMsgPack<User>) -> Json<User> {
+///     /* ... */
+/// }
+/// ```
+///
+/// The summary and description of the operation are taken from the doc comment of the route.
+/// Use `summary` and `description` to document the API differently than the Rust function:
+/// ```rust,ignore
+/// /// Loads the user with `Db::user`, see `UserCache` for the caching.
+/// #[openapi(summary = "Get a user", description = "Returns the user with the given id.")]
+/// #[get("/user/<id>")]
+/// fn get_user(id: u64) -> Option<Json<User>> {
+///     /* ... */
+/// }
+/// ```
+///
+/// The descriptions of responses can be changed (or responses added) per status code:
+/// ```rust,ignore
+/// #[openapi(
+///     response(status = 404, description = "There is no user with this id."),
+///     response(status = "5XX", description = "The database is unavailable."),
+/// )]
+/// #[get("/user/<id>")]
+/// fn get_user(id: u64) -> Option<Json<User>> {
+///     /* ... */
+/// }
+/// ```
+///
+/// Use `skip` to leave a route out of the documentation, or `skip_if` to only leave it out if a
+/// `cfg` predicate holds (see also `OpenApiSettings::skip_route`):
+/// ```rust,ignore
+/// #[openapi(skip_if = "cfg(not(debug_assertions))")]
+/// #[get("/debug/cache")]
+/// fn cache_stats() -> Json<CacheStats> {
+///     /* ... */
+/// }
+/// ```
+///
+/// Ignored segments (`<_>` and `<_..>`) are documented as string parameters named `_1`, `_2`,
+/// ... (in order). Use `ignored_segments` to name them:
+/// ```rust,ignore
+/// #[openapi(ignored_segments("version"))]
+/// #[get("/<_>/users")]
+/// fn get_users() -> Json<Vec<User>> {
+///     /* ... */
+/// }
+/// ```
+///
+/// WebSocket routes (`GET` routes that upgrade the connection) are documented with `websocket`:
+/// the handshake headers and a `101 Switching Protocols` response, so the return type does not
+/// have to implement `OpenApiResponder`. The schema of the messages can be added as the
+/// `x-websocket-messages` extension with `websocket(message = "...")`:
+/// ```rust,ignore
+/// #[openapi(websocket(message = "ChatMessage"))]
+/// #[get("/chat")]
+/// fn chat(ws: WebSocket) -> Channel<'static> {
+///     /* ... */
+/// }
+/// ```
+///
+/// Parameters that are registered with `OpenApiSettings::register_parameter` are referenced with
+/// `param_ref` (which can be repeated):
+/// ```rust,ignore
+/// #[openapi(param_ref = "TenantHeader")]
+/// #[get("/users")]
+/// fn get_users(tenant: Tenant) -> Json<Vec<User>> {
+///     /* ... */
+/// }
+/// ```
+///
+/// Responses that are registered with `OpenApiSettings::register_response` are referenced per
+/// status code with `response_ref`, which replaces the response of that status:
+/// ```rust,ignore
+/// #[openapi(response_ref(status = 401, name = "Unauthorized"))]
+/// #[get("/users")]
+/// fn get_users(user: User) -> Json<Vec<User>> {
+///     /* ... */
+/// }
+/// ```
+///
+/// A request body that is registered with `OpenApiSettings::register_request_body` documents
+/// the data guard with `request_body_ref`:
+/// ```rust,ignore
+/// #[openapi(request_body_ref = "NewUser")]
+/// #[post("/users", data = "<user>")]
+/// fn create_user(user: Json<NewUser>) -> Json<User> {
+///     /* ... */
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn openapi(args: TokenStream, mut input: TokenStream) -> TokenStream {
     // We don't need to modify/replace the input TokenStream,
@@ -85,6 +188,27 @@ pub fn openapi_spec(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Create a `rocket_okapi::OpenApiRoutes` with the routes and the functions that add their
+/// operations to the OpenAPI specification. Unlike the closures of the other macros, this can be
+/// returned from a library crate, so the binary crate can combine the routes of several crates
+/// into one specification with `rocket_okapi::openapi_get_routes_from!`.
+///
+/// Example:
+/// ```rust,ignore
+/// pub fn api() -> rocket_okapi::OpenApiRoutes {
+///     rocket_okapi::openapi_route_list![get_message, post_message]
+/// }
+/// ```
+#[proc_macro]
+pub fn openapi_route_list(input: TokenStream) -> TokenStream {
+    let route_list =
+        openapi_spec::create_route_list(input).unwrap_or_else(|e| e.to_compile_error());
+    (quote! {
+        #route_list
+    })
+    .into()
+}
+
 /// Derive marco for the `OpenApiFromRequest` trait.
 ///
 /// This derive trait is a very simple implementation for anything that does not
@@ -136,6 +260,185 @@ pub fn open_api_from_request_derive(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Derive marco for the `OpenApiFromFormField` trait.
+///
+/// This documents a custom type that implements `FromFormField` (like a validated email
+/// address) as a string, by implementing `JsonSchema` for it. So it can be used as a query
+/// parameter, and as a field of query structs (which derive `JsonSchema`). The `format` of the
+/// string can be set with `#[form_field(format = "...")]`. For an enum, the string is one of the
+/// values of its variants (their names, or their `#[field(value = "...")]`).
+///
+/// Use:
+/// ```rust,ignore
+/// use rocket_okapi::OpenApiFromFormField;
+///
+/// #[derive(OpenApiFromFormField)]
+/// #[form_field(format = "email")]
+/// pub struct EmailAddress(String);
+///
+/// impl<'r> rocket::form::FromFormField<'r> for EmailAddress {
+///     /* ... */
+/// }
+///
+/// #[derive(rocket::FromFormField, OpenApiFromFormField)]
+/// pub enum Order {
+///     #[field(value = "asc")]
+///     Ascending,
+///     #[field(value = "desc")]
+///     Descending,
+/// }
+/// ```
+#[proc_macro_derive(OpenApiFromFormField, attributes(form_field))]
+pub fn open_api_from_form_field_derive(input: TokenStream) -> TokenStream {
+    form_field_derive::derive(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive marco for the `OpenApiFromParam` trait.
+///
+/// This documents a newtype that implements `FromParam` (like a wrapper of a `Uuid`, or a
+/// validated id) with the schema of its inner type. So the newtype does not need to implement
+/// `JsonSchema` itself.
+///
+/// Use:
+/// ```rust,ignore
+/// use rocket_okapi::OpenApiFromParam;
+///
+/// #[derive(OpenApiFromParam)]
+/// pub struct UserId(u64);
+///
+/// impl<'r> rocket::request::FromParam<'r> for UserId {
+///     /* ... */
+/// }
+/// ```
+#[proc_macro_derive(OpenApiFromParam)]
+pub fn open_api_from_param_derive(input: TokenStream) -> TokenStream {
+    param_derive::derive(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive marco for the `OpenApiFromSegments` trait.
+///
+/// This documents a type that implements `FromSegments` (for a `<path..>` parameter) like a
+/// `PathBuf`: as a string, which can contain `/`. Types that also implement `JsonSchema` do not
+/// need this, they use their schema.
+///
+/// Use:
+/// ```rust,ignore
+/// use rocket_okapi::OpenApiFromSegments;
+///
+/// #[derive(OpenApiFromSegments)]
+/// pub struct FilePath(Vec<String>);
+///
+/// impl<'r> rocket::request::FromSegments<'r> for FilePath {
+///     /* ... */
+/// }
+/// ```
+#[proc_macro_derive(OpenApiFromSegments)]
+pub fn open_api_from_segments_derive(input: TokenStream) -> TokenStream {
+    let ast: syn::DeriveInput = syn::parse(input).unwrap();
+    let name = &ast.ident;
+
+    let gen = quote! {
+        impl<'r> rocket_okapi::request::OpenApiFromSegments<'r> for #name {
+            fn path_multi_parameter(
+                gen: &mut rocket_okapi::gen::OpenApiGenerator,
+                name: String,
+            ) -> rocket_okapi::Result<rocket_okapi::okapi::openapi3::Parameter> {
+                <::std::path::PathBuf as rocket_okapi::request::OpenApiFromSegments>::path_multi_parameter(gen, name)
+            }
+        }
+    };
+    gen.into()
+}
+
+/// An attribute macro that documents the query parameters of a `FromForm` struct with the names
+/// Rocket parses: the names of `#[field(name = "...")]` (or `#[field(name = uncased("..."))]`),
+/// and the names of the fields instead of their serde renames, which Rocket does not use.
+///
+/// This attribute has to be placed *before* `#[derive(JsonSchema)]`. It adds a
+/// `#[schemars(rename = "...")]` attribute to every field that does not have one yet.
+///
+/// Example:
+/// ```rust,ignore
+/// use rocket_okapi::{form_schema, JsonSchema};
+///
+/// #[form_schema]
+/// #[derive(rocket::FromForm, serde::Serialize, JsonSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Search {
+///     // Documented as `q`.
+///     #[field(name = "q")]
+///     query: String,
+///     // Documented as `page_size`, not `pageSize`.
+///     page_size: Option<u32>,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn form_schema(_args: TokenStream, input: TokenStream) -> TokenStream {
+    form_attr::parse(input)
+}
+
+/// An attribute macro that documents [`garde`](https://docs.rs/garde) validation rules in the
+/// generated JSON Schema, similar to how `schemars` already handles `#[validate(...)]` attributes.
+///
+/// This attribute has to be placed *before* `#[derive(JsonSchema)]`.
+/// It copies every supported `#[garde(...)]` field rule into an equivalent `#[schemars(...)]`
+/// attribute, the `garde` attributes themselves are left untouched.
+///
+/// Supported rules: `length`, `range`, `pattern`, `contains`, `email`, `url`, `phone_number`,
+/// `required` and `inner`. Bounds have to be literals. Other rules are not represented in the
+/// schema.
+///
+/// Example:
+/// ```rust,ignore
+/// use rocket_okapi::{garde_schema, JsonSchema};
+/// use garde::Validate;
+///
+/// #[garde_schema]
+/// #[derive(Validate, JsonSchema)]
+/// struct User {
+///     #[garde(length(min = 3, max = 25))]
+///     username: String,
+///     #[garde(email)]
+///     email: String,
+///     #[garde(range(min = 18))]
+///     age: u8,
+/// }
+/// ```
+///
+/// The rules become the JSON Schema keywords of the fields:
+/// ```rust
+/// use garde::Validate;
+/// use rocket_okapi_codegen::garde_schema;
+/// use schemars::JsonSchema;
+///
+/// #[garde_schema]
+/// #[derive(Validate, JsonSchema)]
+/// struct User {
+///     #[garde(length(min = 3, max = 25))]
+///     username: String,
+///     #[garde(range(min = 18))]
+///     age: u8,
+///     #[garde(inner(length(min = 1)))]
+///     tags: Vec<String>,
+/// }
+///
+/// let schema = serde_json::to_value(schemars::schema_for!(User)).unwrap();
+/// let properties = &schema["properties"];
+/// assert_eq!(properties["username"]["minLength"], 3);
+/// assert_eq!(properties["username"]["maxLength"], 25);
+/// assert_eq!(properties["age"]["minimum"], 18.0);
+/// assert_eq!(properties["tags"]["items"]["minLength"], 1);
+/// ```
+#[cfg(feature = "garde")]
+#[proc_macro_attribute]
+pub fn garde_schema(_args: TokenStream, input: TokenStream) -> TokenStream {
+    garde_attr::parse(input)
+}
+
 fn get_add_operation_fn_name(route_fn_name: &Ident) -> Ident {
     Ident::new(
         &format!("okapi_add_operation_for_{}_", route_fn_name),
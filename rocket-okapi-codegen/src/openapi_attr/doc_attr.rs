@@ -1,9 +1,24 @@
 use syn::{Attribute, Lit::Str, Meta::NameValue, MetaNameValue};
 
+/// The code block attributes of rustdoc, code blocks with only these are Rust code.
+const RUST_CODE_BLOCK_ATTRIBUTES: &[&str] = &[
+    "rust",
+    "ignore",
+    "no_run",
+    "should_panic",
+    "compile_fail",
+    "test_harness",
+    "edition2015",
+    "edition2018",
+    "edition2021",
+];
+
+/// Get the title (from a `# Title` heading on the first line) and the description of the doc
+/// comment, as CommonMark.
 pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
     let doc = match get_doc(attrs) {
         None => return (None, None),
-        Some(doc) => doc,
+        Some(doc) => to_markdown(&doc),
     };
 
     if doc.starts_with('#') {
@@ -21,18 +36,248 @@ pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Opti
     }
 }
 
+/// Like [`get_title_and_desc_from_doc`], but if the doc comment does not start with a heading,
+/// the first paragraph (up to the first blank line) is used as the title.
+pub fn get_title_and_desc_from_first_paragraph(
+    attrs: &[Attribute],
+) -> (Option<String>, Option<String>) {
+    let doc = match get_doc(attrs) {
+        None => return (None, None),
+        Some(doc) => to_markdown(&doc),
+    };
+    let first_line = doc.lines().next().unwrap_or_default();
+    if first_line.starts_with('#') || starts_block(first_line) {
+        return get_title_and_desc_from_doc(attrs);
+    }
+    let mut split = doc.splitn(2, "\n\n");
+    let title = split.next().and_then(merge_description_lines);
+    let maybe_desc = split.next().and_then(merge_description_lines);
+    (title, maybe_desc)
+}
+
+/// Join the lines of paragraphs, so line breaks of the doc comment are not rendered. Code
+/// blocks, headings, lists, quotes and tables are kept as is.
 fn merge_description_lines(doc: &str) -> Option<String> {
-    let desc = doc
-        .trim()
-        .split("\n\n")
-        .filter_map(|line| none_if_empty(line.trim().replace('\n', " ")))
-        .collect::<Vec<_>>()
-        .join("\n\n");
-    none_if_empty(desc)
+    let mut desc = String::new();
+    let mut in_code_block = false;
+    // If the last line is text that the next line can be joined with.
+    let mut joinable = false;
+    for line in doc.trim().lines() {
+        if in_code_block {
+            desc.push('\n');
+            desc.push_str(line);
+            in_code_block = !is_fence(line);
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if !desc.is_empty() && !desc.ends_with("\n\n") {
+                desc.push_str("\n\n");
+            }
+            joinable = false;
+            continue;
+        }
+        if joinable && !starts_block(trimmed) {
+            desc.push(' ');
+            desc.push_str(trimmed);
+            continue;
+        }
+        if !desc.is_empty() && !desc.ends_with('\n') {
+            desc.push('\n');
+        }
+        if is_fence(trimmed) {
+            desc.push_str(line);
+            in_code_block = true;
+            joinable = false;
+        } else {
+            desc.push_str(trimmed);
+            joinable = !trimmed.starts_with('#') && !trimmed.starts_with('|');
+        }
+    }
+    none_if_empty(desc.trim_end().to_owned())
+}
+
+/// Convert the rustdoc specific parts of a doc comment to CommonMark: intra-doc links become
+/// text (or code), and Rust code blocks get the `rust` language without the hidden lines.
+fn to_markdown(doc: &str) -> String {
+    // The labels of intra-doc link definitions, so `[Foo]` is known to be an intra-doc link.
+    let labels: Vec<&str> = doc
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| is_intra_doc_link_definition(line))
+        .filter_map(|line| line[1..].split_once("]:").map(|(label, _)| label))
+        .collect();
+    let mut lines = Vec::new();
+    // The fence of the current code block, and if it contains Rust code.
+    let mut code_block: Option<(&str, bool)> = None;
+    for line in doc.lines() {
+        let trimmed = line.trim_start();
+        match code_block {
+            Some((fence, is_rust)) => {
+                if trimmed.starts_with(fence) && trimmed.trim_start_matches(fence).trim().is_empty()
+                {
+                    code_block = None;
+                    lines.push(line.to_owned());
+                } else if is_rust {
+                    // Hidden lines (`# use ...;`) are not shown by rustdoc either.
+                    if trimmed == "#" || trimmed.starts_with("# ") {
+                        continue;
+                    }
+                    lines.push(match trimmed.strip_prefix("##") {
+                        Some(rest) => line.replacen(trimmed, &format!("#{}", rest), 1),
+                        None => line.to_owned(),
+                    });
+                } else {
+                    lines.push(line.to_owned());
+                }
+            }
+            None if is_fence(trimmed) => {
+                let fence_char = trimmed.chars().next().unwrap();
+                let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+                let fence = &trimmed[..fence_len];
+                let info = trimmed[fence_len..].trim();
+                let is_rust = info
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|attribute| !attribute.is_empty())
+                    .all(|attribute| RUST_CODE_BLOCK_ATTRIBUTES.contains(&attribute));
+                let indent = &line[..line.len() - trimmed.len()];
+                if is_rust {
+                    lines.push(format!("{}{}rust", indent, fence));
+                } else {
+                    lines.push(line.to_owned());
+                }
+                code_block = Some((fence, is_rust));
+            }
+            None => {
+                if !is_intra_doc_link_definition(trimmed) {
+                    lines.push(replace_intra_doc_links(line, &labels));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Replace the intra-doc links (`[Foo]`, `` [`Foo`] ``, `[text](Foo::bar)` and
+/// `[text][Foo]`) in a line by their text. `labels` are the labels of intra-doc link
+/// definitions.
+fn replace_intra_doc_links(line: &str, labels: &[&str]) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        result.push_str(&rest[..start]);
+        let after_bracket = &rest[start + 1..];
+        let end = match after_bracket.find(']') {
+            Some(end) => end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+        let text = &after_bracket[..end];
+        let after_text = &after_bracket[end + 1..];
+        // If the link is an intra-doc link, and the length of its target (with the brackets).
+        let link = if let Some(target) = after_text.strip_prefix('(') {
+            closing_paren(target).map(|target_end| {
+                let is_intra = is_intra_doc_target(target[..target_end].trim_matches('`'));
+                (is_intra, target_end + 2)
+            })
+        } else if let Some(target) = after_text.strip_prefix('[') {
+            // A reference link, which uses the definition of its label.
+            target.find(']').map(|target_end| {
+                let label = &target[..target_end];
+                let is_intra = labels.contains(&label) || is_shortcut_intra_doc_link(label);
+                (is_intra, target_end + 2)
+            })
+        } else {
+            None
+        };
+        match link {
+            Some((true, len)) => {
+                result.push_str(text);
+                rest = &after_text[len..];
+            }
+            Some((false, len)) => {
+                // A normal link, keep it.
+                result.push_str(&rest[start..start + 1 + end + 1 + len]);
+                rest = &after_text[len..];
+            }
+            None if labels.contains(&text) || is_shortcut_intra_doc_link(text) => {
+                result.push_str(text);
+                rest = after_text;
+            }
+            None => {
+                result.push('[');
+                rest = after_bracket;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Find the `)` that closes a link target, which can contain parentheses (like `Self::new()`).
+fn closing_paren(target: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in target.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(index),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// If the shortcut link `[text]` is an intra-doc link. Only links to code (`` [`Foo`] ``) and
+/// paths (`[foo::Bar]`) are replaced, so other text in brackets is kept.
+fn is_shortcut_intra_doc_link(text: &str) -> bool {
+    let code = text.len() > 2 && text.starts_with('`') && text.ends_with('`');
+    (code || text.contains("::")) && is_intra_doc_target(text.trim_matches('`'))
+}
+
+/// If the target of a link is a Rust path (like `crate::Foo`, `Self::new()`, `fn@foo` or
+/// `vec!`), instead of a URL.
+fn is_intra_doc_target(target: &str) -> bool {
+    !target.is_empty()
+        && !target.contains("://")
+        && target
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_:<>@!(),& ".contains(c))
+        && target
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+}
+
+/// If the line is a link reference definition for an intra-doc link, like `[Foo]: crate::Foo`.
+fn is_intra_doc_link_definition(line: &str) -> bool {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once("]:"))
+        .is_some_and(|(_, target)| is_intra_doc_target(target.trim().trim_matches('`')))
+}
+
+/// If the line starts a code block.
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// If the (trimmed) line starts a block that is not joined with the previous line.
+fn starts_block(line: &str) -> bool {
+    let ordered_list = line.split_once(". ").is_some_and(|(number, _)| {
+        !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+    });
+    ordered_list
+        || is_fence(line)
+        || ["#", "- ", "* ", "+ ", "> ", "|"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
 }
 
 fn get_doc(attrs: &[Attribute]) -> Option<String> {
-    let doc = attrs
+    let lines = attrs
         .iter()
         .filter_map(|attr| {
             if !attr.path.is_ident("doc") {
@@ -46,14 +291,27 @@ fn get_doc(attrs: &[Attribute]) -> Option<String> {
 
             None
         })
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>();
+    let lines = lines
         .iter()
         .flat_map(|a| a.split('\n'))
-        .map(str::trim)
+        .map(str::trim_end)
+        .collect::<Vec<_>>();
+    // Remove the indentation of all lines (like the space after `///`), but keep the
+    // indentation of code.
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let doc = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default())
         .skip_while(|s| s.is_empty())
         .collect::<Vec<_>>()
         .join("\n");
-    none_if_empty(doc)
+    none_if_empty(doc.trim_end().to_owned())
 }
 
 fn none_if_empty(s: String) -> Option<String> {
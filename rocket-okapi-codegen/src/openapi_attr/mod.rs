@@ -16,8 +16,185 @@ use syn::{parse_macro_input, AttributeArgs, FnArg, Ident, ItemFn, ReturnType, Ty
 struct OpenApiAttribute {
     pub skip: bool,
 
+    /// Skip the route if the `cfg` predicate holds: `skip_if = "cfg(feature = \"internal\")"`.
+    pub skip_if: Option<SkipIf>,
+
     #[darling(multiple, rename = "tag")]
     pub tags: Vec<String>,
+
+    /// The media types accepted by the data guard: `consumes("application/json", ...)`.
+    pub consumes: MediaTypeList,
+
+    /// The summary of the operation, instead of the title from the doc comment.
+    pub summary: Option<String>,
+
+    /// The description of the operation, instead of the description from the doc comment.
+    pub description: Option<String>,
+
+    /// The descriptions of responses: `response(status = 404, description = "...")`.
+    #[darling(multiple, rename = "response")]
+    pub responses: Vec<ResponseAttribute>,
+
+    /// The names of the ignored segments (`<_>` and `<_..>`) of the path:
+    /// `ignored_segments("version", ...)`.
+    pub ignored_segments: SegmentNames,
+
+    /// Document a WebSocket route: `websocket`, or `websocket(message = "ChatMessage")` with the
+    /// type of the messages.
+    pub websocket: Option<WebSocketAttribute>,
+
+    /// Reference parameters that are registered in the settings: `param_ref = "TenantHeader"`.
+    #[darling(multiple, rename = "param_ref")]
+    pub param_refs: Vec<String>,
+
+    /// Reference responses that are registered in the settings:
+    /// `response_ref(status = 401, name = "Unauthorized")`.
+    #[darling(multiple, rename = "response_ref")]
+    pub response_refs: Vec<ResponseRefAttribute>,
+
+    /// Reference a request body that is registered in the settings, instead of documenting the
+    /// data guard: `request_body_ref = "NewUser"`.
+    pub request_body_ref: Option<String>,
+}
+
+/// The options of a WebSocket route.
+#[derive(Debug, Default)]
+struct WebSocketAttribute {
+    /// The type of the messages, for the `x-websocket-messages` extension.
+    message: Option<Type>,
+}
+
+impl FromMeta for WebSocketAttribute {
+    fn from_word() -> darling::Result<Self> {
+        Ok(WebSocketAttribute::default())
+    }
+
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut attribute = WebSocketAttribute::default();
+        for item in items {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(message),
+                    ..
+                })) if path.is_ident("message") => {
+                    attribute.message = Some(message.parse()?);
+                }
+                _ => {
+                    return Err(darling::Error::custom(
+                        "Expected `message = \"...\"` with the type of the messages.",
+                    )
+                    .with_span(item))
+                }
+            }
+        }
+        Ok(attribute)
+    }
+}
+
+/// The description of the response with a status code.
+#[derive(Debug, FromMeta)]
+struct ResponseAttribute {
+    status: ResponseStatus,
+    description: String,
+}
+
+/// The registered response of a status code.
+#[derive(Debug, FromMeta)]
+struct ResponseRefAttribute {
+    status: ResponseStatus,
+    name: String,
+}
+
+/// The status code of a response: a number (`404`), or a string like `"4XX"` or `"default"`.
+#[derive(Debug)]
+struct ResponseStatus(String);
+
+impl FromMeta for ResponseStatus {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        let status = match value {
+            syn::Lit::Int(status) => status.base10_parse::<u16>()?.to_string(),
+            syn::Lit::Str(status) => status.value(),
+            _ => return Err(darling::Error::unexpected_lit_type(value)),
+        };
+        let bytes = status.as_bytes();
+        let valid = status == "default"
+            || (bytes.len() == 3
+                && (b'1'..=b'5').contains(&bytes[0])
+                && (bytes[1..].iter().all(u8::is_ascii_digit) || &bytes[1..] == b"XX"));
+        if valid {
+            Ok(ResponseStatus(status))
+        } else {
+            Err(darling::Error::custom(
+                "Expected a status code like `404`, `\"4XX\"` or `\"default\"`.",
+            )
+            .with_span(value))
+        }
+    }
+}
+
+/// The `cfg` predicate of `skip_if`, like `feature = "internal"`.
+#[derive(Debug)]
+struct SkipIf(proc_macro2::TokenStream);
+
+impl FromMeta for SkipIf {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        let error = || {
+            darling::Error::custom(
+                "Expected a `cfg` predicate, like `skip_if = \"cfg(debug_assertions)\"`.",
+            )
+            .with_span(value)
+        };
+        let predicate = match value {
+            syn::Lit::Str(predicate) => predicate.value(),
+            _ => return Err(error()),
+        };
+        match syn::parse_str::<syn::Meta>(&predicate) {
+            Ok(syn::Meta::List(list)) if list.path.is_ident("cfg") && list.nested.len() == 1 => {
+                Ok(SkipIf(list.nested.into_token_stream()))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+/// A list of media types, like `("application/json", "application/msgpack")`.
+#[derive(Debug, Default)]
+struct MediaTypeList(Vec<String>);
+
+impl FromMeta for MediaTypeList {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                syn::NestedMeta::Lit(syn::Lit::Str(s)) => Ok(s.value()),
+                _ => Err(
+                    darling::Error::custom("Expected a media type string literal.").with_span(item),
+                ),
+            })
+            .collect::<darling::Result<_>>()
+            .map(MediaTypeList)
+    }
+}
+
+/// The names of path segments, like `("version", "rest")`.
+#[derive(Debug, Default)]
+struct SegmentNames(Vec<String>);
+
+impl FromMeta for SegmentNames {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                syn::NestedMeta::Lit(syn::Lit::Str(s)) => Ok(s.value()),
+                _ => Err(
+                    darling::Error::custom("Expected a parameter name string literal.")
+                        .with_span(item),
+                ),
+            })
+            .collect::<darling::Result<_>>()
+            .map(SegmentNames)
+    }
 }
 
 pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -36,7 +213,7 @@ pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 
     match route_attr::parse_attrs(&input.attrs) {
-        Ok(route) => create_route_operation_fn(input, route, okapi_attr.tags),
+        Ok(route) => create_route_operation_fn(input, route, okapi_attr),
         Err(e) => e,
     }
 }
@@ -56,8 +233,22 @@ fn create_empty_route_operation_fn(route_fn: ItemFn) -> TokenStream {
 fn create_route_operation_fn(
     route_fn: ItemFn,
     route: route_attr::Route,
-    tags: Vec<String>,
+    okapi_attr: OpenApiAttribute,
 ) -> TokenStream {
+    let OpenApiAttribute {
+        tags,
+        consumes,
+        summary,
+        description,
+        responses: response_descriptions,
+        skip_if,
+        ignored_segments: SegmentNames(ignored_segment_names),
+        websocket,
+        param_refs,
+        response_refs,
+        request_body_ref,
+        ..
+    } = okapi_attr;
     let arg_types = get_arg_types(route_fn.sig.inputs.into_iter());
     let return_type = match route_fn.sig.output {
         ReturnType::Type(_, ty) => *ty,
@@ -103,6 +294,34 @@ fn create_route_operation_fn(
             <#ty as ::rocket_okapi::request::OpenApiFromSegments>::path_multi_parameter(gen, #arg.to_owned())?.into()
         })
     }
+    // Ignored path segments: `/<_>/<_..>`
+    let ignored_segments: Vec<bool> = route.ignored_segments().collect();
+    if ignored_segment_names.len() > ignored_segments.len() {
+        return quote! {
+            compile_error!("`ignored_segments` has more names than the path has ignored segments.");
+        }
+        .into();
+    }
+    for (index, is_multi) in ignored_segments.into_iter().enumerate() {
+        let name = ignored_segment_names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("_{}", index + 1));
+        let parameter = if is_multi {
+            quote! {
+                <::std::path::PathBuf as ::rocket_okapi::request::OpenApiFromSegments>::path_multi_parameter(gen, #name.to_owned())?
+            }
+        } else {
+            quote! {
+                <::std::string::String as ::rocket_okapi::request::OpenApiFromParam>::path_parameter(gen, #name.to_owned())?
+            }
+        };
+        params.push(quote! {{
+            let mut parameter = #parameter;
+            parameter.description = Some("Any value, this segment is ignored.".to_owned());
+            parameter.into()
+        }})
+    }
     // Query parameters: `/?<id>&<name>`
     for arg in route.query_params() {
         let ty = match arg_types.get(arg) {
@@ -145,15 +364,36 @@ fn create_route_operation_fn(
                 Some(m) => {
                     let s = m.to_string();
                     quote!(Some(#s))
-                },
-                None => quote!(None)
+                }
+                None => quote!(None),
             };
 
             // Add parameter to list
             params_names_used.push(data_param.clone());
-            quote! {
-                Some(<#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen, #mime_type)?.into())
+            let request_body_fn = request_body_fn(ty);
+            if let Some(name) = &request_body_ref {
+                quote! {
+                    Some(gen.request_body_ref(#name)?)
+                }
+            } else if consumes.0.is_empty() {
+                quote! {
+                    Some(#request_body_fn(gen, #mime_type)?.into())
+                }
+            } else {
+                // Document the body for every media type listed in `consumes(...)`.
+                let media_types = consumes.0;
+                quote! {
+                    Some(::rocket_okapi::util::combine_request_bodies(vec![#(
+                        (#media_types, #request_body_fn(gen, Some(#media_types))?)
+                    ),*]).into())
+                }
+            }
+        }
+        None if request_body_ref.is_some() => {
+            return quote! {
+                compile_error!("`request_body_ref` routes need a `data` parameter.");
             }
+            .into()
         }
         None => quote! { None },
     };
@@ -173,41 +413,108 @@ fn create_route_operation_fn(
         }
     }
 
+    // -- WebSocket --
+    // The response is the upgrade, so the return type does not have to be documented.
+    let (responses, websocket_parameters, extensions) = match &websocket {
+        Some(websocket) => {
+            if route.method != Method::Get {
+                return quote! {
+                    compile_error!("`websocket` routes have to use the `GET` method.");
+                }
+                .into();
+            }
+            let messages = websocket.message.as_ref().map(|message| {
+                quote! {
+                    extensions.insert(
+                        "x-websocket-messages".to_owned(),
+                        ::rocket_okapi::util::websocket_messages::<#message>(gen),
+                    );
+                }
+            });
+            (
+                quote!(::rocket_okapi::util::websocket_responses(gen)),
+                quote! {
+                    for parameter in ::rocket_okapi::util::websocket_parameters(gen) {
+                        parameters.push(parameter.into());
+                    }
+                },
+                quote! {
+                    let mut extensions = ::rocket_okapi::okapi::openapi3::Object::default();
+                    #messages
+                },
+            )
+        }
+        None => (
+            quote!(<#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?),
+            quote!(),
+            quote!(let extensions = ::rocket_okapi::okapi::openapi3::Object::default();),
+        ),
+    };
+
     let fn_name = get_add_operation_fn_name(&route_fn.sig.ident);
-    let path = route
-        .origin
-        .path()
-        .as_str()
-        .replace("<", "{")
-        .replace("..>", "}")
-        .replace(">", "}");
+    let path = route.openapi_path(&ignored_segment_names);
     let method = Ident::new(&to_pascal_case_string(route.method), Span::call_site());
     let (title, desc) = doc_attr::get_title_and_desc_from_doc(&route_fn.attrs);
-    let title = match title {
-        Some(x) => quote!(Some(#x.to_owned())),
-        None => quote!(None),
-    };
-    let desc = match desc {
-        Some(x) => quote!(Some(#x.to_owned())),
-        None => quote!(None),
-    };
+    let (paragraph_title, paragraph_desc) =
+        doc_attr::get_title_and_desc_from_first_paragraph(&route_fn.attrs);
+    let title = option_string(summary.clone().or(title));
+    let desc = option_string(description.clone().or(desc));
+    let paragraph_title = option_string(summary.or(paragraph_title));
+    let paragraph_desc = option_string(description.or(paragraph_desc));
+
+    let response_descriptions = response_descriptions.into_iter().map(|response| {
+        let ResponseAttribute {
+            status: ResponseStatus(status),
+            description,
+        } = response;
+        quote! {
+            ::rocket_okapi::util::set_response_description(&mut responses, #status, #description)?;
+        }
+    });
+
+    let response_refs = response_refs.into_iter().map(|response| {
+        let ResponseRefAttribute {
+            status: ResponseStatus(status),
+            name,
+        } = response;
+        quote! {
+            responses.responses.insert(#status.to_owned(), gen.response_ref(#name)?);
+        }
+    });
 
     let tags = tags
         .into_iter()
         .map(|tag| quote!(#tag.to_owned()))
         .collect::<Vec<_>>();
 
+    let skip_if = match skip_if {
+        Some(SkipIf(predicate)) => quote!(cfg!(#predicate)),
+        None => quote!(false),
+    };
+
     TokenStream::from(quote! {
         pub fn #fn_name(
             gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
             op_id: String,
         ) -> ::rocket_okapi::Result<()> {
-            let mut responses = <#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?;
+            let tags: Vec<String> = vec![#(#tags),*];
+            let route = ::rocket_okapi::RouteInfo {
+                path: #path,
+                method: ::rocket::http::Method::#method,
+                operation_id: &op_id,
+                tags: &tags,
+            };
+            if #skip_if || gen.skips_route(&route) {
+                return Ok(());
+            }
+            let mut responses = #responses;
             // Add responses from Request Guards.
             let request_guard_responses = vec![#(#request_guard_responses),*];
             for request_guard_response in request_guard_responses {
                 ::rocket_okapi::okapi::merge::merge_responses(&mut responses, &request_guard_response)?;
             }
+            #(#response_descriptions)*
+            #(#response_refs)*
 
             let request_body = #request_body;
             // Add the security scheme that are quired for all the routes.
@@ -225,6 +532,8 @@ fn create_route_operation_fn(
                 }
             }
             // Body Data does not add any parameters
+            #websocket_parameters
+            #(parameters.push(gen.parameter_ref(#param_refs)?);)*
 
             // Add all Request Guards
             let request_guards_route: Vec<::rocket_okapi::request::RequestHeaderInput> = vec![#(#params_request_guards),*];
@@ -253,6 +562,12 @@ fn create_route_operation_fn(
             } else {
                 Some(security_requirements)
             };
+            let (summary, description) = if gen.settings().summary_from_first_paragraph {
+                (#paragraph_title, #paragraph_desc)
+            } else {
+                (#title, #desc)
+            };
+            #extensions
             // Add route/endpoint to OpenApi object.
             gen.add_operation(::rocket_okapi::OperationInfo {
                 path: #path.to_owned(),
@@ -262,10 +577,11 @@ fn create_route_operation_fn(
                     responses,
                     request_body,
                     parameters,
-                    summary: #title,
-                    description: #desc,
+                    summary,
+                    description,
                     security,
-                    tags: vec![#(#tags),*],
+                    tags,
+                    extensions,
                     ..Default::default()
                 },
             });
@@ -274,6 +590,59 @@ fn create_route_operation_fn(
     })
 }
 
+/// Get the function that documents the request body of the data guard `ty`.
+///
+/// `Form<Strict<T>>` and `Form<Lenient<T>>` can not have their own `OpenApiFromData` impl (it
+/// would conflict with the one of `Form<T>`), so they are detected by name. Type aliases of them
+/// are documented like `Form<T>`.
+fn request_body_fn(ty: &Type) -> proc_macro2::TokenStream {
+    if let Some((mode, inner)) = form_mode(ty) {
+        let function = match mode.as_str() {
+            "Strict" => quote!(strict_form_request_body),
+            _ => quote!(lenient_form_request_body),
+        };
+        return quote!(::rocket_okapi::request::#function::<#inner>);
+    }
+    quote!(<#ty as ::rocket_okapi::request::OpenApiFromData>::request_body)
+}
+
+/// If `ty` is `Form<Strict<T>>` or `Form<Lenient<T>>` (with any path), get `Strict` or `Lenient`
+/// and `T`.
+fn form_mode(ty: &Type) -> Option<(String, Type)> {
+    let inner = last_segment_argument(ty, "Form")?;
+    ["Strict", "Lenient"]
+        .iter()
+        .find_map(|mode| last_segment_argument(&inner, mode).map(|inner| (mode.to_string(), inner)))
+}
+
+/// If the last segment of the path `ty` is `name<T>`, get `T`.
+fn last_segment_argument(ty: &Type, name: &str) -> Option<Type> {
+    let path = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return None,
+    };
+    let segment = path
+        .segments
+        .last()
+        .filter(|segment| segment.ident == name)?;
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn option_string(value: Option<String>) -> proc_macro2::TokenStream {
+    match value {
+        Some(x) => quote!(Some(#x.to_owned())),
+        None => quote!(None),
+    }
+}
+
 fn unit_type() -> Type {
     Type::Tuple(TypeTuple {
         paren_token: syn::token::Paren::default(),
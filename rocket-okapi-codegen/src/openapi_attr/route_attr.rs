@@ -17,7 +17,7 @@ pub struct Route {
 impl Route {
     pub fn path_params(&self) -> impl Iterator<Item = &str> {
         self.origin.path().segments().filter_map(|s| {
-            if s.starts_with('<') && s.ends_with('>') && !s.ends_with("..>") {
+            if s.starts_with('<') && s.ends_with('>') && !s.ends_with("..>") && s != "<_>" {
                 Some(&s[1..s.len() - 1])
             } else {
                 None
@@ -27,7 +27,7 @@ impl Route {
 
     pub fn path_multi_param(&self) -> Option<&str> {
         self.origin.path().segments().find_map(|s| {
-            if s.starts_with('<') && s.ends_with("..>") {
+            if s.starts_with('<') && s.ends_with("..>") && s != "<_..>" {
                 Some(&s[1..s.len() - 3])
             } else {
                 None
@@ -35,6 +35,38 @@ impl Route {
         })
     }
 
+    /// The ignored segments (`<_>` and `<_..>`) of the path, as `true` for `<_..>`.
+    pub fn ignored_segments(&self) -> impl Iterator<Item = bool> + '_ {
+        self.origin.path().segments().filter_map(|s| match s {
+            "<_>" => Some(false),
+            "<_..>" => Some(true),
+            _ => None,
+        })
+    }
+
+    /// The path in the syntax of OpenAPI (`/users/{id}`). The ignored segments get the `names`
+    /// (in order), or `_1`, `_2`, ... if there are not enough names.
+    pub fn openapi_path(&self, names: &[String]) -> String {
+        let mut ignored = 0;
+        self.origin
+            .path()
+            .as_str()
+            .split('/')
+            .map(|s| match s {
+                "<_>" | "<_..>" => {
+                    ignored += 1;
+                    let name = names
+                        .get(ignored - 1)
+                        .cloned()
+                        .unwrap_or_else(|| format!("_{}", ignored));
+                    format!("{{{}}}", name)
+                }
+                _ => s.replace('<', "{").replace("..>", "}").replace('>', "}"),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
     pub fn query_params(&self) -> impl Iterator<Item = &str> {
         let mut query_params: Vec<&str> = vec![];
         if let Some(query) = self.origin.query() {
@@ -13,51 +13,52 @@ pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
             let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
             #add_operations
             let mut spec = gen.into_openapi();
-            let mut info = ::rocket_okapi::okapi::openapi3::Info {
-                title: env!("CARGO_PKG_NAME").to_owned(),
-                version: env!("CARGO_PKG_VERSION").to_owned(),
-                ..Default::default()
-            };
-            if !env!("CARGO_PKG_DESCRIPTION").is_empty() {
-                info.description = Some(env!("CARGO_PKG_DESCRIPTION").to_owned());
-            }
-            if !env!("CARGO_PKG_REPOSITORY").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Repository".to_owned()),
-                    url: Some(env!("CARGO_PKG_REPOSITORY").to_owned()),
-                    ..Default::default()
-                });
-            }
-            if !env!("CARGO_PKG_HOMEPAGE").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Homepage".to_owned()),
-                    url: Some(env!("CARGO_PKG_HOMEPAGE").to_owned()),
-                    ..Default::default()
-                });
-            }
-            spec.info = info;
-
+            spec.info = ::rocket_okapi::package_info!();
             spec
         }
     })
 }
 
+/// Parses routes and returns a `rocket_okapi::OpenApiRoutes` with the routes and their
+/// operations.
+pub fn create_route_list(routes: TokenStream) -> Result<TokenStream2> {
+    let paths = <Punctuated<Path, Comma>>::parse_terminated.parse(routes)?;
+    let operations = operations(&paths);
+    Ok(quote! {
+        ::rocket_okapi::OpenApiRoutes::new(
+            ::rocket::routes![#paths],
+            vec![#(#operations),*],
+        )
+    })
+}
+
 fn create_add_operations(paths: Punctuated<Path, Comma>) -> TokenStream2 {
-    let function_calls = paths.into_iter().map(|path| {
-        let fn_name = fn_name_for_add_operation(path.clone());
-        let operation_id = operation_id(&path);
-        quote! {
-            #fn_name(&mut gen, #operation_id.to_owned())
-                .expect(&format!("Could not generate OpenAPI operation for `{}`.", stringify!(#path)));
-        }
-    });
+    let operations = operations(&paths);
     quote! {
-        #(#function_calls)*
+        gen.add_operations(vec![#(#operations),*]);
     }
 }
 
+/// The `(name, operation id, add operation function)` tuples of the routes.
+fn operations(paths: &Punctuated<Path, Comma>) -> Vec<TokenStream2> {
+    paths
+        .iter()
+        .map(|path| {
+            let fn_name = fn_name_for_add_operation(path.clone());
+            let operation_id = operation_id(path);
+            quote! {
+                (
+                    stringify!(#path),
+                    #operation_id.to_owned(),
+                    #fn_name as ::rocket_okapi::gen::AddOperationFn,
+                )
+            }
+        })
+        .collect()
+}
+
 fn fn_name_for_add_operation(mut fn_path: Path) -> Path {
-    let mut last_seg = fn_path.segments.last_mut().expect("syn::Path has segments");
+    let last_seg = fn_path.segments.last_mut().expect("syn::Path has segments");
     last_seg.ident = get_add_operation_fn_name(&last_seg.ident);
     fn_path
 }
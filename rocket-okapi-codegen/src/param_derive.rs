@@ -0,0 +1,81 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, GenericParam, Lifetime, LifetimeDef, Result};
+
+/// Implements `OpenApiFromParam` for a newtype (a struct with one field), documenting the path
+/// parameter with the schema of the type of its field.
+pub fn derive(input: TokenStream) -> Result<TokenStream2> {
+    let ast: DeriveInput = syn::parse(input)?;
+    let name = &ast.ident;
+    let inner = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0].ty,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => return Err(newtype_error(&ast)),
+        },
+        _ => return Err(newtype_error(&ast)),
+    };
+
+    // The trait needs a lifetime, which the type itself does not have to use.
+    let mut generics = ast.generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(Lifetime::new(
+            "'__r",
+            proc_macro2::Span::call_site(),
+        ))),
+    );
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    // Generic newtypes are only documented when their inner type has a schema.
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! {
+            #inner: ::rocket_okapi::JsonSchema
+        });
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! {
+            #name #ty_generics: ::rocket::request::FromParam<'__r>
+        });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::request::OpenApiFromParam<'__r> for #name #ty_generics
+            #where_clause
+        {
+            fn path_parameter(
+                gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+                name: String,
+            ) -> ::rocket_okapi::Result<::rocket_okapi::okapi::openapi3::Parameter> {
+                let schema = gen.json_schema::<#inner>();
+                Ok(::rocket_okapi::okapi::openapi3::Parameter {
+                    name,
+                    location: "path".to_owned(),
+                    description: None,
+                    required: true,
+                    deprecated: false,
+                    allow_empty_value: false,
+                    value: ::rocket_okapi::okapi::openapi3::ParameterValue::Schema {
+                        style: None,
+                        explode: None,
+                        allow_reserved: false,
+                        schema,
+                        example: None,
+                        examples: None,
+                    },
+                    extensions: ::rocket_okapi::okapi::openapi3::Object::default(),
+                })
+            }
+        }
+    })
+}
+
+fn newtype_error(ast: &DeriveInput) -> Error {
+    Error::new_spanned(
+        &ast.ident,
+        "Only structs with exactly one field can derive `OpenApiFromParam`.",
+    )
+}
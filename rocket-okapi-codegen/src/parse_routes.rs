@@ -14,10 +14,7 @@ pub fn parse_routes(routes: TokenStream) -> Result<TokenStream2> {
             -> Vec<::rocket::Route> {
                 let mut routes = ::rocket::routes![#paths];
                 if let Some(spec) = spec_opt {
-                    routes.push(
-                        ::rocket_okapi::handlers::OpenApiHandler::new(spec)
-                            .into_route(&settings.json_path)
-                    );
+                    routes.extend(::rocket_okapi::get_openapi_routes(spec, settings));
                 }
                 routes
         }
@@ -0,0 +1,278 @@
+use crate::gen::OpenApiGenerator;
+use crate::request::{OpenApiFromRequest, RequestHeaderInput};
+use hmac::{Hmac, Mac, NewMac};
+use okapi::openapi3::{
+    Object, Response, Responses, SecurityRequirement, SecurityScheme, SecuritySchemeData,
+};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The name of the security scheme of [`Jwt`].
+pub const JWT_SECURITY_SCHEME: &str = "jwt";
+
+/// The key that verifies the tokens of [`Jwt`] guards. It has to be managed state of the
+/// Rocket instance.
+///
+/// ```rust,no_run
+/// use rocket_okapi::auth::JwtKey;
+///
+/// let key = JwtKey::hs256(std::env::var("JWT_SECRET").unwrap()).leeway(30);
+/// rocket::build().manage(key);
+/// ```
+#[derive(Clone)]
+pub struct JwtKey {
+    secret: Vec<u8>,
+    leeway: u64,
+}
+
+impl JwtKey {
+    /// Verify tokens that are signed with HMAC-SHA256 (`HS256`) and the shared `secret`.
+    #[must_use]
+    pub fn hs256(secret: impl AsRef<[u8]>) -> Self {
+        JwtKey {
+            secret: secret.as_ref().to_vec(),
+            leeway: 0,
+        }
+    }
+
+    /// Accept tokens that expired (or become valid) at most `seconds` ago (or from now), for
+    /// clocks that are not synchronized. The default is `0`.
+    #[must_use]
+    pub fn leeway(mut self, seconds: u64) -> Self {
+        self.leeway = seconds;
+        self
+    }
+
+    /// Verify the signature and the `exp` and `nbf` claims of `token`, and deserialize its
+    /// claims.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is not valid, or its claims are not a `C`.
+    pub fn verify<C: DeserializeOwned>(&self, token: &str) -> Result<C, JwtError> {
+        let mut parts = token.split('.');
+        let (header, payload, signature) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(header), Some(payload), Some(signature)) if parts.next().is_none() => {
+                (header, payload, signature)
+            }
+            _ => return Err(JwtError::Malformed),
+        };
+
+        let header: JwtHeader =
+            serde_json::from_slice(&decode(header)?).map_err(|_| JwtError::Malformed)?;
+        if header.alg != "HS256" {
+            return Err(JwtError::UnsupportedAlgorithm(header.alg));
+        }
+        let mut mac = Hmac::<Sha256>::new_varkey(&self.secret).expect("HMAC takes any key size.");
+        mac.update(&token.as_bytes()[..token.len() - signature.len() - 1]);
+        mac.verify(&decode(signature)?)
+            .map_err(|_| JwtError::InvalidSignature)?;
+
+        let payload = decode(payload)?;
+        let times: JwtTimes = serde_json::from_slice(&payload).map_err(|_| JwtError::Malformed)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        if times
+            .exp
+            .is_some_and(|exp| exp.saturating_add(self.leeway) <= now)
+        {
+            return Err(JwtError::Expired);
+        }
+        if times
+            .nbf
+            .is_some_and(|nbf| nbf > now.saturating_add(self.leeway))
+        {
+            return Err(JwtError::NotYetValid);
+        }
+        serde_json::from_slice(&payload).map_err(|err| JwtError::InvalidClaims(err.to_string()))
+    }
+}
+
+impl fmt::Debug for JwtKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not print the secret.
+        f.debug_struct("JwtKey")
+            .field("leeway", &self.leeway)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtTimes {
+    exp: Option<u64>,
+    nbf: Option<u64>,
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, JwtError> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).map_err(|_| JwtError::Malformed)
+}
+
+/// The reason a [`Jwt`] guard failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwtError {
+    /// The request has no `Authorization: Bearer ...` header.
+    Missing,
+    /// The token is not a JWT.
+    Malformed,
+    /// The token is signed with an other algorithm than `HS256`.
+    UnsupportedAlgorithm(String),
+    /// The signature of the token is not valid.
+    InvalidSignature,
+    /// The token is expired (`exp`).
+    Expired,
+    /// The token is not valid yet (`nbf`).
+    NotYetValid,
+    /// The token is valid, but its claims could not be deserialized, like when a required claim
+    /// is missing.
+    InvalidClaims(String),
+    /// There is no managed [`JwtKey`].
+    NoKey,
+}
+
+impl JwtError {
+    /// The status of the response: `403 Forbidden` for tokens with invalid claims, `500` if there
+    /// is no [`JwtKey`], and `401 Unauthorized` otherwise.
+    #[must_use]
+    pub fn status(&self) -> Status {
+        match self {
+            JwtError::InvalidClaims(_) => Status::Forbidden,
+            JwtError::NoKey => Status::InternalServerError,
+            _ => Status::Unauthorized,
+        }
+    }
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtError::Missing => write!(f, "The request has no bearer token."),
+            JwtError::Malformed => write!(f, "The bearer token is not a JWT."),
+            JwtError::UnsupportedAlgorithm(alg) => {
+                write!(f, "The algorithm `{}` of the token is not supported.", alg)
+            }
+            JwtError::InvalidSignature => write!(f, "The signature of the token is not valid."),
+            JwtError::Expired => write!(f, "The token is expired."),
+            JwtError::NotYetValid => write!(f, "The token is not valid yet."),
+            JwtError::InvalidClaims(err) => write!(f, "The claims are not valid: {}", err),
+            JwtError::NoKey => write!(f, "No `JwtKey` is managed."),
+        }
+    }
+}
+
+impl std::error::Error for JwtError {}
+
+/// A request guard that verifies the JWT of the `Authorization: Bearer ...` header with the
+/// managed [`JwtKey`], and deserializes its claims `C`. Routes that use it are documented with
+/// the bearer security scheme `jwt`, and `401 Unauthorized` and `403 Forbidden` responses (see
+/// [`JwtError::status`]).
+///
+/// ```rust,no_run
+/// use rocket::get;
+/// use rocket_okapi::auth::Jwt;
+/// use rocket_okapi::openapi;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Claims {
+///     sub: String,
+/// }
+///
+/// #[openapi]
+/// #[get("/me")]
+/// fn me(token: Jwt<Claims>) -> String {
+///     token.0.sub
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Jwt<C>(pub C);
+
+impl<C> Jwt<C> {
+    /// Get the claims.
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> std::ops::Deref for Jwt<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, C: DeserializeOwned + Send> FromRequest<'r> for Jwt<C> {
+    type Error = JwtError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let result = match request.rocket().state::<JwtKey>() {
+            Some(key) => match request
+                .headers()
+                .get_one("Authorization")
+                .and_then(|value| value.strip_prefix("Bearer "))
+            {
+                Some(token) => key.verify(token.trim()).map(Jwt),
+                None => Err(JwtError::Missing),
+            },
+            None => Err(JwtError::NoKey),
+        };
+        match result {
+            Ok(jwt) => Outcome::Success(jwt),
+            Err(err) => Outcome::Failure((err.status(), err)),
+        }
+    }
+}
+
+impl<'r, C: DeserializeOwned + Send> OpenApiFromRequest<'r> for Jwt<C> {
+    fn from_request_input(
+        _gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> crate::Result<RequestHeaderInput> {
+        let scheme = SecurityScheme {
+            description: Some("A JWT in the `Authorization: Bearer ...` header.".to_owned()),
+            data: SecuritySchemeData::Http {
+                scheme: "bearer".to_owned(),
+                bearer_format: Some("JWT".to_owned()),
+            },
+            extensions: Object::default(),
+        };
+        let mut requirement = SecurityRequirement::new();
+        requirement.insert(JWT_SECURITY_SCHEME.to_owned(), Vec::new());
+        Ok(RequestHeaderInput::Security(
+            JWT_SECURITY_SCHEME.to_owned(),
+            scheme,
+            requirement,
+        ))
+    }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        let mut responses = Responses::default();
+        for (status, description) in [
+            ("401", "The bearer token is missing or not valid."),
+            ("403", "The claims of the token do not allow this request."),
+        ] {
+            responses.responses.insert(
+                status.to_owned(),
+                Response {
+                    description: description.to_owned(),
+                    ..Response::default()
+                }
+                .into(),
+            );
+        }
+        Ok(responses)
+    }
+}
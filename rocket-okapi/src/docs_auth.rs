@@ -0,0 +1,243 @@
+use rocket::futures::future::BoxFuture;
+use rocket::http::{Cookie, Header, Status};
+use rocket::request::{self, FromRequest};
+use rocket::response::{self, Responder, Response};
+use rocket::route::{Handler, Outcome};
+use rocket::{Data, Request, Route};
+use std::fmt;
+use std::sync::Arc;
+
+/// Protects the routes of the documentation (the `openapi.json` file, Swagger UI and RapiDoc),
+/// for APIs whose specification must not be public.
+///
+/// The `openapi.json` route is protected with
+/// [`OpenApiSettings::docs_auth`](crate::settings::OpenApiSettings::docs_auth), other routes
+/// (like the ones of the UIs) with [`protect`](Self::protect). Requests that are not allowed get
+/// a `401 Unauthorized` response (or the status of the failed guard).
+///
+/// Note that [`get_openapi_spec`](crate::get_openapi_spec) can not get a protected
+/// specification.
+///
+/// ```rust,no_run
+/// use rocket_okapi::docs_auth::DocsAuth;
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::swagger_ui::{make_swagger_ui, SwaggerUIConfig};
+/// use rocket_okapi::openapi_get_routes;
+///
+/// let auth = DocsAuth::basic("docs", "secret");
+/// let mut settings = OpenApiSettings::new();
+/// settings.docs_auth = Some(auth.clone());
+/// let config = SwaggerUIConfig {
+///     url: "../openapi.json".to_owned(),
+///     ..SwaggerUIConfig::default()
+/// };
+/// rocket::build()
+///     .mount("/", openapi_get_routes![settings:])
+///     .mount("/swagger-ui/", auth.protect(make_swagger_ui(&config)));
+/// ```
+#[derive(Clone)]
+pub struct DocsAuth {
+    kind: AuthKind,
+}
+
+#[derive(Clone)]
+enum AuthKind {
+    /// The expected `Authorization` header.
+    Basic(String),
+    QueryToken {
+        name: String,
+        token: String,
+    },
+    Guard(Arc<GuardFn>),
+}
+
+type GuardFn =
+    dyn for<'r, 'c> Fn(&'r Request<'c>) -> BoxFuture<'r, Result<(), Status>> + Send + Sync;
+
+impl DocsAuth {
+    /// Allow requests that use HTTP basic auth with the given username and password. Browsers ask
+    /// for the credentials.
+    #[must_use]
+    pub fn basic(username: impl AsRef<str>, password: impl AsRef<str>) -> Self {
+        let credentials = format!("{}:{}", username.as_ref(), password.as_ref());
+        DocsAuth {
+            kind: AuthKind::Basic(format!("Basic {}", base64_encode(credentials.as_bytes()))),
+        }
+    }
+
+    /// Allow requests that have the query parameter `name` set to `token`, like
+    /// `/swagger-ui/index.html?docs_token=secret`. The token is then stored in a cookie with the
+    /// same name, so the files the UI loads (like `openapi.json`) are allowed too.
+    #[must_use]
+    pub fn query_token(name: impl Into<String>, token: impl Into<String>) -> Self {
+        DocsAuth {
+            kind: AuthKind::QueryToken {
+                name: name.into(),
+                token: token.into(),
+            },
+        }
+    }
+
+    /// Allow requests for which the request guard `G` succeeds, like a guard that checks the
+    /// session of a logged in administrator. If the guard fails, its status is used.
+    #[must_use]
+    pub fn guard<G>() -> Self
+    where
+        G: for<'r> FromRequest<'r>,
+    {
+        let guard: Arc<GuardFn> = Arc::new(|req| {
+            Box::pin(async move {
+                match G::from_request(req).await {
+                    request::Outcome::Success(_) => Ok(()),
+                    request::Outcome::Failure((status, _)) => Err(status),
+                    request::Outcome::Forward(_) => Err(Status::Unauthorized),
+                }
+            })
+        });
+        DocsAuth {
+            kind: AuthKind::Guard(guard),
+        }
+    }
+
+    /// Protect the given routes, like the routes of Swagger UI or RapiDoc.
+    pub fn protect(&self, routes: impl Into<Vec<Route>>) -> Vec<Route> {
+        routes
+            .into()
+            .into_iter()
+            .map(|route| self.protect_route(route))
+            .collect()
+    }
+
+    pub(crate) fn protect_route(&self, mut route: Route) -> Route {
+        route.handler = Box::new(ProtectedHandler {
+            auth: self.clone(),
+            handler: route.handler,
+        });
+        route
+    }
+
+    /// Check if the request is allowed.
+    async fn check(&self, req: &Request<'_>) -> Result<(), Rejection> {
+        match &self.kind {
+            AuthKind::Basic(expected) => {
+                let authorization = req.headers().get_one("Authorization").unwrap_or_default();
+                if constant_time_eq(authorization, expected) {
+                    Ok(())
+                } else {
+                    Err(Rejection {
+                        status: Status::Unauthorized,
+                        basic_challenge: true,
+                    })
+                }
+            }
+            AuthKind::QueryToken { name, token } => {
+                let query = req
+                    .query_value::<&str>(name)
+                    .and_then(|value| value.ok())
+                    .filter(|value| constant_time_eq(value, token));
+                if query.is_some() {
+                    let cookie = Cookie::build(name.clone(), token.clone())
+                        .http_only(true)
+                        .finish();
+                    req.cookies().add(cookie);
+                    return Ok(());
+                }
+                let cookie = req
+                    .cookies()
+                    .get(name)
+                    .filter(|cookie| constant_time_eq(cookie.value(), token));
+                match cookie {
+                    Some(_) => Ok(()),
+                    None => Err(Rejection {
+                        status: Status::Unauthorized,
+                        basic_challenge: false,
+                    }),
+                }
+            }
+            AuthKind::Guard(guard) => guard(req).await.map_err(|status| Rejection {
+                status,
+                basic_challenge: false,
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for DocsAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Don't show the secrets.
+        let kind = match &self.kind {
+            AuthKind::Basic(_) => "basic",
+            AuthKind::QueryToken { .. } => "query_token",
+            AuthKind::Guard(_) => "guard",
+        };
+        f.debug_struct("DocsAuth").field("kind", &kind).finish()
+    }
+}
+
+/// A handler that only calls `handler` if the request is allowed by `auth`.
+#[derive(Clone)]
+struct ProtectedHandler {
+    auth: DocsAuth,
+    handler: Box<dyn Handler>,
+}
+
+#[rocket::async_trait]
+impl Handler for ProtectedHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match self.auth.check(req).await {
+            Ok(()) => self.handler.handle(req, data).await,
+            Err(rejection) => Outcome::from(req, rejection),
+        }
+    }
+}
+
+/// The response to a request that is not allowed.
+struct Rejection {
+    status: Status,
+    /// Whether to ask the browser for basic auth credentials.
+    basic_challenge: bool,
+}
+
+impl<'r> Responder<'r, 'static> for Rejection {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.status(self.status);
+        if self.basic_challenge {
+            response.header(Header::new(
+                "WWW-Authenticate",
+                "Basic realm=\"API documentation\", charset=\"UTF-8\"",
+            ));
+        }
+        response.ok()
+    }
+}
+
+/// Compare secrets in a time that does not depend on how many characters match.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
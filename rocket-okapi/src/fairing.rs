@@ -0,0 +1,189 @@
+use crate::settings::{OpenApiSettings, SharedSettings};
+use okapi::openapi3::OpenApi;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::{Build, Rocket, Route};
+
+#[cfg(feature = "rapidoc")]
+use crate::rapidoc::{make_rapidoc, GeneralConfig, RapiDocConfig};
+#[cfg(any(feature = "swagger", feature = "rapidoc"))]
+use crate::settings::UrlObject;
+#[cfg(feature = "swagger")]
+use crate::swagger_ui::{make_swagger_ui, SwaggerUIConfig};
+
+/// A fairing that mounts the documented routes, the `openapi.json` file and the documentation
+/// UIs, in one `attach` call.
+///
+/// The specifications of all mounted routes are merged (like
+/// [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs)), and served at
+/// the `json_path` of the settings. The UIs use the `doc_theme` and the routes of the UIs are
+/// protected by the `docs_auth` of the settings.
+///
+/// ```rust,no_run
+/// use rocket::get;
+/// use rocket_okapi::fairing::OpenApiFairing;
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::{openapi, openapi_get_routes_spec};
+///
+/// #[openapi]
+/// #[get("/hello")]
+/// fn hello() -> &'static str {
+///     "Hello!"
+/// }
+///
+/// let settings = OpenApiSettings::new();
+/// let fairing = OpenApiFairing::new(settings.clone())
+///     .mount("/", openapi_get_routes_spec![settings: hello])
+///     .with_swagger_ui("/docs")
+///     .with_rapidoc("/rapidoc");
+/// rocket::build().attach(fairing);
+/// ```
+pub struct OpenApiFairing {
+    settings: SharedSettings,
+    endpoints: Vec<(String, Vec<Route>, OpenApi)>,
+    #[cfg(feature = "swagger")]
+    swagger_ui: Vec<(String, SwaggerUIConfig)>,
+    #[cfg(feature = "rapidoc")]
+    rapidoc: Vec<(String, RapiDocConfig)>,
+}
+
+impl OpenApiFairing {
+    /// Create a fairing that serves the specification as configured by `settings`.
+    #[must_use]
+    pub fn new(settings: OpenApiSettings) -> Self {
+        OpenApiFairing {
+            settings: SharedSettings::new(&settings),
+            endpoints: Vec::new(),
+            #[cfg(feature = "swagger")]
+            swagger_ui: Vec::new(),
+            #[cfg(feature = "rapidoc")]
+            rapidoc: Vec::new(),
+        }
+    }
+
+    /// Mount routes and their specification (as returned by
+    /// [`openapi_get_routes_spec!`](crate::openapi_get_routes_spec)) at `path`.
+    #[must_use]
+    pub fn mount(
+        mut self,
+        path: impl Into<String>,
+        routes_and_spec: (Vec<Route>, OpenApi),
+    ) -> Self {
+        let (routes, spec) = routes_and_spec;
+        self.endpoints.push((path.into(), routes, spec));
+        self
+    }
+
+    /// Serve Swagger UI at `path`, showing the served specification.
+    #[cfg(feature = "swagger")]
+    #[must_use]
+    pub fn with_swagger_ui(self, path: impl Into<String>) -> Self {
+        let config = SwaggerUIConfig {
+            url: self.settings.json_path.clone(),
+            ..SwaggerUIConfig::default()
+        };
+        self.with_swagger_ui_config(path, config)
+    }
+
+    /// Serve Swagger UI at `path` with a custom config. If the config has no `url` and `urls`,
+    /// the served specification is shown.
+    #[cfg(feature = "swagger")]
+    #[must_use]
+    pub fn with_swagger_ui_config(
+        mut self,
+        path: impl Into<String>,
+        mut config: SwaggerUIConfig,
+    ) -> Self {
+        if config.url.is_empty() && config.urls.is_empty() {
+            config.url = self.settings.json_path.clone();
+        }
+        if config.doc_theme.is_none() {
+            config.doc_theme = self.settings.doc_theme.clone();
+        }
+        self.swagger_ui.push((path.into(), config));
+        self
+    }
+
+    /// Serve RapiDoc at `path`, showing the served specification.
+    #[cfg(feature = "rapidoc")]
+    #[must_use]
+    pub fn with_rapidoc(self, path: impl Into<String>) -> Self {
+        let config = RapiDocConfig {
+            general: GeneralConfig {
+                spec_urls: vec![UrlObject::new("General", &self.settings.json_path)],
+                ..GeneralConfig::default()
+            },
+            ..RapiDocConfig::default()
+        };
+        self.with_rapidoc_config(path, config)
+    }
+
+    /// Serve RapiDoc at `path` with a custom config. If the config has no `spec_urls`, the
+    /// served specification is shown.
+    #[cfg(feature = "rapidoc")]
+    #[must_use]
+    pub fn with_rapidoc_config(
+        mut self,
+        path: impl Into<String>,
+        mut config: RapiDocConfig,
+    ) -> Self {
+        if config.general.spec_urls.is_empty() {
+            config.general.spec_urls = vec![UrlObject::new("General", &self.settings.json_path)];
+        }
+        if config.doc_theme.is_none() {
+            config.doc_theme = self.settings.doc_theme.clone();
+        }
+        self.rapidoc.push((path.into(), config));
+        self
+    }
+
+    /// Protect the routes of a UI with the `docs_auth` of the settings.
+    #[cfg(any(feature = "swagger", feature = "rapidoc"))]
+    fn ui_routes(&self, routes: impl Into<Vec<Route>>) -> Vec<Route> {
+        match &self.settings.docs_auth {
+            Some(auth) => auth.protect(routes),
+            None => routes.into(),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for OpenApiFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenAPI documentation",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, mut rocket: Rocket<Build>) -> fairing::Result {
+        let mut spec = OpenApi::new();
+        if let Some((_, _, first)) = self.endpoints.first() {
+            // Also merge specs of other OpenAPI versions.
+            spec.openapi = first.openapi.clone();
+        }
+        for (path, routes, endpoint_spec) in &self.endpoints {
+            rocket = rocket.mount(path.as_str(), routes.clone());
+            if let Err(err) = okapi::merge::merge_specs(&mut spec, path, endpoint_spec) {
+                log::error!("Could not merge OpenAPI spec: {}", err);
+                return Err(rocket);
+            }
+        }
+        let settings = match self.settings.to_settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                log::error!("Could not serve the OpenAPI spec: {}", err);
+                return Err(rocket);
+            }
+        };
+        rocket = rocket.mount("/", crate::get_openapi_routes(spec, &settings));
+        #[cfg(feature = "swagger")]
+        for (path, config) in &self.swagger_ui {
+            rocket = rocket.mount(path.as_str(), self.ui_routes(make_swagger_ui(config)));
+        }
+        #[cfg(feature = "rapidoc")]
+        for (path, config) in &self.rapidoc {
+            rocket = rocket.mount(path.as_str(), self.ui_routes(make_rapidoc(config)));
+        }
+        Ok(rocket)
+    }
+}
@@ -1,18 +1,155 @@
-use crate::settings::OpenApiSettings;
-use crate::OperationInfo;
-use okapi::openapi3::{Components, OpenApi, Operation, PathItem, RefOr, SecurityScheme};
+use crate::settings::{OpenApiSettings, OpenApiVersion, SharedSettings};
+use crate::util::read_spec_file;
+use crate::{OpenApiError, OperationInfo};
+use okapi::merge::MergePolicy;
+use okapi::openapi3::{
+    Components, Example, Header, MediaType, OpenApi, Operation, Parameter, PathItem, RefOr,
+    RequestBody, Response, SecurityScheme,
+};
 use okapi::{Map, MapEntry};
+use rocket::data::Limits;
 use rocket::http::Method;
 use schemars::gen::SchemaGenerator;
-use schemars::schema::SchemaObject;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
 use schemars::JsonSchema;
+use serde::Deserialize;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// A schema generator that is shared by the generators of several groups of routes (see
+/// [`OpenApiSettings::share_schemas`]), so the schemas of types that are used by several groups
+/// are only generated once.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSchemaGenerator(Rc<RefCell<Option<SchemaGenerator>>>);
+
+impl SharedSchemaGenerator {
+    /// Take the shared generator, or create a new one if it is not available (like for the first
+    /// group of routes).
+    fn take(&self, settings: &OpenApiSettings) -> SchemaGenerator {
+        self.0
+            .borrow_mut()
+            .take()
+            .unwrap_or_else(|| schema_generator(settings))
+    }
+
+    /// Give the generator back, for the next group of routes.
+    fn put_back(&self, schema_generator: SchemaGenerator) {
+        *self.0.borrow_mut() = Some(schema_generator);
+    }
+}
+
+/// Where the definitions of the schema generator end up in the spec.
+const DEFINITIONS_PATH: &str = "#/components/schemas/";
+
+/// Create the schema generator for the settings. The definitions are always added to the
+/// `components.schemas` of the spec, so references must point there, whatever the
+/// `definitions_path` of the settings is.
+fn schema_generator(settings: &OpenApiSettings) -> SchemaGenerator {
+    let mut schema_settings = settings.schema_settings.clone();
+    schema_settings.definitions_path = DEFINITIONS_PATH.to_owned();
+    schema_settings.into_generator()
+}
+
+/// Create the schema generator for the types of `Schemars` 1.0, with the settings of the
+/// `Schemars` 0.8 generator (see [`schema_generator`]) that `Schemars` 1.0 has too: the
+/// definitions are added to `components.schemas`, and `inline_subschemas`, `meta_schema` and
+/// the documentation of `Option<T>` (see
+/// [`OpenApiSettings::option_handling`](crate::settings::OpenApiSettings::option_handling)) are
+/// the same.
+#[cfg(feature = "schemars1")]
+fn schema_generator_v1(settings: &OpenApiSettings) -> okapi::schemars1::SchemaGenerator {
+    use okapi::schemars1::transform::AddNullable;
+
+    let schema_settings = &settings.schema_settings;
+    let mut settings_v1 = okapi::schemars1::generate::SchemaSettings::openapi3();
+    settings_v1.definitions_path = DEFINITIONS_PATH.trim_start_matches('#').into();
+    settings_v1.inline_subschemas = schema_settings.inline_subschemas;
+    settings_v1.meta_schema = schema_settings.meta_schema.clone().map(Into::into);
+    if schema_settings.option_add_null_type {
+        // `Schemars` 1.0 allows `null` with a type array or `anyOf`, like `option_add_null_type`.
+        settings_v1
+            .transforms
+            .retain(|transform| !transform.is::<AddNullable>());
+    } else {
+        let nullable = schema_settings.option_nullable;
+        if !nullable {
+            settings_v1
+                .transforms
+                .retain(|transform| !transform.is::<AddNullable>());
+        }
+        // Before `RemoveRefSiblings`, which moves a nullable reference to an `allOf`, like
+        // `Schemars` 0.8 does.
+        settings_v1
+            .transforms
+            .insert(0, Box::new(OptionsWithoutNullType { nullable }));
+    }
+    settings_v1.into_generator()
+}
+
+/// Removes the `null` type that `Schemars` 1.0 adds to the schemas of options, and makes them
+/// `nullable` (like `Schemars` 0.8 with `option_nullable`) if `nullable` is set.
+#[cfg(feature = "schemars1")]
+#[derive(Debug, Clone)]
+struct OptionsWithoutNullType {
+    nullable: bool,
+}
+
+#[cfg(feature = "schemars1")]
+impl okapi::schemars1::transform::Transform for OptionsWithoutNullType {
+    fn transform(&mut self, schema: &mut okapi::schemars1::Schema) {
+        use serde_json::Value;
+
+        okapi::schemars1::transform::transform_subschemas(self, schema);
+        let object = match schema.as_object_mut() {
+            Some(object) => object,
+            None => return,
+        };
+        let mut removed_null = false;
+        // `"type": ["string", "null"]`
+        if let Some(Value::Array(types)) = object.get_mut("type") {
+            if types.len() > 1 && types.contains(&"null".into()) {
+                types.retain(|instance_type| instance_type != "null");
+                if types.len() == 1 {
+                    let instance_type = types.remove(0);
+                    object.insert("type".to_owned(), instance_type);
+                }
+                removed_null = true;
+            }
+        }
+        // `"anyOf": [{ "$ref": "..." }, { "type": "null" }]`
+        let null = serde_json::json!({ "type": "null" });
+        if let Some(Value::Array(any_of)) = object.get("anyOf") {
+            if any_of.len() == 2 && any_of[1] == null {
+                if let Some(Value::Array(mut any_of)) = object.remove("anyOf") {
+                    if let Value::Object(inner) = any_of.remove(0) {
+                        for (key, value) in inner {
+                            object.entry(key).or_insert(value);
+                        }
+                    }
+                }
+                removed_null = true;
+            }
+        }
+        if removed_null && self.nullable {
+            object.insert("nullable".to_owned(), true.into());
+        }
+    }
+}
+
+/// The function that the `#[openapi]` attribute creates for a route, which adds the operation of
+/// the route to the generator. The `String` is the operation id.
+pub type AddOperationFn = fn(&mut OpenApiGenerator, String) -> crate::Result<()>;
 
 /// A struct that visits all `rocket::Route`s, and aggregates information about them.
 #[derive(Debug, Clone)]
 pub struct OpenApiGenerator {
     settings: OpenApiSettings,
     schema_generator: SchemaGenerator,
+    #[cfg(feature = "schemars1")]
+    schema_generator_v1: okapi::schemars1::SchemaGenerator,
+    data_limits: Limits,
     security_schemes: Map<String, SecurityScheme>,
     operations: Map<String, HashMap<Method, Operation>>,
 }
@@ -22,24 +159,133 @@ impl OpenApiGenerator {
     #[must_use]
     pub fn new(settings: &OpenApiSettings) -> Self {
         OpenApiGenerator {
-            schema_generator: settings.schema_settings.clone().into_generator(),
+            schema_generator: match &settings.shared_schema_generator {
+                Some(shared) => shared.take(settings),
+                None => schema_generator(settings),
+            },
+            #[cfg(feature = "schemars1")]
+            schema_generator_v1: schema_generator_v1(settings),
+            data_limits: settings.data_limits.clone().unwrap_or_else(|| {
+                rocket::Config::figment()
+                    .extract_inner("limits")
+                    .unwrap_or_default()
+            }),
             settings: settings.clone(),
             security_schemes: Map::default(),
             operations: Map::default(),
         }
     }
 
+    /// Obtain the `OpenApiSettings` this generator was created with.
+    #[must_use]
+    pub fn settings(&self) -> &OpenApiSettings {
+        &self.settings
+    }
+
+    /// Obtain the data `Limits` used to document the size of request bodies.
+    #[must_use]
+    pub fn data_limits(&self) -> &Limits {
+        &self.data_limits
+    }
+
     /// Adds/Replace a security scheme to the generated output
     pub fn add_security_scheme(&mut self, name: String, scheme: SecurityScheme) {
         self.security_schemes.insert(name, scheme);
     }
 
+    /// Whether the route is left out of the spec, because the `skip_route` of the settings returns
+    /// `true` for it. This is checked by the functions of `#[openapi]` before the operation is
+    /// generated.
+    #[must_use]
+    pub fn skips_route(&self, route: &crate::RouteInfo<'_>) -> bool {
+        self.settings
+            .skip_route
+            .is_some_and(|skip_route| skip_route(route))
+    }
+
+    /// Reference the parameter `name` of the settings (see
+    /// [`OpenApiSettings::register_parameter`]). This is used by `#[openapi(param_ref = "...")]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no parameter with this name is registered.
+    pub fn parameter_ref(&self, name: &str) -> crate::Result<RefOr<Parameter>> {
+        component_ref(
+            &self.settings.parameters,
+            "parameters",
+            "register_parameter",
+            name,
+        )
+    }
+
+    /// Reference the response `name` of the settings (see
+    /// [`OpenApiSettings::register_response`]). This is used by
+    /// `#[openapi(response_ref(...))]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no response with this name is registered.
+    pub fn response_ref(&self, name: &str) -> crate::Result<RefOr<Response>> {
+        component_ref(
+            &self.settings.responses,
+            "responses",
+            "register_response",
+            name,
+        )
+    }
+
+    /// Reference the request body `name` of the settings (see
+    /// [`OpenApiSettings::register_request_body`]). This is used by
+    /// `#[openapi(request_body_ref = "...")]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no request body with this name is registered.
+    pub fn request_body_ref(&self, name: &str) -> crate::Result<RefOr<RequestBody>> {
+        component_ref(
+            &self.settings.request_bodies,
+            "requestBodies",
+            "register_request_body",
+            name,
+        )
+    }
+
+    /// Reference the header `name` of the settings (see
+    /// [`OpenApiSettings::register_header`]), for the `headers` of a response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no header with this name is registered.
+    pub fn header_ref(&self, name: &str) -> crate::Result<RefOr<Header>> {
+        component_ref(&self.settings.headers, "headers", "register_header", name)
+    }
+
+    /// Reference the example `name` of the settings (see
+    /// [`OpenApiSettings::register_example`]), for the `examples` of a media type or parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no example with this name is registered.
+    pub fn example_ref(&self, name: &str) -> crate::Result<RefOr<Example>> {
+        component_ref(
+            &self.settings.examples,
+            "examples",
+            "register_example",
+            name,
+        )
+    }
+
     /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
     pub fn add_operation(&mut self, mut op: OperationInfo) {
         if let Some(op_id) = op.operation.operation_id {
             // TODO do this outside add_operation
             op.operation.operation_id = Some(op_id.trim_start_matches(':').replace("::", "_"));
         }
+        if self.settings.annotate_data_limits {
+            if let Some(RefOr::Object(body)) = &mut op.operation.request_body {
+                self.annotate_data_limits(body);
+            }
+        }
         match self.operations.entry(op.path) {
             MapEntry::Occupied(mut e) => {
                 let map = e.get_mut();
@@ -47,7 +293,7 @@ impl OpenApiGenerator {
                     // This will trow a warning if 2 routes have the same path and method
                     // This is allowed by Rocket when a ranking is given for example: `#[get("/user", rank = 2)]`
                     // See: https://rocket.rs/v0.4/guide/requests/#forwarding
-                    println!("Warning: Operation replaced for {}:{}", op.method, e.key());
+                    log::warn!("Operation replaced for {}:{}", op.method, e.key());
                 }
             }
             MapEntry::Vacant(e) => {
@@ -58,9 +304,326 @@ impl OpenApiGenerator {
         };
     }
 
+    /// Add the operation of a route that is not documented with `#[openapi]`, like a route that
+    /// is created with `rocket::Route::new`. The `path` can use the syntax of Rocket
+    /// (`/users/<id>`) or of OpenAPI (`/users/{id}`). Like the routes of `#[openapi]`, the
+    /// operation is left out if the `skip_route` of the settings returns `true` for it.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::Operation;
+    /// use rocket::http::Method;
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    /// gen.add_manual_operation(
+    ///     Method::Get,
+    ///     "/files/<path..>",
+    ///     Operation {
+    ///         operation_id: Some("get_file".to_owned()),
+    ///         ..Operation::default()
+    ///     },
+    /// );
+    /// assert!(gen.into_openapi().paths.contains_key("/files/{path}"));
+    /// ```
+    pub fn add_manual_operation(&mut self, method: Method, path: &str, operation: Operation) {
+        let path = path.replace('<', "{").replace("..>", "}").replace('>', "}");
+        let route = crate::RouteInfo {
+            path: &path,
+            method,
+            operation_id: operation.operation_id.as_deref().unwrap_or_default(),
+            tags: &operation.tags,
+        };
+        if self.skips_route(&route) {
+            return;
+        }
+        self.add_operation(OperationInfo {
+            path,
+            method,
+            operation,
+        });
+    }
+
+    /// Add the operations of routes, given as the name of the route (for error messages), the
+    /// operation id and the function that adds the operation.
+    ///
+    /// With [`OpenApiSettings::generation_threads`], the routes are split into one group per
+    /// thread. Each thread generates the operations of its routes with its own generator, which
+    /// are then merged into this generator in the order of the routes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation of a route can not be generated.
+    pub fn add_operations(&mut self, operations: Vec<(&str, String, AddOperationFn)>) {
+        let threads = match self.settings.generation_threads {
+            0 => std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            threads => threads,
+        };
+        if threads <= 1 || operations.len() <= 1 {
+            for (name, op_id, add_operation) in operations {
+                add_operation_or_panic(self, name, op_id, add_operation);
+            }
+            return;
+        }
+        let mut settings = self.settings.clone();
+        // Don't read the Rocket config again in every thread.
+        settings.data_limits = Some(self.data_limits.clone());
+        let settings = SharedSettings::new(&settings);
+        if let Err(err) = settings.to_settings() {
+            log::warn!("Generating the operations on one thread: {}", err);
+            for (name, op_id, add_operation) in operations {
+                add_operation_or_panic(self, name, op_id, add_operation);
+            }
+            return;
+        }
+        let chunk_size = operations.len().div_ceil(threads);
+        let parts: Vec<GeneratedParts> = std::thread::scope(|scope| {
+            let handles: Vec<_> = operations
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let settings = &settings;
+                    scope.spawn(move || {
+                        let settings = settings
+                            .to_settings()
+                            .expect("The schema visitors were checked before.");
+                        let mut gen = OpenApiGenerator::new(&settings);
+                        for (name, op_id, add_operation) in chunk {
+                            add_operation_or_panic(&mut gen, name, op_id.clone(), *add_operation);
+                        }
+                        gen.into_parts()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+        for part in parts {
+            self.merge_parts(part);
+        }
+    }
+
+    /// Take everything that was generated, to merge it into another generator.
+    fn into_parts(mut self) -> GeneratedParts {
+        GeneratedParts {
+            operations: self.operations,
+            security_schemes: self.security_schemes,
+            definitions: self.schema_generator.take_definitions(),
+            // The transforms are applied by the generator the definitions are merged into.
+            #[cfg(feature = "schemars1")]
+            definitions_v1: self.schema_generator_v1.take_definitions(false),
+        }
+    }
+
+    fn merge_parts(&mut self, parts: GeneratedParts) {
+        for (path, operations) in parts.operations {
+            let map = self.operations.entry(path.clone()).or_default();
+            for (method, operation) in operations {
+                if map.insert(method, operation).is_some() {
+                    log::warn!("Operation replaced for {}:{}", method, path);
+                }
+            }
+        }
+        self.security_schemes.extend(parts.security_schemes);
+        let definitions = self.schema_generator.definitions_mut();
+        for (name, schema) in parts.definitions {
+            definitions.entry(name).or_insert(schema);
+        }
+        #[cfg(feature = "schemars1")]
+        {
+            let definitions = self.schema_generator_v1.definitions_mut();
+            for (name, schema) in parts.definitions_v1 {
+                definitions.entry(name).or_insert(schema);
+            }
+        }
+    }
+
+    /// Add the `x-size-limit` extension to all content of the request body.
+    fn annotate_data_limits(&self, body: &mut RequestBody) {
+        for (media_type, content) in body.content.iter_mut() {
+            let limit = self.data_limits.get(data_limit_name(media_type, content));
+            if let Some(limit) = limit {
+                content
+                    .extensions
+                    .insert("x-size-limit".to_owned(), limit.as_u64().into());
+            }
+        }
+    }
+
     /// Returns a JSON Schema object for the type `T`.
+    ///
+    /// Recursive types (directly or through other types) are always referenced with a `$ref` to
+    /// their definition in `components.schemas`, also when the `inline_subschemas` setting is
+    /// used. Inlining them would never end.
+    ///
+    /// ```rust
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Tree {
+    ///     children: Vec<Tree>,
+    /// }
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.schema_settings.inline_subschemas = true;
+    /// let mut gen = OpenApiGenerator::new(&settings);
+    /// let schema = gen.json_schema::<Tree>();
+    /// assert_eq!(schema.reference.as_deref(), Some("#/components/schemas/Tree"));
+    /// let spec = gen.into_openapi();
+    /// assert!(spec.components.unwrap().schemas.contains_key("Tree"));
+    /// ```
+    ///
+    /// The references of mutually recursive types always point to a definition:
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{MediaType, Operation, PathItem, RefOr, Response};
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Post {
+    ///     comments: Vec<Comment>,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Comment {
+    ///     post: Box<Post>,
+    ///     reply: Option<Box<Comment>>,
+    /// }
+    ///
+    /// for inline_subschemas in [false, true] {
+    ///     let mut settings = OpenApiSettings::new();
+    ///     settings.schema_settings.inline_subschemas = inline_subschemas;
+    ///     settings.schema_settings.definitions_path = "#/definitions/".to_owned();
+    ///     let mut gen = OpenApiGenerator::new(&settings);
+    ///     let mut response = Response::default();
+    ///     response.content.insert(
+    ///         "application/json".to_owned(),
+    ///         MediaType {
+    ///             schema: Some(gen.json_schema::<Comment>()),
+    ///             ..MediaType::default()
+    ///         },
+    ///     );
+    ///     let mut operation = Operation {
+    ///         operation_id: Some("get_comment".to_owned()),
+    ///         ..Operation::default()
+    ///     };
+    ///     operation.responses.responses.insert("200".to_owned(), RefOr::Object(response));
+    ///
+    ///     let mut spec = gen.into_openapi();
+    ///     spec.paths.insert(
+    ///         "/comment".to_owned(),
+    ///         PathItem {
+    ///             get: Some(operation),
+    ///             ..PathItem::default()
+    ///         },
+    ///     );
+    ///     assert_eq!(spec.validate(), Ok(()));
+    /// }
+    /// ```
+    ///
+    /// The same holds for optional recursion, recursion through collections and longer cycles:
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{MediaType, OpenApi, Operation, PathItem, RefOr, Response};
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use schemars::JsonSchema;
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Node {
+    ///     value: u32,
+    ///     next: Option<Box<Node>>,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Directory {
+    ///     children: Vec<Directory>,
+    ///     links: HashMap<String, Directory>,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Country {
+    ///     regions: Vec<Region>,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct Region {
+    ///     cities: Vec<City>,
+    /// }
+    ///
+    /// #[derive(JsonSchema)]
+    /// struct City {
+    ///     country: Option<Box<Country>>,
+    /// }
+    ///
+    /// /// A document with one operation that returns `T`.
+    /// fn spec_for<T: JsonSchema>(inline_subschemas: bool) -> OpenApi {
+    ///     let mut settings = OpenApiSettings::new();
+    ///     settings.schema_settings.inline_subschemas = inline_subschemas;
+    ///     let mut gen = OpenApiGenerator::new(&settings);
+    ///     let mut response = Response::default();
+    ///     response.content.insert(
+    ///         "application/json".to_owned(),
+    ///         MediaType {
+    ///             schema: Some(gen.json_schema::<T>()),
+    ///             ..MediaType::default()
+    ///         },
+    ///     );
+    ///     let mut operation = Operation {
+    ///         operation_id: Some("get".to_owned()),
+    ///         ..Operation::default()
+    ///     };
+    ///     operation.responses.responses.insert("200".to_owned(), RefOr::Object(response));
+    ///     let mut spec = gen.into_openapi();
+    ///     spec.paths.insert(
+    ///         "/".to_owned(),
+    ///         PathItem {
+    ///             get: Some(operation),
+    ///             ..PathItem::default()
+    ///         },
+    ///     );
+    ///     spec
+    /// }
+    ///
+    /// for inline_subschemas in [false, true] {
+    ///     let specs = [
+    ///         ("Node", spec_for::<Node>(inline_subschemas)),
+    ///         ("Directory", spec_for::<Directory>(inline_subschemas)),
+    ///         ("Country", spec_for::<Country>(inline_subschemas)),
+    ///     ];
+    ///     for (name, spec) in specs {
+    ///         assert_eq!(spec.validate(), Ok(()), "{} (inlined: {})", name, inline_subschemas);
+    ///         assert!(spec.components.unwrap().schemas.contains_key(name));
+    ///     }
+    /// }
+    /// ```
     pub fn json_schema<T: ?Sized + JsonSchema>(&mut self) -> SchemaObject {
-        self.schema_generator.subschema_for::<T>().into()
+        let schema = self.schema_generator.subschema_for::<T>();
+        if !self.schema_generator.settings().inline_subschemas || !T::is_referenceable() {
+            return schema.into();
+        }
+        // When inlining, only recursive types get a definition (for their references to
+        // themselves), which is the same as the inlined schema.
+        let definition = self
+            .schema_generator
+            .definitions()
+            .iter()
+            .find(|(_, definition)| **definition == schema)
+            .map(|(name, _)| name);
+        match definition {
+            Some(name) => SchemaObject::new_ref(format!("{}{}", DEFINITIONS_PATH, name)),
+            None => schema.into(),
+        }
     }
 
     /// Obtain the internal `SchemaGenerator` object.
@@ -74,11 +637,112 @@ impl OpenApiGenerator {
         <T>::json_schema(&mut self.schema_generator).into()
     }
 
+    /// Returns a JSON Schema object for the type `T` that implements the `JsonSchema` trait
+    /// of `Schemars` 1.0.
+    ///
+    /// The definitions used by `T` are added to the `components.schemas` of the generated spec,
+    /// just like the ones from [`json_schema`](Self::json_schema). The schemas have the same shape
+    /// as the ones of `Schemars` 0.8:
+    ///
+    /// ```rust
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::{OpenApiSettings, OptionHandling};
+    ///
+    /// mod v0_8 {
+    ///     #[derive(rocket_okapi::JsonSchema)]
+    ///     pub struct Pet {
+    ///         pub name: Option<String>,
+    ///         pub owner: Option<Owner>,
+    ///     }
+    ///
+    ///     #[derive(rocket_okapi::JsonSchema)]
+    ///     pub struct Owner {
+    ///         pub name: String,
+    ///     }
+    /// }
+    ///
+    /// mod v1 {
+    ///     use rocket_okapi::okapi::schemars1;
+    ///
+    ///     #[derive(schemars1::JsonSchema)]
+    ///     #[schemars(crate = "schemars1")]
+    ///     pub struct Pet {
+    ///         pub name: Option<String>,
+    ///         pub owner: Option<Owner>,
+    ///     }
+    ///
+    ///     #[derive(schemars1::JsonSchema)]
+    ///     #[schemars(crate = "schemars1")]
+    ///     pub struct Owner {
+    ///         pub name: String,
+    ///     }
+    /// }
+    ///
+    /// for option_handling in [
+    ///     OptionHandling::Nullable,
+    ///     OptionHandling::NullType,
+    ///     OptionHandling::NotRequired,
+    /// ] {
+    ///     for inline in [false, true] {
+    ///         let mut settings = OpenApiSettings::new();
+    ///         settings.option_handling(option_handling);
+    ///         settings.inline_subschemas(inline);
+    ///         let mut gen = OpenApiGenerator::new(&settings);
+    ///         let schema = gen.json_schema::<v0_8::Pet>();
+    ///         let mut gen_v1 = OpenApiGenerator::new(&settings);
+    ///         let schema_v1 = gen_v1.json_schema_v1::<v1::Pet>().unwrap();
+    ///         assert_eq!(schema, schema_v1);
+    ///         assert_eq!(
+    ///             gen.into_openapi().components.unwrap().schemas,
+    ///             gen_v1.into_openapi().components.unwrap().schemas,
+    ///             "{:?}",
+    ///             option_handling
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "schemars1")]
+    pub fn json_schema_v1<T: ?Sized + okapi::schemars1::JsonSchema>(
+        &mut self,
+    ) -> crate::Result<SchemaObject> {
+        let mut schema = self.schema_generator_v1.subschema_for::<T>();
+        for transform in self.schema_generator_v1.transforms_mut() {
+            transform.transform(&mut schema);
+        }
+        okapi::schemars_compat::from_schemars1(schema).map_err(|err| {
+            crate::OpenApiError::new(format!(
+                "Could not convert Schemars 1.0 schema for `{}`: {}",
+                std::any::type_name::<T>(),
+                err
+            ))
+        })
+    }
+
     /// Generate an `OpenApi` specification for all added operations.
+    ///
+    /// The `merge_spec_files` of the settings that can not be read or merged are logged as errors
+    /// and left out.
     #[must_use]
     pub fn into_openapi(self) -> OpenApi {
         let mut schema_generator = self.schema_generator;
-        let mut schemas = schema_generator.take_definitions();
+        let mut schemas = match self.settings.shared_schema_generator {
+            // Keep the definitions, so the next group of routes does not generate them again.
+            Some(_) => schema_generator.definitions().clone(),
+            None => schema_generator.take_definitions(),
+        };
+        #[cfg(feature = "schemars1")]
+        {
+            let mut schema_generator_v1 = self.schema_generator_v1;
+            let definitions = schema_generator_v1.take_definitions(true);
+            match okapi::schemars_compat::definitions_from_schemars1(definitions) {
+                Ok(definitions) => {
+                    for (name, schema) in definitions {
+                        schemas.entry(name).or_insert_with(|| schema.into());
+                    }
+                }
+                Err(err) => log::error!("Could not convert Schemars 1.0 definitions: {}", err),
+            }
+        }
 
         // Add the security schemes
         let mut schemes: Map<String, RefOr<SecurityScheme>> = Default::default();
@@ -91,9 +755,12 @@ impl OpenApiGenerator {
                 visitor.visit_schema(schema)
             }
         }
+        if let Some(shared) = &self.settings.shared_schema_generator {
+            shared.put_back(schema_generator);
+        }
 
-        OpenApi {
-            openapi: "3.0.0".to_owned(),
+        let mut spec = OpenApi {
+            openapi: self.settings.openapi_version.as_str().to_owned(),
             paths: {
                 let mut paths = Map::new();
                 for (path, map) in self.operations {
@@ -107,10 +774,192 @@ impl OpenApiGenerator {
             components: Some(Components {
                 schemas: schemas.into_iter().map(|(k, v)| (k, v.into())).collect(),
                 security_schemes: schemes,
+                parameters: self
+                    .settings
+                    .parameters
+                    .iter()
+                    .map(|(name, parameter)| (name.clone(), parameter.clone().into()))
+                    .collect(),
+                responses: self
+                    .settings
+                    .responses
+                    .iter()
+                    .map(|(name, response)| (name.clone(), response.clone().into()))
+                    .collect(),
+                request_bodies: self
+                    .settings
+                    .request_bodies
+                    .iter()
+                    .map(|(name, body)| (name.clone(), body.clone().into()))
+                    .collect(),
+                headers: self
+                    .settings
+                    .headers
+                    .iter()
+                    .map(|(name, header)| (name.clone(), header.clone().into()))
+                    .collect(),
+                examples: self
+                    .settings
+                    .examples
+                    .iter()
+                    .map(|(name, example)| (name.clone(), example.clone().into()))
+                    .collect(),
                 ..Default::default()
             }),
+            external_docs: self.settings.external_docs.clone(),
             ..OpenApi::default()
+        };
+        spec.convert_enums(&self.settings.enum_representation);
+        spec.convert_integers(&self.settings.integer_representation);
+        if self.settings.discriminators {
+            spec.add_discriminators();
+        }
+        if self.settings.strict_schemas {
+            spec.close_object_schemas();
         }
+        if self.settings.default_examples {
+            okapi::example::add_default_examples(&mut spec);
+        }
+        let mut spec = match self.settings.openapi_version {
+            OpenApiVersion::V3_0 => spec,
+            OpenApiVersion::V3_1 => okapi::convert::to_v31(spec),
+        };
+        if self.settings.option_any_of_null {
+            spec = okapi::convert::null_types_to_any_of(spec);
+        }
+        // With `reload_spec_files`, the spec files are merged by the `openapi.json` route instead.
+        if !self.settings.reloads_spec_files() {
+            for path in &self.settings.merge_spec_files {
+                if let Err(err) = merge_spec_file(&mut spec, path) {
+                    log::error!(
+                        "Could not merge `{}` into the spec: {}",
+                        path.display(),
+                        err
+                    );
+                }
+            }
+            if self.settings.code_samples {
+                okapi::export::add_code_samples(&mut spec);
+            }
+        }
+        if self.settings.sort_spec {
+            spec.sort();
+        }
+        spec
+    }
+}
+
+/// Reference the component `name` of the `kind` (like `parameters`), which must be registered in
+/// the settings with `register_fn`.
+fn component_ref<T, U>(
+    components: &Map<String, U>,
+    kind: &str,
+    register_fn: &str,
+    name: &str,
+) -> crate::Result<RefOr<T>> {
+    if !components.contains_key(name) {
+        return Err(OpenApiError::new(format!(
+            "`{}` is not registered with `OpenApiSettings::{}`.",
+            name, register_fn
+        )));
+    }
+    Ok(RefOr::Ref(okapi::openapi3::Ref {
+        reference: format!("#/components/{}/{}", kind, name),
+    }))
+}
+
+/// Everything a generator creates for the operations of a group of routes, see
+/// [`OpenApiGenerator::add_operations`].
+struct GeneratedParts {
+    operations: Map<String, HashMap<Method, Operation>>,
+    security_schemes: Map<String, SecurityScheme>,
+    definitions: schemars::Map<String, Schema>,
+    #[cfg(feature = "schemars1")]
+    definitions_v1: serde_json::Map<String, serde_json::Value>,
+}
+
+fn add_operation_or_panic(
+    gen: &mut OpenApiGenerator,
+    name: &str,
+    op_id: String,
+    add_operation: AddOperationFn,
+) {
+    if let Err(err) = add_operation(gen, op_id) {
+        panic!(
+            "Could not generate OpenAPI operation for `{}`: {}",
+            name, err
+        );
+    }
+}
+
+/// Merge the files at `paths` into the spec (see [`OpenApiSettings::merge_spec_file`]), then add
+/// the code samples if `code_samples` is set.
+pub(crate) fn merge_spec_files(
+    spec: &mut OpenApi,
+    paths: &[PathBuf],
+    code_samples: bool,
+) -> crate::Result<()> {
+    for path in paths {
+        merge_spec_file(spec, path).map_err(|err| {
+            OpenApiError::new(format!(
+                "Could not merge `{}` into the spec: {}",
+                path.display(),
+                err
+            ))
+        })?;
+    }
+    if code_samples {
+        okapi::export::add_code_samples(spec);
+    }
+    Ok(())
+}
+
+/// The parts of an OpenAPI document that are merged by [`OpenApiSettings::merge_spec_file`].
+#[derive(Debug, Deserialize)]
+struct SpecFragment {
+    #[serde(default)]
+    paths: Map<String, PathItem>,
+    #[serde(default)]
+    components: Option<Components>,
+}
+
+/// Merge the file at `path` into the spec. If it can not be merged, the spec is left unchanged.
+fn merge_spec_file(spec: &mut OpenApi, path: &Path) -> crate::Result<()> {
+    let fragment: SpecFragment = read_spec_file(path)?;
+    let other = OpenApi {
+        openapi: spec.openapi.clone(),
+        info: spec.info.clone(),
+        paths: fragment.paths,
+        components: fragment.components,
+        ..OpenApi::default()
+    };
+    // A failed merge can already have added some of the paths.
+    let mut merged = spec.clone();
+    merged.merge(&other, MergePolicy::Reject)?;
+    *spec = merged;
+    Ok(())
+}
+
+/// Get the name of the Rocket data limit that applies to a body with the given media type.
+fn data_limit_name(media_type: &str, content: &MediaType) -> &'static str {
+    let media_type = media_type.split(';').next().unwrap_or_default().trim();
+    match media_type {
+        "application/json" => "json",
+        "application/msgpack" => "msgpack",
+        "application/x-www-form-urlencoded" => "form",
+        "multipart/form-data" => "data-form",
+        _ if media_type.ends_with("+json") => "json",
+        _ if media_type.starts_with("text/") => "string",
+        _ => match &content.schema {
+            Some(schema)
+                if schema.has_type(InstanceType::String)
+                    && schema.format.as_deref() == Some("binary") =>
+            {
+                "file"
+            }
+            Some(schema) if schema.has_type(InstanceType::String) => "string",
+            _ => "bytes",
+        },
     }
 }
 
@@ -0,0 +1,55 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The request of a GraphQL endpoint, as sent by GraphQL clients over HTTP (and parsed by
+/// `juniper_rocket` and `async-graphql-rocket`).
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRequest {
+    /// The GraphQL document, with the query or mutation.
+    pub query: String,
+    /// The operation of the document to run, if it has several operations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+    /// The values of the variables of the operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Map<String, Value>>,
+}
+
+/// The response of a GraphQL endpoint.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+pub struct GraphQLResponse {
+    /// The result of the operation, in the shape of the query. `null` if the request failed.
+    #[serde(default)]
+    pub data: Option<Value>,
+    /// The errors of the operation, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<GraphQLError>,
+}
+
+/// An error of a [`GraphQLResponse`].
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize, JsonSchema)]
+pub struct GraphQLError {
+    /// The description of the error.
+    pub message: String,
+    /// The locations in the GraphQL document that caused the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<GraphQLLocation>,
+    /// The path of the field in `data` that caused the error, with field names and list
+    /// indexes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<Value>,
+    /// Additional information of the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Map<String, Value>>,
+}
+
+/// A location in a GraphQL document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, JsonSchema)]
+pub struct GraphQLLocation {
+    /// The line, starting at `1`.
+    pub line: u32,
+    /// The column, starting at `1`.
+    pub column: u32,
+}
@@ -1,47 +1,553 @@
 use okapi::openapi3::{OpenApi, Server};
-use rocket::http::{ContentType, Method};
+use rocket::futures::stream::{self, Stream};
+use rocket::http::uri::{Authority, Origin};
+use rocket::http::{ContentType, Header, Method, Status};
 use rocket::response::content::Custom;
+use rocket::response::stream::ByteStream;
+use rocket::response::Responder;
 use rocket::route::{Handler, Outcome};
+use rocket::tokio::sync::mpsc;
+use rocket::tokio::task::spawn_blocking;
 use rocket::{Data, Request, Route};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The name of the routes created by [`OpenApiHandler::into_route`], used to find them again.
+pub(crate) const ROUTE_NAME: &str = "rocket_okapi_openapi_json";
+/// The name of the routes created by [`OpenApiHandler::into_route`] for slim handlers.
+const SLIM_ROUTE_NAME: &str = "rocket_okapi_openapi_json_slim";
+/// The name of the routes created by [`OpenApiHandler::into_route`] for handlers that serve the
+/// spec of a tag.
+const TAG_ROUTE_NAME: &str = "rocket_okapi_openapi_json_tag";
 
 /// A handler type that is used to serve the `openapi.json` files.
 #[derive(Clone)]
 pub struct OpenApiHandler {
     spec: OpenApi,
+    cors_allowed_origins: Vec<String>,
+    /// Creates the spec for every request, instead of `spec`.
+    reload: Option<Arc<ReloadFn>>,
+    /// Serve the spec without descriptions and examples, as compact JSON.
+    slim: bool,
+    /// Serialize the spec as pretty-printed JSON, instead of compact JSON.
+    pretty: bool,
+    /// Serve the spec of the tag in the last path segment, instead of the whole spec.
+    by_tag: bool,
+    /// Write the JSON to the response in chunks while it is serialized.
+    stream: bool,
+    /// The translations, served for `?lang=<locale>`.
+    translations: Vec<Translation>,
+    /// Add the server the request was sent to as the first server of the spec.
+    request_server: bool,
+    /// Replace the servers of the spec by the relative URL of the route.
+    relative_server: bool,
+    /// Use the `X-Forwarded-*` headers of the request for its server.
+    trust_forwarded_headers: bool,
+}
+
+type ReloadFn = dyn Fn() -> crate::Result<OpenApi> + Send + Sync;
+
+/// A translation of the spec, served for `?lang=<locale>`.
+#[derive(Clone)]
+struct Translation {
+    locale: String,
+    /// The JSON Merge Patch with the translated texts.
+    path: PathBuf,
+    /// The spec with the patch applied, `None` if the spec is reloaded for every request.
+    spec: Option<Arc<crate::Result<OpenApi>>>,
 }
 
 impl OpenApiHandler {
     /// Create a new handler from an API spec.
     #[must_use]
     pub fn new(spec: OpenApi) -> Self {
-        OpenApiHandler { spec }
+        OpenApiHandler {
+            spec,
+            cors_allowed_origins: Vec::new(),
+            reload: None,
+            slim: false,
+            pretty: false,
+            by_tag: false,
+            stream: false,
+            translations: Vec::new(),
+            request_server: false,
+            relative_server: false,
+            trust_forwarded_headers: false,
+        }
+    }
+
+    /// Serve the spec created by `reload` for every request, so changes to spec files are
+    /// served without restarting the application.
+    pub(crate) fn reload_with(
+        mut self,
+        reload: impl Fn() -> crate::Result<OpenApi> + Send + Sync + 'static,
+    ) -> Self {
+        self.reload = Some(Arc::new(reload));
+        // The translations are applied to the reloaded spec instead.
+        for translation in &mut self.translations {
+            translation.spec = None;
+        }
+        self
+    }
+
+    /// Allow requests from other origins (like `https://example.readme.io`, or `*` for all
+    /// origins) with CORS headers, so the spec can be fetched by documentation hosted elsewhere.
+    #[must_use]
+    pub fn cors_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.cors_allowed_origins = origins;
+        self
+    }
+
+    /// Serve a size-reduced spec for machine consumers: without descriptions and examples (see
+    /// [`OpenApi::strip_docs`]), and as compact JSON.
+    #[must_use]
+    pub fn slim(mut self) -> Self {
+        if self.reload.is_none() {
+            self.spec.strip_docs();
+        }
+        // Slim handlers are not translated.
+        self.translations.clear();
+        self.slim = true;
+        self.pretty = false;
+        self
+    }
+
+    /// Serialize the spec as pretty-printed JSON, which is easier to read but larger. The
+    /// default is compact JSON.
+    #[must_use]
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Write the JSON to the response body in chunks while the spec is serialized (on a blocking
+    /// thread), instead of serializing the whole spec into a `String` first. This avoids keeping
+    /// the whole serialized spec in memory for very large specs, but the response has no
+    /// `Content-Length`.
+    #[must_use]
+    pub fn stream(mut self, stream: bool) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Serve a translation of the spec for requests with the query parameter `lang=<locale>`:
+    /// the spec with the JSON Merge Patch of the locale applied (see
+    /// [`OpenApiSettings::translation_file`](crate::settings::OpenApiSettings::translation_file)).
+    /// The patches are read and applied once, when this is called (a patch that can not be
+    /// applied is logged, and its requests get a `500 Internal Server Error`). The response has
+    /// a `Content-Language` header. Slim handlers ignore the translations.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    /// use rocket::local::blocking::Client;
+    /// use rocket_okapi::handlers::OpenApiHandler;
+    /// use serde_json::Value;
+    ///
+    /// let path = std::env::temp_dir().join("rocket-okapi-translation-de.json");
+    /// std::fs::write(&path, r#"{"info": {"title": "Die API"}}"#).unwrap();
+    /// let handler =
+    ///     OpenApiHandler::new(OpenApi::new()).translations(vec![("de".to_owned(), path.clone())]);
+    /// std::fs::remove_file(&path).unwrap();
+    ///
+    /// let rocket = rocket::build().mount("/", vec![handler.into_route("/openapi.json")]);
+    /// let client = Client::tracked(rocket).unwrap();
+    /// let response = client.get("/openapi.json?lang=de").dispatch();
+    /// assert_eq!(response.headers().get_one("Content-Language"), Some("de"));
+    /// assert_eq!(response.into_json::<Value>().unwrap()["info"]["title"], "Die API");
+    /// ```
+    #[must_use]
+    pub fn translations(mut self, translations: Vec<(String, PathBuf)>) -> Self {
+        let translations = translations
+            .into_iter()
+            .map(|(locale, path)| {
+                let spec = self.reload.is_none().then(|| {
+                    let spec = translate(self.spec.clone(), &locale, &path);
+                    if let Err(err) = &spec {
+                        log::error!("{}", err);
+                    }
+                    Arc::new(spec)
+                });
+                Translation { locale, path, spec }
+            })
+            .collect();
+        self.translations = translations;
+        self
+    }
+
+    /// Get the error of the first translation that could not be applied.
+    pub(crate) fn translation_error(&self) -> Option<&crate::OpenApiError> {
+        self.translations
+            .iter()
+            .filter_map(|translation| translation.spec.as_deref())
+            .find_map(|spec| spec.as_ref().err())
+    }
+
+    /// Add the server the request was sent to (from its `Host` header, or the `X-Forwarded-Proto`
+    /// and `X-Forwarded-Host` headers with
+    /// [`trust_forwarded_headers`](Self::trust_forwarded_headers), and the base path of the
+    /// route) as the first server of the spec, so "Try it out" uses the hostname the
+    /// documentation was opened with. The default is `false`.
+    #[must_use]
+    pub fn request_server(mut self, request_server: bool) -> Self {
+        self.request_server = request_server;
+        self
+    }
+
+    /// Replace the servers of the spec by a single server with a relative URL: the base path the
+    /// route is mounted at, prefixed with the `X-Forwarded-Prefix` header of the request (which
+    /// reverse proxies set when they strip a prefix, like `/api/v1`) with
+    /// [`trust_forwarded_headers`](Self::trust_forwarded_headers). This takes precedence over
+    /// [`request_server`](Self::request_server). The default is `false`.
+    #[must_use]
+    pub fn relative_server(mut self, relative_server: bool) -> Self {
+        self.relative_server = relative_server;
+        self
+    }
+
+    /// Use the `X-Forwarded-Proto`, `X-Forwarded-Host` and `X-Forwarded-Prefix` headers for the
+    /// servers of [`request_server`](Self::request_server) and
+    /// [`relative_server`](Self::relative_server). Only enable this behind a reverse proxy that
+    /// sets these headers, as clients can send any value. Values that are not a valid scheme
+    /// (`http` or `https`), host or path are ignored. The default is `false`.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    /// use rocket::http::Header;
+    /// use rocket::local::blocking::Client;
+    /// use rocket_okapi::handlers::OpenApiHandler;
+    /// use serde_json::Value;
+    ///
+    /// let handler = OpenApiHandler::new(OpenApi::new()).request_server(true);
+    /// let rocket = rocket::build()
+    ///     .mount("/", vec![handler.clone().into_route("/openapi.json")])
+    ///     .mount(
+    ///         "/proxied",
+    ///         vec![handler.trust_forwarded_headers(true).into_route("/openapi.json")],
+    ///     );
+    /// let client = Client::tracked(rocket).unwrap();
+    /// let server_url = |path: &str| {
+    ///     let response = client
+    ///         .get(path.to_owned())
+    ///         .header(Header::new("Host", "api.example.com"))
+    ///         .header(Header::new("X-Forwarded-Host", "docs.example.com"))
+    ///         .header(Header::new("X-Forwarded-Proto", "javascript"))
+    ///         .dispatch();
+    ///     response.into_json::<Value>().unwrap()["servers"][0]["url"].clone()
+    /// };
+    ///
+    /// assert_eq!(server_url("/openapi.json"), "http://api.example.com");
+    /// assert_eq!(server_url("/proxied/openapi.json"), "http://docs.example.com/proxied");
+    /// ```
+    #[must_use]
+    pub fn trust_forwarded_headers(mut self, trust_forwarded_headers: bool) -> Self {
+        self.trust_forwarded_headers = trust_forwarded_headers;
+        self
+    }
+
+    /// Serve the spec of a single tag (see [`OpenApi::filter_tag`]), instead of the whole spec.
+    /// The tag is taken from the last path segment, which must have the form `{tag}.json`, so
+    /// the path of the route should end with a dynamic segment (like `/openapi/<tag>`).
+    /// Requests for tags that are not used by any operation are forwarded.
+    #[must_use]
+    pub fn by_tag(mut self) -> Self {
+        self.by_tag = true;
+        self
     }
 
     /// Create a new route from this `OpenApiHandler`.
     pub fn into_route(self, path: impl AsRef<str>) -> Route {
-        Route::new(Method::Get, path.as_ref(), self)
+        let name = if self.by_tag {
+            TAG_ROUTE_NAME
+        } else if self.slim {
+            SLIM_ROUTE_NAME
+        } else {
+            ROUTE_NAME
+        };
+        let mut route = Route::new(Method::Get, path.as_ref(), self);
+        route.name = Some(name.into());
+        route
     }
 }
 
 #[rocket::async_trait]
 impl Handler for OpenApiHandler {
-    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
-        let mut spec = self.spec.clone();
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let translation = self.translation(req);
+        let reloaded;
+        let full_spec = match &self.reload {
+            Some(reload) => {
+                // Reloading reads the spec files, so it is done on a blocking thread.
+                let reload = reload.clone();
+                let slim = self.slim;
+                let translation = translation
+                    .map(|translation| (translation.locale.clone(), translation.path.clone()));
+                let spec = spawn_blocking(move || {
+                    let mut spec = reload().map_err(|err| {
+                        crate::OpenApiError::new(format!(
+                            "Could not reload the OpenAPI spec: {}",
+                            err
+                        ))
+                    })?;
+                    if slim {
+                        spec.strip_docs();
+                    }
+                    match translation {
+                        Some((locale, path)) => translate(spec, &locale, &path),
+                        None => Ok(spec),
+                    }
+                })
+                .await
+                .unwrap_or_else(|err| {
+                    Err(crate::OpenApiError::new(format!(
+                        "Could not reload the OpenAPI spec: {}",
+                        err
+                    )))
+                });
+                match spec {
+                    Ok(spec) => {
+                        reloaded = spec;
+                        &reloaded
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        return Outcome::Failure(Status::InternalServerError);
+                    }
+                }
+            }
+            None => match translation.and_then(|translation| translation.spec.as_deref()) {
+                Some(Ok(spec)) => spec,
+                Some(Err(err)) => {
+                    log::error!("{}", err);
+                    return Outcome::Failure(Status::InternalServerError);
+                }
+                None => &self.spec,
+            },
+        };
+        let mut spec = if self.by_tag {
+            let tag = req
+                .routed_segments(0..)
+                .last()
+                .and_then(|segment| segment.strip_suffix(".json"));
+            match tag {
+                Some(tag) if full_spec.operation_tags().iter().any(|t| t == tag) => {
+                    full_spec.filter_tag(tag)
+                }
+                _ => return Outcome::Forward(data),
+            }
+        } else {
+            full_spec.clone()
+        };
         let base_path = req
             .route()
             .expect("Routing should already have occurred.")
             .uri
             .base();
 
-        if spec.servers.is_empty() && base_path != "/" {
+        if self.relative_server {
+            let prefix = self.forwarded_prefix(req).unwrap_or_default();
+            let url = format!("{}{}", prefix.trim_end_matches('/'), base_path);
+            let url = match url.trim_end_matches('/') {
+                "" => "/".to_owned(),
+                url => url.to_owned(),
+            };
+            spec.servers = vec![Server {
+                url,
+                ..Server::default()
+            }];
+        } else if let Some(url) = self.request_server_url(req, base_path) {
+            spec.servers.retain(|server| server.url != url);
+            spec.servers.insert(
+                0,
+                Server {
+                    url,
+                    description: Some("The server of this request.".to_owned()),
+                    ..Server::default()
+                },
+            );
+        } else if spec.servers.is_empty() && base_path != "/" {
             spec.servers.push(Server {
                 url: base_path.to_owned(),
                 ..okapi::openapi3::Server::default()
             })
         }
 
-        let json =
-            serde_json::to_string_pretty(&spec).expect("Could not serialize content as JSON.");
-        Outcome::from(req, Custom(ContentType::JSON, json))
+        let response = if self.stream {
+            ByteStream(stream_json(spec, self.pretty))
+                .respond_to(req)
+                .map(|mut response| {
+                    response.set_header(ContentType::JSON);
+                    response
+                })
+        } else {
+            let json = if self.pretty {
+                serde_json::to_string_pretty(&spec)
+            } else {
+                serde_json::to_string(&spec)
+            }
+            .expect("Could not serialize content as JSON.");
+            Custom(ContentType::JSON, json).respond_to(req)
+        };
+        let mut response = match response {
+            Ok(response) => response,
+            Err(status) => return Outcome::Failure(status),
+        };
+        let any_origin = self.cors_allowed_origins.iter().any(|origin| origin == "*");
+        if !self.cors_allowed_origins.is_empty() && !any_origin {
+            // The response depends on the origin, so caches must not share it.
+            response.set_header(Header::new("Vary", "Origin"));
+        }
+        if let Some(translation) = translation {
+            response.set_header(Header::new("Content-Language", translation.locale.clone()));
+        }
+        if let Some(origin) = self.allowed_origin(req) {
+            response.set_header(Header::new("Access-Control-Allow-Origin", origin));
+            response.set_header(Header::new("Access-Control-Allow-Methods", "GET"));
+        }
+        Outcome::Success(response)
+    }
+}
+
+/// Get the first value of a header. Proxies can append their values to the `X-Forwarded-*`
+/// headers, so the first one is the client's.
+fn first_header<'r>(req: &'r Request<'_>, name: &str) -> Option<&'r str> {
+    req.headers()
+        .get_one(name)
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Apply the translation of `locale` in the file at `path` to the spec.
+fn translate(mut spec: OpenApi, locale: &str, path: &Path) -> crate::Result<OpenApi> {
+    crate::apply_override_files(&mut spec, &[path.to_owned()]).map_err(|err| {
+        crate::OpenApiError::new(format!(
+            "Could not apply the `{}` translation: {}",
+            locale, err
+        ))
+    })?;
+    Ok(spec)
+}
+
+/// Check that a `Host` header is a host with an optional port, without user info.
+fn is_valid_host(host: &str) -> bool {
+    Authority::parse(host)
+        .is_ok_and(|authority| authority.user_info().is_none() && !authority.host().is_empty())
+}
+
+/// Serialize the spec on a blocking thread, and return the chunks of JSON as a stream.
+fn stream_json(spec: OpenApi, pretty: bool) -> impl Stream<Item = Vec<u8>> + Send {
+    // A few chunks are buffered, so serializing does not wait for every chunk to be sent.
+    let (sender, receiver) = mpsc::channel(4);
+    spawn_blocking(move || {
+        let mut writer = ChunkWriter {
+            buffer: Vec::with_capacity(STREAM_CHUNK_SIZE),
+            sender,
+        };
+        let result = if pretty {
+            serde_json::to_writer_pretty(&mut writer, &spec)
+        } else {
+            serde_json::to_writer(&mut writer, &spec)
+        };
+        if let Err(err) = result
+            .map_err(io::Error::from)
+            .and_then(|()| writer.flush())
+        {
+            // The client closed the connection, or the spec can not be serialized.
+            log::warn!("Could not stream the OpenAPI spec: {}", err);
+        }
+    });
+    stream::unfold(receiver, |mut receiver| async move {
+        let chunk = receiver.recv().await?;
+        Some((chunk, receiver))
+    })
+}
+
+/// The size of the chunks in which specs are streamed.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Sends everything that is written in chunks of `STREAM_CHUNK_SIZE`.
+struct ChunkWriter {
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<Vec<u8>>,
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= STREAM_CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(STREAM_CHUNK_SIZE));
+        self.sender
+            .blocking_send(chunk)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "The response was dropped."))
+    }
+}
+
+impl OpenApiHandler {
+    /// Get the translation that is requested with the `lang` query parameter, if there is one.
+    fn translation(&self, req: &Request<'_>) -> Option<&Translation> {
+        if self.slim {
+            return None;
+        }
+        let lang = req.query_value::<&str>("lang")?.ok()?;
+        self.translations
+            .iter()
+            .find(|translation| translation.locale.eq_ignore_ascii_case(lang))
+    }
+
+    /// Get the URL of the server the request was sent to, if `request_server` is set and the
+    /// request has a host.
+    fn request_server_url(&self, req: &Request<'_>, base_path: &str) -> Option<String> {
+        if !self.request_server {
+            return None;
+        }
+        let forwarded = |name| first_header(req, name).filter(|_| self.trust_forwarded_headers);
+        let host = forwarded("X-Forwarded-Host")
+            .filter(|host| is_valid_host(host))
+            .or_else(|| first_header(req, "Host").filter(|host| is_valid_host(host)))?;
+        let proto = forwarded("X-Forwarded-Proto")
+            .map(str::to_ascii_lowercase)
+            .filter(|proto| proto == "http" || proto == "https")
+            .unwrap_or_else(|| "http".to_owned());
+        Some(format!(
+            "{}://{}{}",
+            proto,
+            host,
+            base_path.trim_end_matches('/')
+        ))
+    }
+
+    /// Get the `X-Forwarded-Prefix` of the request, if the forwarded headers are trusted and it is
+    /// an absolute path.
+    fn forwarded_prefix<'r>(&self, req: &'r Request<'_>) -> Option<&'r str> {
+        if !self.trust_forwarded_headers {
+            return None;
+        }
+        first_header(req, "X-Forwarded-Prefix").filter(|prefix| {
+            prefix.starts_with('/')
+                && Origin::parse(prefix).is_ok_and(|origin| origin.query().is_none())
+        })
+    }
+
+    /// Get the value of the `Access-Control-Allow-Origin` header for the request, if its origin
+    /// is allowed.
+    fn allowed_origin(&self, req: &Request<'_>) -> Option<String> {
+        if self.cors_allowed_origins.iter().any(|origin| origin == "*") {
+            return Some("*".to_owned());
+        }
+        let origin = req.headers().get_one("Origin")?;
+        self.cors_allowed_origins
+            .iter()
+            .find(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+            .map(|_| origin.to_owned())
     }
 }
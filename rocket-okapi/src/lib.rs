@@ -63,6 +63,8 @@
 //! - `openapi_get_routes![...]`: To generate and add the `openapi.json` route.
 //! - `openapi_get_routes_spec![...]`: To generate and return a list of routes and the openapi spec.
 //! - `openapi_get_spec![...]`: To generate and return the openapi spec.
+//! - `openapi_route_list![...]` and `openapi_get_routes_from![...]`: To combine the routes of
+//!   several crates into one openapi spec.
 //!
 //! The last 3 macros have very similar behavior, but differ in what they return.
 //! Here is a list of the marcos and what they return:
@@ -73,35 +75,202 @@
 
 mod error;
 
+/// Contains the `Jwt` request guard, which verifies and documents JWT bearer tokens.
+#[cfg(feature = "jwt")]
+pub mod auth;
+/// Contains `DocsAuth`, which protects the documentation routes.
+pub mod docs_auth;
+/// Contains `OpenApiFairing`, which mounts the routes, the specification and the documentation
+/// UIs.
+pub mod fairing;
 /// Contains the `Generator` struct, which you can use to manually control the way a struct is
 /// represented in the documentation.
 pub mod gen;
+/// Contains the schemas of the requests and responses of GraphQL endpoints.
+pub mod graphql;
 /// Contains several `Rocket` `Handler`s, which are used for serving the json files and the swagger
 /// interface.
 pub mod handlers;
+/// Contains routes that mock an API from its specification.
+pub mod mock;
 /// Contains the functions and structs required to display the RapiDoc UI.
 #[cfg(feature = "rapidoc")]
 pub mod rapidoc;
 /// This module contains several traits that correspond to the `Rocket` traits pertaining to request
 /// guards and responses
 pub mod request;
+/// Contains a fairing that validates requests against the generated specification.
+pub mod request_validation;
 /// Contains the trait `OpenApiResponder`, meaning that a response implementing this trait can be
 /// documented.
 pub mod response;
+/// Contains a fairing that checks responses against the generated specification.
+pub mod response_validation;
 /// Contains then `OpenApiSettings` struct, which can be used to customize the behavior of a
 /// `Generator`.
 pub mod settings;
 /// Contains the functions and structs required to display the Swagger UI.
 #[cfg(feature = "swagger")]
 pub mod swagger_ui;
+/// Contains helpers to test the generated specification.
+pub mod testing;
 /// Assorted function that are used throughout the application.
 pub mod util;
+/// Contains the documented health, readiness and version endpoints.
+pub mod wellknown;
 
 pub use error::*;
 /// Re-export Okapi
 pub use okapi;
 pub use rocket_okapi_codegen::*;
 pub use schemars::JsonSchema;
+#[doc(hidden)]
+pub use serde;
+#[doc(hidden)]
+pub use serde_json;
+
+/// The information about a route that is used to decide if it is documented, see
+/// [`OpenApiSettings::skip_route`](settings::OpenApiSettings::skip_route).
+#[derive(Debug, Clone)]
+pub struct RouteInfo<'a> {
+    /// The path of the endpoint, like `/users/{id}`.
+    pub path: &'a str,
+    /// The HTTP Method of this endpoint.
+    pub method: rocket::http::Method,
+    /// The operation id of the endpoint.
+    pub operation_id: &'a str,
+    /// The tags of the endpoint, from `#[openapi(tag = "...")]`.
+    pub tags: &'a [String],
+}
+
+/// Routes with the functions that add their operations to the OpenAPI specification, created by
+/// [`openapi_route_list!`]. Library crates can return this from a function, so the binary crate
+/// can combine the routes of several crates (and its own) into one specification with
+/// [`openapi_get_routes_from!`].
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_okapi::{openapi, openapi_get_routes_from, openapi_route_list};
+///
+/// mod users {
+///     use rocket::get;
+///     use rocket_okapi::{openapi, openapi_route_list, OpenApiRoutes};
+///
+///     #[openapi]
+///     #[get("/users")]
+///     fn get_users() {}
+///
+///     /// In a library crate.
+///     pub fn api() -> OpenApiRoutes {
+///         openapi_route_list![get_users]
+///     }
+/// }
+///
+/// #[openapi]
+/// #[get("/")]
+/// fn index() {}
+///
+/// let routes = openapi_get_routes_from![openapi_route_list![index], users::api()];
+/// assert_eq!(routes.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpenApiRoutes {
+    routes: Vec<rocket::Route>,
+    operations: Vec<(&'static str, String, gen::AddOperationFn)>,
+    manual_operations: Vec<(rocket::http::Method, String, okapi::openapi3::Operation)>,
+}
+
+impl OpenApiRoutes {
+    /// Create the routes, use [`openapi_route_list!`] instead.
+    #[doc(hidden)]
+    pub fn new(
+        routes: Vec<rocket::Route>,
+        operations: Vec<(&'static str, String, gen::AddOperationFn)>,
+    ) -> Self {
+        OpenApiRoutes {
+            routes,
+            operations,
+            manual_operations: Vec::new(),
+        }
+    }
+
+    /// Add the routes (and operations) of `other`.
+    pub fn extend(&mut self, other: OpenApiRoutes) {
+        self.routes.extend(other.routes);
+        self.operations.extend(other.operations);
+        self.manual_operations.extend(other.manual_operations);
+    }
+
+    /// Add a route that is not documented with `#[openapi]` (like a route that is created with
+    /// `rocket::Route::new`), with its operation. The operation is added with
+    /// [`OpenApiGenerator::add_manual_operation`](gen::OpenApiGenerator::add_manual_operation),
+    /// for the method and path of the route.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::Operation;
+    /// use rocket::http::Method;
+    /// use rocket::route::{Handler, Outcome, Route};
+    /// use rocket::{Data, Request};
+    /// use rocket_okapi::{openapi_get_routes_from, OpenApiRoutes};
+    ///
+    /// #[derive(Clone)]
+    /// struct Health;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Handler for Health {
+    ///     async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+    ///         Outcome::from(req, "OK")
+    ///     }
+    /// }
+    ///
+    /// let mut api = OpenApiRoutes::default();
+    /// api.add_manual_route(
+    ///     Route::new(Method::Get, "/health", Health),
+    ///     Operation {
+    ///         operation_id: Some("health".to_owned()),
+    ///         summary: Some("Check if the server is running.".to_owned()),
+    ///         ..Operation::default()
+    ///     },
+    /// );
+    /// let routes = openapi_get_routes_from![api];
+    /// ```
+    pub fn add_manual_route(
+        &mut self,
+        route: rocket::Route,
+        operation: okapi::openapi3::Operation,
+    ) {
+        self.manual_operations
+            .push((route.method, route.uri.path().to_string(), operation));
+        self.routes.push(route);
+    }
+
+    /// The routes, without the `openapi.json` route.
+    pub fn routes(&self) -> &[rocket::Route] {
+        &self.routes
+    }
+
+    /// Generate the specification of the routes (and the manual routes). The `info` is left
+    /// empty, [`openapi_get_routes_from!`] sets it from the package of the binary crate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation of a route can not be generated.
+    pub fn spec(&self, settings: &settings::OpenApiSettings) -> okapi::openapi3::OpenApi {
+        let mut gen = gen::OpenApiGenerator::new(settings);
+        gen.add_operations(
+            self.operations
+                .iter()
+                .map(|(name, operation_id, add_operation)| {
+                    (*name, operation_id.clone(), *add_operation)
+                })
+                .collect(),
+        );
+        for (method, path, operation) in &self.manual_operations {
+            gen.add_manual_operation(*method, path, operation.clone());
+        }
+        gen.into_openapi()
+    }
+}
 
 /// Contains information about an endpoint.
 pub struct OperationInfo {
@@ -116,11 +285,339 @@ pub struct OperationInfo {
 /// Convert OpenApi object to routable endpoint.
 ///
 /// Used to serve an `OpenApi` object as an `openapi.json` file in Rocket.
+///
+/// # Panics
+///
+/// Panics if one of the `override_files` or `translation_files` of the settings can not be
+/// applied, or if the validation fails with `SpecValidation::Abort`.
 pub fn get_openapi_route(
     spec: okapi::openapi3::OpenApi,
     settings: &settings::OpenApiSettings,
 ) -> rocket::Route {
-    handlers::OpenApiHandler::new(spec).into_route(&settings.json_path)
+    let route = openapi_handler(spec, settings).into_route(&settings.json_path);
+    match &settings.docs_auth {
+        Some(auth) => auth.protect_route(route),
+        None => route,
+    }
+}
+
+/// Convert OpenApi object to routable endpoints: the route created by [`get_openapi_route`],
+/// a route that serves the spec of every tag under the `tag_shards_path` of the settings, and a
+/// route that serves a slim version of the spec (without descriptions and examples) at the
+/// `slim_json_path` of the settings, if they are set.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`get_openapi_route`].
+pub fn get_openapi_routes(
+    spec: okapi::openapi3::OpenApi,
+    settings: &settings::OpenApiSettings,
+) -> Vec<rocket::Route> {
+    openapi_routes(openapi_handler(spec, settings), settings)
+}
+
+/// Create the routes of [`get_openapi_routes`], but only generate the spec with `generate` when
+/// it is requested, as configured by the `spec_assembly` of the settings (see
+/// [`SpecAssembly`](settings::SpecAssembly)). `openapi_get_routes!` uses this for the lazy modes.
+///
+/// # Panics
+///
+/// Panics if the `spec_assembly` of the settings is `SpecAssembly::Eager`.
+pub fn get_lazy_openapi_routes(
+    generate: impl Fn(&settings::OpenApiSettings) -> okapi::openapi3::OpenApi + Send + Sync + 'static,
+    settings: &settings::OpenApiSettings,
+) -> Vec<rocket::Route> {
+    use settings::SpecAssembly;
+    let shared_settings = settings::SharedSettings::new(settings);
+    if let Err(err) = shared_settings.to_settings() {
+        log::error!("The OpenAPI spec can not be generated lazily: {}", err);
+    }
+    let finish = finish_spec(settings);
+    let validation = settings.validation;
+    let assemble = move || -> Result<okapi::openapi3::OpenApi> {
+        let spec = finish(generate(&shared_settings.to_settings()?))?;
+        check_spec(&spec, validation)?;
+        Ok(spec)
+    };
+    let handler = handlers::OpenApiHandler::new(okapi::openapi3::OpenApi::new());
+    let handler = match settings.spec_assembly {
+        SpecAssembly::Eager => panic!("`get_lazy_openapi_routes` needs a lazy `spec_assembly`."),
+        SpecAssembly::Lazy => handler.reload_with(assemble),
+        SpecAssembly::LazyCached => {
+            let cache = std::sync::Mutex::new(None::<okapi::openapi3::OpenApi>);
+            handler.reload_with(move || {
+                let mut cache = cache
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                if let Some(spec) = &*cache {
+                    return Ok(spec.clone());
+                }
+                let spec = assemble()?;
+                *cache = Some(spec.clone());
+                Ok(spec)
+            })
+        }
+    };
+    // The handler reloads the spec, so the translations are applied to every reloaded spec.
+    let handler = handler
+        .cors_allowed_origins(settings.cors_allowed_origins.clone())
+        .request_server(settings.request_server)
+        .relative_server(settings.relative_server)
+        .trust_forwarded_headers(settings.trust_forwarded_headers)
+        .translations(settings.translation_files.clone())
+        .pretty(settings.json_pretty)
+        .stream(settings.stream_json);
+    openapi_routes(handler, settings)
+}
+
+/// Create the `openapi.json` route (and the tag and slim routes) of a handler.
+fn openapi_routes(
+    handler: handlers::OpenApiHandler,
+    settings: &settings::OpenApiSettings,
+) -> Vec<rocket::Route> {
+    let mut routes = vec![handler.clone().into_route(&settings.json_path)];
+    if let Some(tag_shards_path) = &settings.tag_shards_path {
+        let path = format!("{}/<tag>", tag_shards_path.trim_end_matches('/'));
+        routes.push(handler.clone().by_tag().into_route(path));
+    }
+    if let Some(slim_json_path) = &settings.slim_json_path {
+        routes.push(handler.slim().into_route(slim_json_path));
+    }
+    match &settings.docs_auth {
+        Some(auth) => auth.protect(routes),
+        None => routes,
+    }
+}
+
+/// Create the handler that serves the spec, after applying the spec files of the settings.
+fn openapi_handler(
+    spec: okapi::openapi3::OpenApi,
+    settings: &settings::OpenApiSettings,
+) -> handlers::OpenApiHandler {
+    let finish = finish_spec(settings);
+    let reload = move || finish(spec.clone());
+    let spec = match reload() {
+        Ok(spec) => spec,
+        Err(err) => panic!("{}", err),
+    };
+    validate_spec(&spec, settings);
+    if let Some(path) = &settings.write_spec_path {
+        if let Err(err) = util::write_spec_file(path, &spec) {
+            log::error!("Could not write the OpenAPI spec: {}", err);
+        }
+    }
+    let mut handler = handlers::OpenApiHandler::new(spec)
+        .cors_allowed_origins(settings.cors_allowed_origins.clone())
+        .request_server(settings.request_server)
+        .relative_server(settings.relative_server)
+        .trust_forwarded_headers(settings.trust_forwarded_headers)
+        .translations(settings.translation_files.clone())
+        .pretty(settings.json_pretty)
+        .stream(settings.stream_json);
+    if let Some(err) = handler.translation_error() {
+        panic!("{}", err);
+    }
+    if settings.reloads_spec_files() {
+        handler = handler.reload_with(reload);
+    }
+    handler
+}
+
+/// Everything that is done with the spec files to a generated spec, so it can be done again for
+/// every request.
+fn finish_spec(
+    settings: &settings::OpenApiSettings,
+) -> impl Fn(okapi::openapi3::OpenApi) -> Result<okapi::openapi3::OpenApi> + Send + Sync + 'static {
+    let reload_spec_files = settings.reloads_spec_files();
+    let merge_spec_files = settings.merge_spec_files.clone();
+    let code_samples = settings.code_samples;
+    let override_files = settings.override_files.clone();
+    let sort_spec = settings.sort_spec;
+    let spec_filter = settings.spec_filter.clone();
+    let info = (!settings.info.is_empty()).then(|| settings.info.clone());
+    let openapi_version = settings.openapi_version;
+    let servers = settings.servers.clone().unwrap_or_else(configured_servers);
+    // The proxied paths are generated here, with the same schema settings as the routes.
+    let proxied_paths = (!settings.proxied_paths.is_empty()).then(|| {
+        let mut settings = settings.clone();
+        settings.shared_schema_generator = None;
+        settings.proxied_paths.spec(&settings)
+    });
+    move |mut spec| {
+        if reload_spec_files {
+            gen::merge_spec_files(&mut spec, &merge_spec_files, code_samples)?;
+        }
+        if let Some(proxied) = &proxied_paths {
+            let proxied = proxied.as_ref().map_err(Clone::clone)?;
+            spec.merge(proxied, okapi::merge::MergePolicy::Reject)
+                .map_err(|err| {
+                    OpenApiError::new(format!("Could not add the proxied paths: {}", err))
+                })?;
+        }
+        if spec.servers.is_empty() {
+            spec.servers = servers.clone();
+        }
+        if let Some(info) = &info {
+            info.apply(&mut spec.info, openapi_version);
+        }
+        apply_override_files(&mut spec, &override_files).map_err(|err| {
+            OpenApiError::new(format!("Could not apply OpenAPI spec overrides: {}", err))
+        })?;
+        if let Some(spec_filter) = &spec_filter {
+            spec = spec_filter.apply(&spec);
+        }
+        if sort_spec {
+            spec.sort();
+        }
+        Ok(spec)
+    }
+}
+
+/// Read the `openapi_servers` of the active profile of Rocket's configuration.
+fn configured_servers() -> Vec<okapi::openapi3::Server> {
+    match rocket::Config::figment().extract_inner("openapi_servers") {
+        Ok(servers) => servers,
+        Err(err) if err.missing() => Vec::new(),
+        Err(err) => {
+            log::error!(
+                "Could not read `openapi_servers` from the configuration: {}",
+                err
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// Create a route that serves the `OpenApi` object as a Postman collection (see
+/// [`okapi::export::postman`]), which can be imported into Postman from its URL.
+///
+/// ```rust,no_run
+/// use rocket_okapi::openapi_get_routes_spec;
+///
+/// let (mut routes, spec) = openapi_get_routes_spec![];
+/// routes.push(rocket_okapi::get_postman_route(&spec, "/postman.json"));
+/// rocket::build().mount("/api", routes);
+/// ```
+pub fn get_postman_route(spec: &okapi::openapi3::OpenApi, path: impl AsRef<str>) -> rocket::Route {
+    handlers::ContentHandler::json(&okapi::export::postman(spec)).into_route(path)
+}
+
+/// Create a route that serves the `OpenApi` object as an Insomnia export (see
+/// [`okapi::export::insomnia`]), which can be imported into Insomnia from its URL.
+///
+/// ```rust,no_run
+/// use rocket_okapi::openapi_get_routes_spec;
+///
+/// let (mut routes, spec) = openapi_get_routes_spec![];
+/// routes.push(rocket_okapi::get_insomnia_route(&spec, "/insomnia.json"));
+/// rocket::build().mount("/api", routes);
+/// ```
+pub fn get_insomnia_route(spec: &okapi::openapi3::OpenApi, path: impl AsRef<str>) -> rocket::Route {
+    handlers::ContentHandler::json(&okapi::export::insomnia(spec)).into_route(path)
+}
+
+/// Get the OpenAPI document served by a Rocket instance, without launching it (no port is bound).
+///
+/// The document is requested from the route created by [`get_openapi_route`] (like the
+/// `openapi.json` route of the `openapi_get_routes!` and `mount_endpoints_and_merged_docs!`
+/// macros) with a local client, so it is the same document a client would get. This ignites
+/// the Rocket instance, so its fairings are run.
+///
+/// This uses a blocking client, and can not be called from an async context.
+///
+/// ```rust,no_run
+/// # fn rocket() -> rocket::Rocket<rocket::Build> { rocket::build() }
+/// let spec = rocket_okapi::get_openapi_spec(rocket()).unwrap();
+/// rocket_okapi::util::write_spec_file("openapi.json".as_ref(), &spec).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the Rocket instance can not be ignited, or if it does not have exactly
+/// one OpenAPI route.
+pub fn get_openapi_spec(rocket: rocket::Rocket<rocket::Build>) -> Result<okapi::openapi3::OpenApi> {
+    let paths: Vec<String> = rocket
+        .routes()
+        .filter(|route| route.name.as_deref() == Some(handlers::ROUTE_NAME))
+        .map(|route| route.uri.path().to_owned())
+        .collect();
+    let path = match paths.as_slice() {
+        [path] => path.clone(),
+        [] => return Err(OpenApiError::new("No OpenAPI route is mounted.".to_owned())),
+        _ => {
+            return Err(OpenApiError::new(format!(
+                "Multiple OpenAPI routes are mounted: `{}`.",
+                paths.join("`, `")
+            )))
+        }
+    };
+    let client = rocket::local::blocking::Client::untracked(rocket)
+        .map_err(|err| OpenApiError::new(format!("Could not ignite Rocket: {}", err)))?;
+    let response = client.get(path.as_str()).dispatch();
+    let body = response.into_string().unwrap_or_default();
+    serde_json::from_str(&body).map_err(|err| {
+        OpenApiError::new(format!(
+            "Could not parse the spec served at `{}`: {}",
+            path, err
+        ))
+    })
+}
+
+/// Apply the `override_files` of the settings (see
+/// [`OpenApiSettings::override_file`](settings::OpenApiSettings::override_file)) to the `OpenApi`
+/// object, in order.
+pub fn apply_spec_overrides(
+    spec: &mut okapi::openapi3::OpenApi,
+    settings: &settings::OpenApiSettings,
+) -> Result<()> {
+    apply_override_files(spec, &settings.override_files)
+}
+
+fn apply_override_files(
+    spec: &mut okapi::openapi3::OpenApi,
+    override_files: &[std::path::PathBuf],
+) -> Result<()> {
+    for path in override_files {
+        let patch: serde_json::Value = util::read_spec_file(path)?;
+        spec.apply_merge_patch(&patch).map_err(|err| {
+            OpenApiError::new(format!("Could not apply `{}`: {}", path.display(), err))
+        })?;
+    }
+    Ok(())
+}
+
+/// Validate the `OpenApi` object, as configured by `settings.validation`.
+///
+/// # Panics
+///
+/// Panics if problems are found and `settings.validation` is `SpecValidation::Abort`.
+pub fn validate_spec(spec: &okapi::openapi3::OpenApi, settings: &settings::OpenApiSettings) {
+    if let Err(err) = check_spec(spec, settings.validation) {
+        panic!("{}", err);
+    }
+}
+
+/// Validate the spec like [`validate_spec`], but return an error instead of panicking.
+fn check_spec(spec: &okapi::openapi3::OpenApi, validation: settings::SpecValidation) -> Result<()> {
+    use settings::SpecValidation;
+    if validation == SpecValidation::Skip {
+        return Ok(());
+    }
+    if let Err(errors) = spec.validate() {
+        for error in &errors {
+            match validation {
+                SpecValidation::Abort => log::error!("Invalid OpenAPI spec: {}", error),
+                _ => log::warn!("Invalid OpenAPI spec: {}", error),
+            }
+        }
+        if validation == SpecValidation::Abort {
+            return Err(OpenApiError::new(format!(
+                "OpenAPI spec has {} problem(s), see the log for details.",
+                errors.len()
+            )));
+        }
+    }
+    Ok(())
 }
 
 /// Mount endpoints and mount merged OpenAPI documentation.
@@ -131,14 +628,18 @@ pub fn get_openapi_route(
 /// The macro expects the following arguments:
 /// - rocket_builder: `Rocket<Build>`,
 /// - base_path: `&str`, `String` or [`Uri`](rocket::http::uri::Uri). (Anything that implements `ToString`)
-/// Anything accepted by [`mount()`](https://docs.rs/rocket/0.5.0-rc.1/rocket/struct.Rocket.html#method.mount)
+///   Anything accepted by [`mount()`](https://docs.rs/rocket/0.5.0-rc.1/rocket/struct.Rocket.html#method.mount)
 /// - openapi_settings: `OpenApiSettings` (use `OpenApiSettings::default()` if default settings are okay for you),
 /// - List of (0 or more):
 ///   - path:  `&str`, `String` or [`Uri`](rocket::http::uri::Uri).
-///   Anything accepted by `mount()` (`base_path` should not be included).
+///     Anything accepted by `mount()` (`base_path` should not be included).
 ///   - `=>`: divider
 ///   - route_and_docs: `(Vec<rocket::Route>, OpenApi)`
 ///
+/// If the groups use the same types, use
+/// [`OpenApiSettings::share_schemas`](settings::OpenApiSettings::share_schemas) to generate their
+/// schemas only once.
+///
 /// Example:
 /// ```rust,ignore
 /// let custom_route_spec = (vec![], custom_spec());
@@ -170,10 +671,7 @@ macro_rules! mount_endpoints_and_merged_docs {
         // Add OpenApi route
         $rocket_builder = $rocket_builder.mount(
             $base_path,
-            vec![rocket_okapi::get_openapi_route(
-                openapi_docs,
-                &$openapi_settings,
-            )],
+            rocket_okapi::get_openapi_routes(openapi_docs, &$openapi_settings),
         );
     }};
 }
@@ -202,9 +700,17 @@ macro_rules! openapi_get_routes {
     // With settings
     ($settings:ident :
      $($route:expr),* $(,)*) => {{
-        let spec = rocket_okapi::openapi_spec![$($route),*](&$settings);
-        let routes = rocket_okapi::openapi_routes![$($route),*](Some(spec), &$settings);
-        routes
+        if $settings.spec_assembly == rocket_okapi::settings::SpecAssembly::Eager {
+            let spec = rocket_okapi::openapi_spec![$($route),*](&$settings);
+            rocket_okapi::openapi_routes![$($route),*](Some(spec), &$settings)
+        } else {
+            let mut routes = rocket_okapi::openapi_routes![$($route),*](None, &$settings);
+            routes.extend(rocket_okapi::get_lazy_openapi_routes(
+                rocket_okapi::openapi_spec![$($route),*],
+                &$settings,
+            ));
+            routes
+        }
     }};
 
     // Without settings
@@ -214,6 +720,192 @@ macro_rules! openapi_get_routes {
     }};
 }
 
+/// Like `openapi_get_routes!`, but for [`OpenApiRoutes`] (created by `openapi_route_list!`),
+/// which can come from other crates. This returns all routes, and the `openapi.json` route with
+/// the combined specification. The `info` of the specification is created from the package of
+/// the crate that calls this macro.
+///
+/// Example:
+/// ```rust,ignore
+/// let settings = rocket_okapi::settings::OpenApiSettings::new();
+/// let routes: Vec<rocket::Route> = openapi_get_routes_from![
+///     settings: openapi_route_list![index], users_api::api(), messages_api::api()
+/// ];
+/// ```
+#[macro_export]
+macro_rules! openapi_get_routes_from {
+    // With settings
+    ($settings:ident :
+     $($route_list:expr),* $(,)*) => {{
+        let mut route_list = rocket_okapi::OpenApiRoutes::default();
+        $(route_list.extend($route_list);)*
+        let info = rocket_okapi::package_info!();
+        let mut routes = route_list.routes().to_vec();
+        if $settings.spec_assembly == rocket_okapi::settings::SpecAssembly::Eager {
+            let mut spec = route_list.spec(&$settings);
+            spec.info = info;
+            routes.extend(rocket_okapi::get_openapi_routes(spec, &$settings));
+        } else {
+            routes.extend(rocket_okapi::get_lazy_openapi_routes(
+                move |settings| {
+                    let mut spec = route_list.spec(settings);
+                    spec.info = info.clone();
+                    spec
+                },
+                &$settings,
+            ));
+        }
+        routes
+    }};
+
+    // Without settings
+    ($($route_list:expr),* $(,)*) => {{
+        let settings = rocket_okapi::settings::OpenApiSettings::new();
+        rocket_okapi::openapi_get_routes_from![settings: $($route_list),*]
+    }};
+}
+
+/// Create the `Info` of the specification from the package of the crate that calls this macro.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! package_info {
+    () => {{
+        let mut info = rocket_okapi::okapi::openapi3::Info {
+            title: env!("CARGO_PKG_NAME").to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            ..Default::default()
+        };
+        if !env!("CARGO_PKG_DESCRIPTION").is_empty() {
+            info.description = Some(env!("CARGO_PKG_DESCRIPTION").to_owned());
+        }
+        if !env!("CARGO_PKG_REPOSITORY").is_empty() {
+            info.contact = Some(rocket_okapi::okapi::openapi3::Contact {
+                name: Some("Repository".to_owned()),
+                url: Some(env!("CARGO_PKG_REPOSITORY").to_owned()),
+                ..Default::default()
+            });
+        }
+        if !env!("CARGO_PKG_HOMEPAGE").is_empty() {
+            info.contact = Some(rocket_okapi::okapi::openapi3::Contact {
+                name: Some("Homepage".to_owned()),
+                url: Some(env!("CARGO_PKG_HOMEPAGE").to_owned()),
+                ..Default::default()
+            });
+        }
+        info
+    }};
+}
+
+/// Create the [`BuildInfo`](wellknown::BuildInfo) of the crate that calls this macro: its name and
+/// version, and the `GIT_SHA` and `BUILD_TIMESTAMP` environment variables at build time.
+///
+/// Example:
+/// ```rust
+/// let info = rocket_okapi::build_info!();
+/// assert_eq!(info.name, "rocket_okapi");
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        rocket_okapi::wellknown::BuildInfo {
+            name: env!("CARGO_PKG_NAME").to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            git_sha: option_env!("GIT_SHA").map(str::to_owned),
+            build_timestamp: option_env!("BUILD_TIMESTAMP").map(str::to_owned),
+        }
+    };
+}
+
+/// Create a marker type that always has the same value, like the `"type": "card"` field of a
+/// payment. It is serialized as the value, only the value can be deserialized, and it is
+/// documented as an `enum` with only the value (see [`util::literal_schema`]).
+///
+/// Example:
+/// ```rust
+/// use rocket_okapi::{literal_type, JsonSchema};
+/// use serde::{Deserialize, Serialize};
+///
+/// literal_type! {
+///     /// The type of a card payment.
+///     pub CardType = "card";
+/// }
+///
+/// #[derive(Serialize, Deserialize, JsonSchema)]
+/// struct CardPayment {
+///     #[serde(rename = "type")]
+///     kind: CardType,
+///     number: String,
+/// }
+///
+/// let payment: CardPayment =
+///     serde_json::from_str(r#"{ "type": "card", "number": "4242" }"#).unwrap();
+/// assert_eq!(payment.kind, CardType);
+/// assert!(serde_json::from_str::<CardPayment>(r#"{ "type": "cash", "number": "" }"#).is_err());
+///
+/// let schema = schemars::schema_for!(CardPayment);
+/// let schema = serde_json::to_value(&schema.schema.object.unwrap().properties["type"]).unwrap();
+/// assert_eq!(schema, serde_json::json!({ "type": "string", "enum": ["card"] }));
+/// ```
+#[macro_export]
+macro_rules! literal_type {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $value:expr $(;)?) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        $vis struct $name;
+
+        impl $name {
+            /// The value of this type.
+            fn value() -> rocket_okapi::serde_json::Value {
+                rocket_okapi::serde_json::Value::from($value)
+            }
+        }
+
+        impl rocket_okapi::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: rocket_okapi::serde::Serializer,
+            {
+                rocket_okapi::serde::Serialize::serialize(&Self::value(), serializer)
+            }
+        }
+
+        impl<'de> rocket_okapi::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: rocket_okapi::serde::Deserializer<'de>,
+            {
+                let value: rocket_okapi::serde_json::Value =
+                    rocket_okapi::serde::Deserialize::deserialize(deserializer)?;
+                if value == Self::value() {
+                    Ok($name)
+                } else {
+                    Err(<D::Error as rocket_okapi::serde::de::Error>::custom(format!(
+                        "expected {}, found {}",
+                        Self::value(),
+                        value
+                    )))
+                }
+            }
+        }
+
+        impl rocket_okapi::JsonSchema for $name {
+            fn is_referenceable() -> bool {
+                false
+            }
+
+            fn schema_name() -> String {
+                stringify!($name).to_owned()
+            }
+
+            fn json_schema(
+                _gen: &mut rocket_okapi::okapi::schemars::gen::SchemaGenerator,
+            ) -> rocket_okapi::okapi::schemars::schema::Schema {
+                rocket_okapi::util::literal_schema(Self::value()).into()
+            }
+        }
+    };
+}
+
 /// A replacement macro for `rocket::routes`. This parses the routes and provides
 /// a tuple with 2 parts `(Vec<rocket::Route>, OpenApi)`:
 /// - `Vec<rocket::Route>`: A list of all the routes that `rocket::routes![]` would have provided.
@@ -284,3 +976,31 @@ macro_rules! openapi_get_spec {
         rocket_okapi::openapi_get_spec![settings: $($route),*]
     }};
 }
+
+/// Implement [`OpenApiFromRequest`](request::OpenApiFromRequest) for a list of request guards
+/// that do not need to be documented (like a database pool or configuration), so they add no
+/// parameters, security requirements or responses to the routes that use them. This is the same
+/// as `#[derive(OpenApiFromRequest)]`, but can also be used for types of other crates.
+///
+/// Guards with a lifetime can use the lifetime `'r`.
+///
+/// Example:
+/// ```rust,ignore
+/// rocket_okapi::ignore_from_request!(DbPool, Metrics, AppConfig<'r>);
+/// ```
+#[macro_export]
+macro_rules! ignore_from_request {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl<'r> rocket_okapi::request::OpenApiFromRequest<'r> for $ty {
+                fn from_request_input(
+                    _gen: &mut rocket_okapi::gen::OpenApiGenerator,
+                    _name: String,
+                    _required: bool,
+                ) -> rocket_okapi::Result<rocket_okapi::request::RequestHeaderInput> {
+                    Ok(rocket_okapi::request::RequestHeaderInput::None)
+                }
+            }
+        )*
+    };
+}
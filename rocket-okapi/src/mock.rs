@@ -0,0 +1,138 @@
+use okapi::example::media_type_example;
+use okapi::openapi3::{OpenApi, Operation, RefOr, Response};
+use rocket::http::{ContentType, Method, Status};
+use rocket::response::content::Custom;
+use rocket::route::{Handler, Outcome};
+use rocket::{Data, Request, Route};
+use serde_json::Value;
+
+/// Create routes that respond to every operation of `spec` with an example response, to run a
+/// mock of the API without its implementation.
+///
+/// Every operation responds with its first documented success (`2XX`) response, or else its
+/// `default` response. The body uses the `application/json` content if it is documented (or
+/// else the first content), with its `example`, the first of its `examples`, or else a value
+/// generated from its schema (see [`okapi::example`]).
+/// The routes do not validate requests, use
+/// [`RequestValidator`](crate::request_validation::RequestValidator) for that.
+///
+/// ```rust,no_run
+/// use rocket_okapi::openapi_get_routes_spec;
+///
+/// let (routes, spec) = openapi_get_routes_spec![];
+/// let routes = if std::env::var("MOCK_API").is_ok() {
+///     rocket_okapi::mock::mock_routes(&spec)
+/// } else {
+///     routes
+/// };
+/// rocket::build().mount("/api", routes);
+/// ```
+#[must_use]
+pub fn mock_routes(spec: &OpenApi) -> Vec<Route> {
+    let mut routes = Vec::new();
+    for (path, path_item) in &spec.paths {
+        let uri = route_uri(path);
+        let methods = vec![
+            (Method::Get, &path_item.get),
+            (Method::Put, &path_item.put),
+            (Method::Post, &path_item.post),
+            (Method::Delete, &path_item.delete),
+            (Method::Options, &path_item.options),
+            (Method::Head, &path_item.head),
+            (Method::Patch, &path_item.patch),
+            (Method::Trace, &path_item.trace),
+        ];
+        for (method, operation) in methods {
+            if let Some(operation) = operation {
+                let handler = MockHandler::new(spec, operation);
+                routes.push(Route::new(method, &uri, handler));
+            }
+        }
+    }
+    routes
+}
+
+/// A handler that always sends the same response.
+#[derive(Clone)]
+struct MockHandler {
+    status: Status,
+    body: Option<(ContentType, String)>,
+}
+
+impl MockHandler {
+    fn new(spec: &OpenApi, operation: &Operation) -> Self {
+        let components = spec.components.clone().unwrap_or_default();
+        let mut success: Vec<(&String, &RefOr<Response>)> = operation
+            .responses
+            .responses
+            .iter()
+            .filter(|(status, _)| status.starts_with('2'))
+            .collect();
+        success.sort_by_key(|(status, _)| status.as_str());
+        let (status, response) = match (success.first(), &operation.responses.default) {
+            (Some((status, response)), _) => {
+                // `2XX` is sent as `200`.
+                let status = status.replace('X', "0").parse().unwrap_or(200);
+                (Status::from_code(status).unwrap_or(Status::Ok), *response)
+            }
+            (None, Some(response)) => (Status::Ok, response),
+            (None, None) => {
+                return MockHandler {
+                    status: Status::NoContent,
+                    body: None,
+                }
+            }
+        };
+        let content = response.resolve(&components).ok().and_then(|response| {
+            response
+                .content
+                .get("application/json")
+                .map(|content| ("application/json", content))
+                .or_else(|| {
+                    let (media_type, content) = response.content.iter().next()?;
+                    Some((media_type.as_str(), content))
+                })
+        });
+        let body = content.map(|(media_type, content)| {
+            let content_type = ContentType::parse_flexible(media_type).unwrap_or(ContentType::Any);
+            let body = match media_type_example(spec, content) {
+                Value::String(string) if !content_type.is_json() => string,
+                value => value.to_string(),
+            };
+            (content_type, body)
+        });
+        MockHandler { status, body }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for MockHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        match &self.body {
+            Some((content_type, body)) => Outcome::from(
+                req,
+                (self.status, Custom(content_type.clone(), body.clone())),
+            ),
+            None => Outcome::from(req, self.status),
+        }
+    }
+}
+
+/// Convert an OpenAPI path template to a Rocket route URI: `/users/{id}` becomes
+/// `/users/<p0>`. A segment that contains a parameter matches any value.
+fn route_uri(path: &str) -> String {
+    let mut parameters = 0;
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.contains('{') {
+                parameters += 1;
+                format!("<p{}>", parameters - 1)
+            } else {
+                segment.to_owned()
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
@@ -30,7 +30,7 @@
 //! ```
 
 use crate::handlers::{ContentHandler, RedirectHandler};
-use crate::settings::UrlObject;
+use crate::settings::{DocTheme, ThemeMode, UiAssets, UrlObject};
 use rocket::http::ContentType;
 use rocket::Route;
 use std::collections::HashMap;
@@ -108,6 +108,14 @@ pub struct RapiDocConfig {
     /// existing tags, prefix your custom tags with `_`, `c_` or `C_`.
     /// We will never use these prefixes in the provided tags.
     pub custom_template_tags: HashMap<String, String>,
+    /// Where the JavaScript file of RapiDoc (`rapidoc-min.js`) is loaded from.
+    /// The default is `UiAssets::Embedded`.
+    pub assets: UiAssets,
+    /// The color theme of the page, usually the
+    /// [`OpenApiSettings::doc_theme`](crate::settings::OpenApiSettings::doc_theme).
+    /// If set, it is used instead of `ui.theme` and `ui.primary_color`.
+    /// The default is `None`.
+    pub doc_theme: Option<DocTheme>,
 }
 
 /// A struct containing information about where and how the `openapi.json` files are served.
@@ -480,11 +488,13 @@ pub struct SlotsConfig {
     /// - An URL to an image (eg: `"https://example.com/example.png"`)
     /// - An encoded image (eg: `"data:image/svg+xml;base64,...=="`)
     pub logo: Option<String>,
-    /// The contents appear at the header after the spec-url input.
+    /// The contents appear at the header after the spec-url input, for example links back to
+    /// the portal of the API.
     pub header: Option<String>,
     /// The contents appear at the bottom of the spec.
     pub footer: Option<String>,
-    /// The contents appear at side navigation bar (only available in read-mode).
+    /// The contents appear at the top of the side navigation bar (only available in read-mode),
+    /// for example a logo that links back to the portal of the API.
     pub nav_logo: Option<String>,
     /// The contents appear at overview section.
     pub overview: Option<String>,
@@ -501,7 +511,7 @@ pub struct SlotsConfig {
     pub tags: HashMap<String, String>,
     /// Each path is identified by an id. The key of which is in the format of `{method}-{path}`.
     /// and certain invalid characters such as `{`, `}`, `#`, space is replaced by hyphen (-).
-    /// Use this slot to insert HTML content into a specific tag.
+    /// Use this slot to insert HTML content into a specific endpoint (like `get-/users`).
     pub endpoints: HashMap<String, String>,
 }
 
@@ -781,14 +791,14 @@ fn slot_list(slots: &[String]) -> String {
     let mut result = "".to_owned();
     for html in slots {
         // Append new slot
-        result = format!(r#"{}<slot>{}</slot>"#, result, html);
+        result = format!(r#"{}<div>{}</div>"#, result, html);
     }
     result
 }
 
 fn slot_opt(slot: &Option<String>, name: &str) -> String {
     match slot {
-        Some(html) => format!(r#"<slot name="{}">{}</slot>"#, name, html),
+        Some(html) => format!(r#"<div slot="{}">{}</div>"#, name, html),
         None => "".to_owned(),
     }
 }
@@ -807,7 +817,18 @@ fn slot_tags(slots: &HashMap<String, String>) -> String {
     let mut result = "".to_owned();
     for (key, html) in slots {
         // Append new slot
-        result = format!(r#"{}<slot name="tag--{}">{}</slot>"#, result, key, html);
+        // RapiDoc replaces these characters in the ids of tags.
+        let id: String = key
+            .chars()
+            .map(|c| {
+                if c.is_whitespace() || "#:?&={}".contains(c) {
+                    '-'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        result = format!(r#"{}<div slot="tag--{}">{}</div>"#, result, id, html);
     }
     result
 }
@@ -829,7 +850,7 @@ fn slot_endpoints(slots: &HashMap<String, String>) -> String {
             }
         }
         // Append new slot
-        result = format!(r#"{}<slot name="{}">{}</slot>"#, result, key, html);
+        result = format!(r#"{}<div slot="{}">{}</div>"#, result, key, html);
     }
     result
 }
@@ -852,11 +873,19 @@ pub fn make_rapidoc(config: &RapiDocConfig) -> impl Into<Vec<Route>> {
         "GOTO_PATH" => config.general.goto_path.clone(),
         "REQUEST_EXAMPLE_FIELDS" => config.general.fill_request_fields_with_example.to_string(),
         // UI Colors and Fonts
-        "THEME" => config.ui.theme.to_string(),
+        "THEME" => match &config.doc_theme {
+            Some(DocTheme { mode: ThemeMode::Dark, .. }) => Theme::Dark.to_string(),
+            Some(_) => Theme::Light.to_string(),
+            None => config.ui.theme.to_string(),
+        },
         "BG_COLOR" => config.ui.bg_color.clone(),
         "TEXT_COLOR" => config.ui.text_color.clone(),
         "HEADER_COLOR" => config.ui.header_color.clone(),
-        "PRIMARY_COLOR" => config.ui.primary_color.clone(),
+        "PRIMARY_COLOR" => config
+            .doc_theme
+            .as_ref()
+            .and_then(|theme| theme.accent_color.clone())
+            .unwrap_or_else(|| config.ui.primary_color.clone()),
         "LOAD_FONTS" => config.ui.load_fonts.to_string(),
         "REGULAR_FONT" => config.ui.regular_font.clone(),
         "MONO_FONT" => config.ui.mono_font.clone(),
@@ -911,7 +940,7 @@ pub fn make_rapidoc(config: &RapiDocConfig) -> impl Into<Vec<Route>> {
         "SERVERS" => slot_opt(&config.slots.servers, "servers"),
         "AUTH" => slot_opt(&config.slots.auth, "auth"),
         "TAGS" => slot_tags(&config.slots.tags),
-        "ENDPOINTS" => slot_endpoints(&config.slots.tags),
+        "ENDPOINTS" => slot_endpoints(&config.slots.endpoints),
     };
 
     let mut index_page = match &config.custom_html {
@@ -927,14 +956,34 @@ pub fn make_rapidoc(config: &RapiDocConfig) -> impl Into<Vec<Route>> {
         // Replace `{{KEY}}` with `VALUE`, So `{{ {{ KEY }} }}` => `{ { KEY } }`
         index_page = index_page.replace(&format!("{{{{{}}}}}", key), &value);
     }
+    if let Some(DocTheme {
+        mode: ThemeMode::Auto,
+        ..
+    }) = &config.doc_theme
+    {
+        // RapiDoc does not follow the preference of the browser itself.
+        let script = r#"    <script>
+      if (window.matchMedia("(prefers-color-scheme: dark)").matches) {
+        document.getElementById("rapidoc").setAttribute("theme", "dark");
+      }
+    </script>
+</body>"#;
+        index_page = index_page.replacen("</body>", script, 1);
+    }
+    if let Some(attributes) = config.assets.cdn_attributes("rapidoc-min.js", "src") {
+        index_page = index_page.replace("src=\"rapidoc-min.js\"", &attributes);
+    }
 
-    vec![
+    let mut routes = vec![
         RedirectHandler::to("index.html").into_route("/"),
         // Add custom html file
         ContentHandler::bytes_owned(ContentType::HTML, index_page.as_bytes().to_vec())
             .into_route("/index.html"),
         // Add other static files
-        static_file!("rapidoc-min.js", JavaScript),
         static_file!("oauth-receiver.html", HTML),
-    ]
+    ];
+    if config.assets == UiAssets::Embedded {
+        routes.push(static_file!("rapidoc-min.js", JavaScript));
+    }
+    routes
 }
@@ -1,45 +1,88 @@
 use super::OpenApiFromData;
 use crate::gen::OpenApiGenerator;
+use crate::settings::OpenApiVersion;
+use crate::util::{binary_schema, binary_schema_v31, json_schema_or_free_form, multipart_encoding};
 use okapi::{
-    openapi3::{MediaType, RequestBody},
+    openapi3::{MediaType, RequestBody, SchemaObject},
     Map,
 };
 use rocket::data::Data;
 use rocket::serde::json::Json;
+use schemars::schema::{InstanceType, Schema};
 use schemars::JsonSchema;
 use serde::Deserialize;
-use std::{borrow::Cow, result::Result as StdResult};
+use std::{borrow::Cow, convert::TryFrom, result::Result as StdResult};
 
 type Result = crate::Result<RequestBody>;
 
 const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+const DEFAULT_FORM_MIME_TYPE: &str = "application/x-www-form-urlencoded";
 
-fn get_mime_type<'a>(mime_type: Option<&'a str>, def: &'static str) -> &'a str
-{
+fn get_mime_type<'a>(mime_type: Option<&'a str>, def: &'static str) -> &'a str {
     match mime_type {
         Some(t) => t,
-        None => def
+        None => def,
+    }
+}
+
+fn request_body_with_schema(schema: SchemaObject, mime_type: &str) -> Result {
+    Ok(RequestBody {
+        content: {
+            let mut map = Map::new();
+            map.insert(
+                mime_type.to_owned(),
+                MediaType {
+                    schema: Some(schema),
+                    ..MediaType::default()
+                },
+            );
+            map
+        },
+        required: true,
+        ..okapi::openapi3::RequestBody::default()
+    })
+}
+
+/// Document the data limit with the name `limit` on all content of the request body.
+/// String schemas get a `maxLength` and byte arrays a `maxItems`, the description notes the limit.
+fn with_data_limit(gen: &OpenApiGenerator, limit: &str, body: Result) -> Result {
+    let mut body = body?;
+    let limit = match gen.data_limits().get(limit) {
+        Some(limit) => limit,
+        None => return Ok(body),
+    };
+    let max = u32::try_from(limit.as_u64()).unwrap_or(u32::MAX);
+    for media in body.content.values_mut() {
+        if let Some(schema) = media.schema.as_mut() {
+            if schema.has_type(InstanceType::String) {
+                schema.string().max_length = Some(max);
+            } else if schema.has_type(InstanceType::Array) {
+                schema.array().max_items = Some(max);
+            }
+        }
     }
+    let note = format!("The size of the body is limited to {}.", limit);
+    body.description = Some(match body.description {
+        Some(description) => format!("{}\n\n{}", description, note),
+        None => note,
+    });
+    Ok(body)
+}
+
+/// Request body for binary data. OpenAPI 3.1 uses `contentMediaType` for binary data, OpenAPI 3.0
+/// uses the given `schema`.
+fn binary_request_body(gen: &OpenApiGenerator, schema: SchemaObject, mime_type: &str) -> Result {
+    let schema = match gen.settings().openapi_version {
+        OpenApiVersion::V3_0 => schema,
+        OpenApiVersion::V3_1 => binary_schema_v31(mime_type),
+    };
+    request_body_with_schema(schema, mime_type)
 }
 
 macro_rules! fn_request_body {
     ($gen:ident, $ty:path, $mime_type:expr) => {{
         let schema = $gen.json_schema::<$ty>();
-        Ok(RequestBody {
-            content: {
-                let mut map = Map::new();
-                map.insert(
-                    $mime_type.to_owned(),
-                    MediaType {
-                        schema: Some(schema),
-                        ..MediaType::default()
-                    },
-                );
-                map
-            },
-            required: true,
-            ..okapi::openapi3::RequestBody::default()
-        })
+        request_body_with_schema(schema, $mime_type)
     }};
 }
 
@@ -63,7 +106,8 @@ impl<'r> OpenApiFromData<'r> for Cow<'r, str> {
 
 impl<'r> OpenApiFromData<'r> for Vec<u8> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, Vec<u8>, get_mime_type(mime_type, DEFAULT_MIME_TYPE))
+        let schema = gen.json_schema::<Vec<u8>>();
+        binary_request_body(gen, schema, get_mime_type(mime_type, DEFAULT_MIME_TYPE))
     }
 }
 
@@ -88,46 +132,58 @@ impl<'r, T: OpenApiFromData<'r>> OpenApiFromData<'r> for Option<T> {
     }
 }
 
-// Waiting for https://github.com/GREsau/schemars/issues/103
+/// A `TempFile` body is documented as `{ "type": "string", "format": "binary" }`, so it is shown
+/// as a file upload instead of an array of integers.
 impl<'r> OpenApiFromData<'r> for rocket::fs::TempFile<'r> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        Vec::<u8>::request_body(gen, mime_type)
+        binary_request_body(
+            gen,
+            binary_schema(),
+            get_mime_type(mime_type, DEFAULT_MIME_TYPE),
+        )
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<rocket::fs::TempFile<'r>> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        rocket::fs::TempFile::request_body(gen, mime_type)
+        let body = rocket::fs::TempFile::request_body(gen, mime_type);
+        with_data_limit(gen, "file", body)
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<Cow<'r, str>> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, str, get_mime_type(mime_type, DEFAULT_MIME_TYPE))
+        let body = fn_request_body!(gen, str, get_mime_type(mime_type, DEFAULT_MIME_TYPE));
+        with_data_limit(gen, "string", body)
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r str> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, str, get_mime_type(mime_type, DEFAULT_MIME_TYPE))
+        let body = fn_request_body!(gen, str, get_mime_type(mime_type, DEFAULT_MIME_TYPE));
+        with_data_limit(gen, "string", body)
     }
 }
 // See: https://github.com/GREsau/schemars/issues/103
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r rocket::http::RawStr> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        <&'r rocket::http::RawStr>::request_body(gen, mime_type)
+        let body = <&'r rocket::http::RawStr>::request_body(gen, mime_type);
+        with_data_limit(gen, "string", body)
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r [u8]> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        Vec::<u8>::request_body(gen, mime_type)
+        let body = Vec::<u8>::request_body(gen, mime_type);
+        with_data_limit(gen, "bytes", body)
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<String> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        String::request_body(gen, mime_type)
+        let body = String::request_body(gen, mime_type);
+        with_data_limit(gen, "string", body)
     }
 }
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<Vec<u8>> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        Vec::<u8>::request_body(gen, mime_type)
+        let body = Vec::<u8>::request_body(gen, mime_type);
+        with_data_limit(gen, "bytes", body)
     }
 }
 
@@ -145,15 +201,94 @@ impl<'r> OpenApiFromData<'r> for Data<'r> {
 }
 
 // `OpenApiFromForm` is correct, not a mistake, as Rocket requires `FromForm`.
+/// A `multipart/form-data` form documents the `encoding` of its file parts,
+/// see [`multipart_encoding`](crate::util::multipart_encoding).
 impl<'r, T: JsonSchema + super::OpenApiFromForm<'r>> OpenApiFromData<'r> for rocket::form::Form<T> {
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, T, get_mime_type(mime_type, DEFAULT_MIME_TYPE))
+        let mime_type = get_mime_type(mime_type, DEFAULT_FORM_MIME_TYPE);
+        let mut body = fn_request_body!(gen, T, mime_type)?;
+        if mime_type.starts_with("multipart/form-data") {
+            for media in body.content.values_mut() {
+                if let Some(schema) = &media.schema {
+                    media.encoding = multipart_encoding(gen, schema);
+                }
+            }
+        }
+        Ok(body)
+    }
+}
+
+/// Document the request body of a `Form<Strict<T>>`: other fields are not allowed
+/// (`additionalProperties: false`), and Rocket does not use defaults for missing fields, except
+/// an empty list.
+///
+/// `#[openapi]` uses this for `Form<Strict<T>>` data guards, as an implementation of
+/// `OpenApiFromData` would conflict with the one of `Form<T>`.
+///
+/// # Errors
+///
+/// Returns an error if the request body of `Form<T>` can not be created.
+pub fn strict_form_request_body<'r, T: JsonSchema + super::OpenApiFromForm<'r>>(
+    gen: &mut OpenApiGenerator,
+    mime_type: Option<&str>,
+) -> Result {
+    form_request_body_with_mode::<T>(gen, mime_type, true)
+}
+
+/// Document the request body of a `Form<Lenient<T>>`: other fields are ignored
+/// (`additionalProperties: true`), and missing booleans and lists get their default (`false` and
+/// an empty list), so they are not required.
+///
+/// `#[openapi]` uses this for `Form<Lenient<T>>` data guards, as an implementation of
+/// `OpenApiFromData` would conflict with the one of `Form<T>`.
+///
+/// # Errors
+///
+/// Returns an error if the request body of `Form<T>` can not be created.
+pub fn lenient_form_request_body<'r, T: JsonSchema + super::OpenApiFromForm<'r>>(
+    gen: &mut OpenApiGenerator,
+    mime_type: Option<&str>,
+) -> Result {
+    form_request_body_with_mode::<T>(gen, mime_type, false)
+}
+
+fn form_request_body_with_mode<'r, T: JsonSchema + super::OpenApiFromForm<'r>>(
+    gen: &mut OpenApiGenerator,
+    mime_type: Option<&str>,
+    strict: bool,
+) -> Result {
+    let mut body = rocket::form::Form::<T>::request_body(gen, mime_type)?;
+    for media in body.content.values_mut() {
+        // Change a copy of the schema, the schema of `T` can also be used by other forms.
+        let mut schema = gen.json_schema_no_ref::<T>();
+        if !schema.has_type(InstanceType::Object) {
+            continue;
+        }
+        let object = schema.object();
+        object.additional_properties = Some(Box::new(Schema::Bool(!strict)));
+        // Missing lists are always empty, missing booleans are `false` in lenient forms.
+        let properties = &object.properties;
+        object.required.retain(|name| {
+            !properties.get(name).is_some_and(|property| match property {
+                Schema::Object(property) => {
+                    property.has_type(InstanceType::Array)
+                        || (!strict && property.has_type(InstanceType::Boolean))
+                }
+                Schema::Bool(_) => false,
+            })
+        });
+        media.schema = Some(schema);
     }
+    Ok(body)
 }
 
+/// A `Json<serde_json::Value>` body is documented as a free-form object.
+/// Dictionaries like `Json<HashMap<String, T>>` and `Json<BTreeMap<String, T>>` are documented as
+/// an object with `additionalProperties` set to the schema of `T`.
 impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r> for Json<T> {
-    fn request_body(gen: &mut OpenApiGenerator, _mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, T, "application/json")
+    fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
+        let schema = json_schema_or_free_form::<T>(gen);
+        request_body_with_schema(schema, get_mime_type(mime_type, "application/json"))
     }
 }
 
@@ -161,7 +296,8 @@ impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r> for Json<T> {
 impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r>
     for rocket::serde::msgpack::MsgPack<T>
 {
-    fn request_body(gen: &mut OpenApiGenerator, _mime_type: Option<&str>) -> Result {
-        fn_request_body!(gen, T, "application/msgpack")
+    fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result {
+        let schema = json_schema_or_free_form::<T>(gen);
+        request_body_with_schema(schema, get_mime_type(mime_type, "application/msgpack"))
     }
 }
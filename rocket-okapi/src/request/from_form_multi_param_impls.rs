@@ -1,5 +1,5 @@
 use crate::gen::OpenApiGenerator;
-use okapi::openapi3::{Object, Parameter, ParameterValue};
+use okapi::openapi3::{Object, Parameter, ParameterStyle, ParameterValue};
 use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
 use schemars::JsonSchema;
 
@@ -26,7 +26,9 @@ where
     let mut parameter_list: Vec<Parameter> = Vec::new();
     match &schema.instance_type {
         Some(SingleOrVec::Single(instance_type)) => {
-            if **instance_type == InstanceType::Object {
+            if **instance_type == InstanceType::Object && is_map_schema(&schema) {
+                parameter_list.push(map_parameter_from_schema(schema, name));
+            } else if **instance_type == InstanceType::Object {
                 if let Some(object) = schema.object {
                     properties = object.properties;
                 }
@@ -58,6 +60,26 @@ where
     parameter_list
 }
 
+/// Dictionaries (like `HashMap` and `BTreeMap`) have no fixed properties, only
+/// `additionalProperties`.
+fn is_map_schema(schema: &SchemaObject) -> bool {
+    schema.object.as_ref().is_some_and(|object| {
+        object.properties.is_empty() && object.additional_properties.is_some()
+    })
+}
+
+/// Document a dictionary as one free-form query object, where every key-value pair is a separate
+/// query parameter. See: <https://swagger.io/docs/specification/serialization/#query>
+/// The dictionary can be empty, so the parameter is never required.
+fn map_parameter_from_schema(schema: SchemaObject, name: String) -> Parameter {
+    let mut parameter = parameter_from_schema(schema, name, false);
+    if let ParameterValue::Schema { style, explode, .. } = &mut parameter.value {
+        *style = Some(ParameterStyle::Form);
+        *explode = Some(true);
+    }
+    parameter
+}
+
 fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool) -> Parameter {
     // Check if parameter is optional (only is not already optional)
     if required {
@@ -68,6 +90,15 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
                 }
             }
         }
+        if has_instance_type(&schema, InstanceType::Null) {
+            required = false;
+        }
+    }
+    // Rocket parses every value of a list as a repeated key (`?id=1&id=2`), and a missing list
+    // as an empty one.
+    let is_array = has_instance_type(&schema, InstanceType::Array);
+    if is_array {
+        required = false;
     }
     let description = schema.metadata.as_ref().and_then(|m| m.description.clone());
     Parameter {
@@ -78,8 +109,8 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         deprecated: false,
         allow_empty_value: false,
         value: ParameterValue::Schema {
-            style: None,
-            explode: None,
+            style: is_array.then_some(ParameterStyle::Form),
+            explode: is_array.then_some(true),
             allow_reserved: false,
             schema,
             example: None,
@@ -88,3 +119,12 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         extensions: Object::default(),
     }
 }
+
+/// If the schema allows values of the type, like `"type": ["array", "null"]` for an array.
+fn has_instance_type(schema: &SchemaObject, instance_type: InstanceType) -> bool {
+    match &schema.instance_type {
+        Some(SingleOrVec::Single(single)) => **single == instance_type,
+        Some(SingleOrVec::Vec(types)) => types.contains(&instance_type),
+        None => false,
+    }
+}
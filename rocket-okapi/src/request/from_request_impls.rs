@@ -127,6 +127,30 @@ impl<'r> OpenApiFromRequest<'r> for rocket::Shutdown {
     }
 }
 
+// The message is read from the `_flash` cookie, which is set by a `Flash` response.
+impl<'r> OpenApiFromRequest<'r> for rocket::request::FlashMessage<'r> {
+    fn from_request_input(gen: &mut OpenApiGenerator, _name: String, required: bool) -> Result {
+        let schema = gen.json_schema::<String>();
+        Ok(RequestHeaderInput::Parameter(Parameter {
+            name: "_flash".to_owned(),
+            location: "cookie".to_owned(),
+            description: Some("The flash message, set by a previous response.".to_owned()),
+            required,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema,
+                example: None,
+                examples: None,
+            },
+            extensions: Object::default(),
+        }))
+    }
+}
+
 impl<'r, T: Send + Sync + 'static> OpenApiFromRequest<'r> for &'r rocket::State<T> {
     fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> Result {
         Ok(RequestHeaderInput::None)
@@ -13,17 +13,51 @@ use okapi::openapi3::{Parameter, RequestBody, Responses, SecurityRequirement, Se
 /// [Form Guard](https://api.rocket.rs/master/rocket/form/trait.FromForm.html).
 pub use from_form_multi_param_impls::get_nested_form_parameters;
 
+pub use from_data_impls::{lenient_form_request_body, strict_form_request_body};
+
 /// This trait is used to document the request body that implements
 /// [`FromData`](rocket::data::FromData).
 pub trait OpenApiFromData<'r>: rocket::data::FromData<'r> {
     /// Return a [`RequestBody`] containing the information required to document the
     /// [`FromData`](rocket::data::FromData) object.
+    ///
+    /// `mime_type` is the media type of the route's `format` attribute, parsed by Rocket, so
+    /// `format = "json"` is passed as `Some("application/json")`. It should be used as the key of
+    /// the `content` of the request body, if it is set.
     fn request_body(gen: &mut OpenApiGenerator, mime_type: Option<&str>) -> Result<RequestBody>;
 }
 
 /// This trait is used to document a dynamic part of a path that implements
 /// [`FromParam`](rocket::request::FromParam).
 /// For example `<user_id>` in route path.
+///
+/// This is implemented for all types that implement `JsonSchema`. Newtypes (like a validated
+/// id) can use `#[derive(OpenApiFromParam)]`, which documents them like their inner type.
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_okapi::{openapi, openapi_get_spec, OpenApiFromParam};
+///
+/// #[derive(OpenApiFromParam)]
+/// struct UserId(u64);
+///
+/// impl<'r> rocket::request::FromParam<'r> for UserId {
+///     type Error = std::num::ParseIntError;
+///
+///     fn from_param(param: &'r str) -> Result<Self, Self::Error> {
+///         param.parse().map(UserId)
+///     }
+/// }
+///
+/// #[openapi]
+/// #[get("/users/<id>")]
+/// fn get_user(id: UserId) -> String {
+///     id.0.to_string()
+/// }
+///
+/// let spec = openapi_get_spec![get_user];
+/// assert!(spec.paths.contains_key("/users/{id}"));
+/// ```
 pub trait OpenApiFromParam<'r>: rocket::request::FromParam<'r> {
     /// Return a [`Parameter`] containing the information required to document the
     /// [`FromParam`](rocket::request::FromParam) path parameter.
@@ -33,6 +67,42 @@ pub trait OpenApiFromParam<'r>: rocket::request::FromParam<'r> {
 /// This trait is used to document a dynamic path segment that implements
 /// [`FromSegments`](rocket::request::FromSegments).
 /// For example `<param..>` in route path.
+///
+/// This is implemented for all types that implement `JsonSchema`, like `PathBuf`. Other types
+/// can use `#[derive(OpenApiFromSegments)]`, which documents them like a `PathBuf`.
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket::http::uri::{fmt::Path, Segments};
+/// use rocket_okapi::{openapi, openapi_get_spec, OpenApiFromSegments};
+///
+/// #[derive(OpenApiFromSegments)]
+/// struct FilePath(Vec<String>);
+///
+/// impl<'r> rocket::request::FromSegments<'r> for FilePath {
+///     type Error = std::convert::Infallible;
+///
+///     fn from_segments(segments: Segments<'r, Path>) -> Result<Self, Self::Error> {
+///         Ok(FilePath(segments.map(str::to_owned).collect()))
+///     }
+/// }
+///
+/// #[openapi]
+/// #[get("/files/<path..>")]
+/// fn get_file(path: FilePath) -> String {
+///     path.0.join("/")
+/// }
+///
+/// #[openapi]
+/// #[get("/static/<path..>")]
+/// fn get_static(path: std::path::PathBuf) -> String {
+///     path.display().to_string()
+/// }
+///
+/// let spec = openapi_get_spec![get_file, get_static];
+/// assert!(spec.paths.contains_key("/files/{path}"));
+/// assert!(spec.paths.contains_key("/static/{path}"));
+/// ```
 pub trait OpenApiFromSegments<'r>: rocket::request::FromSegments<'r> {
     /// Return a [`Parameter`] containing the information required to document the
     /// [`FromSegments`](rocket::request::FromSegments) path parameter.
@@ -42,6 +112,10 @@ pub trait OpenApiFromSegments<'r>: rocket::request::FromSegments<'r> {
 /// This trait is used to document a query guard segment that implements
 /// [`FromFormField`](rocket::form::FromFormField).
 /// For example `?<param>` in the route's query part.
+///
+/// This is implemented for all types that implement `JsonSchema`. Custom types (that can also be
+/// used in query structs) can use `#[derive(OpenApiFromFormField)]`, which implements
+/// `JsonSchema` for them as a string.
 pub trait OpenApiFromFormField<'r>: rocket::form::FromFormField<'r> {
     /// Return a [`Parameter`] containing the information required to document the
     /// [`FromFormField`](rocket::form::FromFormField) route's query part.
@@ -55,6 +129,10 @@ pub trait OpenApiFromFormField<'r>: rocket::form::FromFormField<'r> {
 /// This trait is used to document multiple query guard segments that implement
 /// [`FromForm`](rocket::form::FromForm).
 /// For example `?<param>` in the route's query part.
+///
+/// This is implemented for all types that implement `JsonSchema`, the fields of structs are
+/// documented with the names of their schema. Use the `#[form_schema]` attribute to use the
+/// names Rocket parses (like the names of `#[field(name = "...")]`) instead.
 pub trait OpenApiFromForm<'r>: rocket::form::FromForm<'r> {
     /// Return a [`Vec<Parameter>`] containing the information required to document the
     /// [`FromForm`](rocket::form::FromForm) route's query part.
@@ -0,0 +1,578 @@
+use okapi::instance::validate_instance;
+use okapi::openapi3::{OpenApi, Parameter, ParameterValue, RequestBody, Response};
+use okapi::Map;
+use rocket::http::{ContentType, Method, Status};
+use rocket::response::content::Custom;
+use rocket::route::{Handler, Outcome};
+use rocket::{Data, Request, Route};
+use schemars::schema::{InstanceType, SchemaObject, SingleOrVec};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// The number of bytes of the request body that are read to validate it. The body can only be
+/// read once, and Rocket does not allow to look at more of it before the handler reads it
+/// (see `rocket::data::PEEK_BYTES`).
+pub const BODY_VALIDATION_LIMIT: usize = 512;
+
+/// A part of a request that does not match the OpenAPI specification.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestValidationError {
+    /// Where the problem is: `path`, `query`, `header`, `cookie` or `body`.
+    #[serde(rename = "in")]
+    pub location: String,
+    /// The name of the parameter, `None` for the request body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Describes the problem.
+    pub message: String,
+}
+
+/// Validates requests against the operations of an OpenAPI document, before they reach their
+/// handler. Invalid requests get a `400 Bad Request` response with a JSON body like:
+///
+/// ```json
+/// {
+///   "error": {
+///     "code": 400,
+///     "reason": "Bad Request",
+///     "description": "The request does not match the OpenAPI specification."
+///   },
+///   "errors": [
+///     { "in": "query", "name": "limit", "message": "Expected a value of type `integer`." }
+///   ]
+/// }
+/// ```
+///
+/// The path, query, header and cookie parameters are validated against their schemas (values are
+/// parsed according to the `type` of the schema), and required parameters and request bodies
+/// must be present. Requests that do not match an operation of the document reach their handler
+/// without changes.
+///
+/// JSON request bodies are only validated if they are at most [`BODY_VALIDATION_LIMIT`] (512)
+/// bytes, as Rocket does not allow to read more of the body before the handler. Larger bodies
+/// are logged as warnings, or rejected with [`reject_large_bodies`](Self::reject_large_bodies).
+///
+/// The requests are validated by the handlers of the routes given to
+/// [`validate_routes`](Self::validate_routes), so the request is not changed:
+///
+/// ```rust,no_run
+/// use rocket_okapi::openapi_get_routes_spec;
+/// use rocket_okapi::request_validation::RequestValidator;
+///
+/// let (routes, spec) = openapi_get_routes_spec![];
+/// let validator = RequestValidator::new("/api", spec);
+/// rocket::build().mount("/api", validator.validate_routes(routes));
+/// ```
+///
+/// Valid requests reach their handler, invalid path or query parameters and request bodies get
+/// the errors:
+///
+/// ```rust
+/// use rocket::http::{ContentType, Status};
+/// use rocket::local::blocking::{Client, LocalResponse};
+/// use rocket::serde::json::Json;
+/// use rocket::{get, post};
+/// use rocket_okapi::request_validation::RequestValidator;
+/// use rocket_okapi::{openapi, openapi_get_routes_spec};
+/// use schemars::JsonSchema;
+/// use serde::Deserialize;
+/// use serde_json::{json, Value};
+///
+/// #[derive(Deserialize, JsonSchema)]
+/// struct Item {
+///     name: String,
+/// }
+///
+/// #[openapi]
+/// #[get("/items/<id>?<limit>")]
+/// fn get_item(id: u32, limit: Option<u16>) -> String {
+///     format!("{} {:?}", id, limit)
+/// }
+///
+/// #[openapi]
+/// #[post("/items", data = "<item>")]
+/// fn create_item(item: Json<Item>) -> String {
+///     item.into_inner().name
+/// }
+///
+/// fn errors(response: LocalResponse<'_>) -> Value {
+///     assert_eq!(response.status(), Status::BadRequest);
+///     response.into_json::<Value>().unwrap()["errors"].clone()
+/// }
+///
+/// let (routes, spec) = openapi_get_routes_spec![get_item, create_item];
+/// let validator = RequestValidator::new("/api", spec).reject_large_bodies();
+/// let rocket = rocket::build().mount("/api", validator.validate_routes(routes));
+/// let client = Client::tracked(rocket).unwrap();
+///
+/// let response = client.get("/api/items/1?limit=5").dispatch();
+/// assert_eq!(response.into_string().unwrap(), "1 Some(5)");
+/// let response = client
+///     .post("/api/items")
+///     .header(ContentType::JSON)
+///     .body(r#"{"name": "Pen"}"#)
+///     .dispatch();
+/// assert_eq!(response.into_string().unwrap(), "Pen");
+///
+/// let path_errors = errors(client.get("/api/items/one").dispatch());
+/// assert_eq!(path_errors[0]["in"], json!("path"));
+/// assert_eq!(path_errors[0]["name"], json!("id"));
+/// let query_errors = errors(client.get("/api/items/1?limit=many").dispatch());
+/// assert_eq!(query_errors[0]["in"], json!("query"));
+/// assert_eq!(query_errors[0]["name"], json!("limit"));
+/// let body_errors = errors(
+///     client
+///         .post("/api/items")
+///         .header(ContentType::JSON)
+///         .body(r#"{"name": 5}"#)
+///         .dispatch(),
+/// );
+/// assert_eq!(body_errors[0]["in"], json!("body"));
+///
+/// // A body that is too large to validate.
+/// let name = "a".repeat(600);
+/// let body_errors = errors(
+///     client
+///         .post("/api/items")
+///         .header(ContentType::JSON)
+///         .body(json!({ "name": name }).to_string())
+///         .dispatch(),
+/// );
+/// assert_eq!(body_errors[0]["in"], json!("body"));
+/// ```
+#[derive(Clone)]
+pub struct RequestValidator {
+    operations: Arc<SpecOperations>,
+    reject_large_bodies: bool,
+}
+
+/// The operations of a document, to find the operation of a request.
+pub(crate) struct SpecOperations {
+    base_path: Vec<String>,
+    pub(crate) spec: OpenApi,
+    operations: Vec<OperationRules>,
+}
+
+/// A segment of a path template.
+enum Segment {
+    /// A segment that must match exactly, like `users`.
+    Literal(String),
+    /// A parameter, like `{id}`.
+    Parameter(String),
+}
+
+/// The parts of an operation that are needed to validate a request or response, with all
+/// references resolved.
+pub(crate) struct OperationRules {
+    method: Method,
+    /// The path template, split in segments.
+    segments: Vec<Segment>,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) request_body: Option<RequestBody>,
+    pub(crate) responses: Map<String, Response>,
+}
+
+impl RequestValidator {
+    /// Create a validator for the requests of the operations in `spec`. `base_path` is the path
+    /// the routes of the spec are mounted at, like `/api` (or `/`).
+    #[must_use]
+    pub fn new(base_path: &str, spec: OpenApi) -> Self {
+        RequestValidator {
+            operations: Arc::new(SpecOperations::new(base_path, spec)),
+            reject_large_bodies: false,
+        }
+    }
+
+    /// Respond with an error to JSON request bodies that are larger than
+    /// [`BODY_VALIDATION_LIMIT`], instead of passing them to the handler without validating them.
+    #[must_use]
+    pub fn reject_large_bodies(mut self) -> Self {
+        self.reject_large_bodies = true;
+        self
+    }
+
+    /// Validate the requests of the given routes, before they reach their handlers.
+    pub fn validate_routes(&self, routes: impl Into<Vec<Route>>) -> Vec<Route> {
+        routes
+            .into()
+            .into_iter()
+            .map(|mut route| {
+                route.handler = Box::new(ValidatingHandler {
+                    validator: self.clone(),
+                    handler: route.handler,
+                });
+                route
+            })
+            .collect()
+    }
+}
+
+impl SpecOperations {
+    pub(crate) fn new(base_path: &str, spec: OpenApi) -> Self {
+        let components = spec.components.clone().unwrap_or_default();
+        let mut operations = Vec::new();
+        for (path, path_item) in &spec.paths {
+            let methods = vec![
+                (Method::Get, &path_item.get),
+                (Method::Put, &path_item.put),
+                (Method::Post, &path_item.post),
+                (Method::Delete, &path_item.delete),
+                (Method::Options, &path_item.options),
+                (Method::Head, &path_item.head),
+                (Method::Patch, &path_item.patch),
+                (Method::Trace, &path_item.trace),
+            ];
+            for (method, operation) in methods {
+                let operation = match operation {
+                    Some(operation) => operation,
+                    None => continue,
+                };
+                // Operation parameters override path item parameters with the same name and
+                // location.
+                let mut parameters: Vec<Parameter> = operation
+                    .parameters
+                    .iter()
+                    .filter_map(|parameter| parameter.resolve(&components).ok())
+                    .cloned()
+                    .collect();
+                for parameter in path_item
+                    .parameters
+                    .iter()
+                    .filter_map(|parameter| parameter.resolve(&components).ok())
+                {
+                    if !parameters
+                        .iter()
+                        .any(|p| p.name == parameter.name && p.location == parameter.location)
+                    {
+                        parameters.push(parameter.clone());
+                    }
+                }
+                let request_body = operation
+                    .request_body
+                    .as_ref()
+                    .and_then(|body| body.resolve(&components).ok())
+                    .cloned();
+                let default = operation
+                    .responses
+                    .default
+                    .as_ref()
+                    .map(|response| ("default", response));
+                let responses = operation
+                    .responses
+                    .responses
+                    .iter()
+                    .map(|(status, response)| (status.as_str(), response))
+                    .chain(default)
+                    .filter_map(|(status, response)| {
+                        let response = response.resolve(&components).ok()?;
+                        Some((status.to_owned(), response.clone()))
+                    })
+                    .collect();
+                operations.push(OperationRules {
+                    method,
+                    segments: split_template(path),
+                    parameters,
+                    request_body,
+                    responses,
+                });
+            }
+        }
+        SpecOperations {
+            base_path: split_path(base_path),
+            spec,
+            operations,
+        }
+    }
+
+    /// Find the operation for a request, and the values of its path parameters.
+    pub(crate) fn find<'a>(
+        &self,
+        method: Method,
+        path: &[&'a str],
+    ) -> Option<(&OperationRules, Vec<(&str, &'a str)>)> {
+        if path.len() < self.base_path.len()
+            || path.iter().zip(&self.base_path).any(|(a, b)| a != b)
+        {
+            return None;
+        }
+        let path = &path[self.base_path.len()..];
+        // Prefer the template with the most literal segments, like Rocket does for `/users/me`
+        // and `/users/<id>`.
+        self.operations
+            .iter()
+            .filter(|operation| operation.method == method)
+            .filter(|operation| operation.segments.len() == path.len())
+            .filter(|operation| {
+                operation
+                    .segments
+                    .iter()
+                    .zip(path)
+                    .all(|(segment, value)| match segment {
+                        Segment::Literal(literal) => literal == value,
+                        Segment::Parameter(_) => true,
+                    })
+            })
+            .max_by_key(|operation| {
+                operation
+                    .segments
+                    .iter()
+                    .filter(|s| matches!(s, Segment::Literal(_)))
+                    .count()
+            })
+            .map(|operation| {
+                let parameters = operation
+                    .segments
+                    .iter()
+                    .zip(path)
+                    .filter_map(|(segment, value)| match segment {
+                        Segment::Literal(_) => None,
+                        Segment::Parameter(name) => Some((name.as_str(), *value)),
+                    })
+                    .collect();
+                (operation, parameters)
+            })
+    }
+}
+
+impl RequestValidator {
+    fn validate_parameter(
+        &self,
+        parameter: &Parameter,
+        values: &[&str],
+        errors: &mut Vec<RequestValidationError>,
+    ) {
+        let mut error = |message: String| {
+            errors.push(RequestValidationError {
+                location: parameter.location.clone(),
+                name: Some(parameter.name.clone()),
+                message,
+            })
+        };
+        if values.is_empty() {
+            if parameter.required {
+                error("Missing required parameter.".to_owned());
+            }
+            return;
+        }
+        let schema = match &parameter.value {
+            ParameterValue::Schema { schema, .. } => schema,
+            ParameterValue::Content { .. } => return,
+        };
+        let value = if self.instance_type(schema) == Some(InstanceType::Array) {
+            let item_schema = schema
+                .array
+                .as_ref()
+                .and_then(|array| match &array.items {
+                    Some(SingleOrVec::Single(items)) => Some(items.clone().into_object()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.parse_value(&item_schema, value))
+                    .collect(),
+            )
+        } else {
+            self.parse_value(schema, values[0])
+        };
+        if let Err(instance_errors) = validate_instance(&self.operations.spec, schema, &value) {
+            for instance_error in instance_errors {
+                error(instance_error.to_string());
+            }
+        }
+    }
+
+    /// Parse the value of a parameter according to the `type` of its schema. Values that can not
+    /// be parsed are kept as strings, so the validation reports the wrong type.
+    fn parse_value(&self, schema: &SchemaObject, value: &str) -> Value {
+        let parsed = match self.instance_type(schema) {
+            Some(InstanceType::Integer) | Some(InstanceType::Number) => {
+                serde_json::from_str::<serde_json::Number>(value)
+                    .ok()
+                    .map(Value::Number)
+            }
+            Some(InstanceType::Boolean) => value.parse().ok().map(Value::Bool),
+            Some(InstanceType::Null) if value.is_empty() => Some(Value::Null),
+            _ => None,
+        };
+        parsed.unwrap_or_else(|| Value::String(value.to_owned()))
+    }
+
+    /// The `type` of a schema, looking up `$ref`s. `None` if it has no (single) `type`.
+    fn instance_type(&self, schema: &SchemaObject) -> Option<InstanceType> {
+        let mut schema = schema;
+        for _ in 0..16 {
+            if let Some(SingleOrVec::Single(instance_type)) = &schema.instance_type {
+                return Some(**instance_type);
+            }
+            let reference = schema.reference.as_ref()?;
+            let name = reference.strip_prefix("#/components/schemas/")?;
+            schema = self
+                .operations
+                .spec
+                .components
+                .as_ref()?
+                .schemas
+                .get(name)?;
+        }
+        None
+    }
+
+    async fn validate_body(
+        &self,
+        request_body: &RequestBody,
+        content_type: Option<&ContentType>,
+        data: &mut Data<'_>,
+        errors: &mut Vec<RequestValidationError>,
+    ) {
+        let mut error = |message: String| {
+            errors.push(RequestValidationError {
+                location: "body".to_owned(),
+                name: None,
+                message,
+            })
+        };
+        let body = data.peek(BODY_VALIDATION_LIMIT).await;
+        if body.is_empty() {
+            if request_body.required {
+                error("Missing required request body.".to_owned());
+            }
+            return;
+        }
+        let media_type = match content_type {
+            Some(content_type) => format!("{}/{}", content_type.top(), content_type.sub()),
+            None => return,
+        };
+        let schema = match request_body.content.get(&media_type) {
+            Some(content) if content_type.is_some_and(|ct| ct.is_json()) => &content.schema,
+            _ => return,
+        };
+        if !data.peek_complete() {
+            if self.reject_large_bodies {
+                error(format!(
+                    "The request body is larger than {} bytes, so it can not be validated.",
+                    BODY_VALIDATION_LIMIT
+                ));
+            } else {
+                log::warn!(
+                    "The request body is larger than {} bytes, so it is not validated.",
+                    BODY_VALIDATION_LIMIT
+                );
+            }
+            return;
+        }
+        let body = data.peek(BODY_VALIDATION_LIMIT).await;
+        let value: Value = match serde_json::from_slice(body) {
+            Ok(value) => value,
+            Err(err) => return error(format!("Invalid JSON: {}.", err)),
+        };
+        if let Some(schema) = schema {
+            if let Err(instance_errors) = validate_instance(&self.operations.spec, schema, &value) {
+                for instance_error in instance_errors {
+                    error(instance_error.to_string());
+                }
+            }
+        }
+    }
+}
+
+impl RequestValidator {
+    /// Validate the request against its operation, if it has one.
+    async fn validate(
+        &self,
+        req: &Request<'_>,
+        data: &mut Data<'_>,
+    ) -> Vec<RequestValidationError> {
+        let mut errors = Vec::new();
+        let path: Vec<&str> = req.uri().path().segments().collect();
+        let (operation, path_parameters) = match self.operations.find(req.method(), &path) {
+            Some(found) => found,
+            None => return errors,
+        };
+        for parameter in &operation.parameters {
+            let values: Vec<&str> = match parameter.location.as_str() {
+                "path" => path_parameters
+                    .iter()
+                    .filter(|(name, _)| *name == parameter.name)
+                    .map(|(_, value)| *value)
+                    .collect(),
+                "query" => req
+                    .query_fields()
+                    .filter(|field| field.name.source().as_str() == parameter.name)
+                    .map(|field| field.value)
+                    .collect(),
+                "header" => req.headers().get(&parameter.name).collect(),
+                "cookie" => req
+                    .cookies()
+                    .get(&parameter.name)
+                    .map(|cookie| cookie.value())
+                    .into_iter()
+                    .collect(),
+                _ => continue,
+            };
+            self.validate_parameter(parameter, &values, &mut errors);
+        }
+        if let Some(request_body) = &operation.request_body {
+            self.validate_body(request_body, req.content_type(), data, &mut errors)
+                .await;
+        }
+        errors
+    }
+}
+
+/// A handler that only calls `handler` if the request is valid, and else responds with the
+/// validation errors.
+#[derive(Clone)]
+struct ValidatingHandler {
+    validator: RequestValidator,
+    handler: Box<dyn Handler>,
+}
+
+#[rocket::async_trait]
+impl Handler for ValidatingHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, mut data: Data<'r>) -> Outcome<'r> {
+        let errors = self.validator.validate(req, &mut data).await;
+        if errors.is_empty() {
+            return self.handler.handle(req, data).await;
+        }
+        let body = serde_json::json!({
+            "error": {
+                "code": 400,
+                "reason": "Bad Request",
+                "description": "The request does not match the OpenAPI specification.",
+            },
+            "errors": errors,
+        });
+        Outcome::from(
+            req,
+            (
+                Status::BadRequest,
+                Custom(ContentType::JSON, body.to_string()),
+            ),
+        )
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn split_template(path: &str) -> Vec<Segment> {
+    split_path(path)
+        .into_iter()
+        .map(|segment| {
+            match segment
+                .strip_prefix('{')
+                .and_then(|name| name.strip_suffix('}'))
+            {
+                Some(name) => Segment::Parameter(name.to_owned()),
+                None => Segment::Literal(segment),
+            }
+        })
+        .collect()
+}
@@ -1,5 +1,8 @@
+mod prometheus;
 mod responder_impls;
 
+pub use prometheus::PrometheusText;
+
 use super::gen::OpenApiGenerator;
 use super::Result;
 use okapi::openapi3::Responses;
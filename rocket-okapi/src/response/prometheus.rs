@@ -0,0 +1,47 @@
+use super::OpenApiResponderInner;
+use crate::gen::OpenApiGenerator;
+use crate::util::add_schema_response;
+use okapi::openapi3::Responses;
+use rocket::http::ContentType;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+/// The media type of the Prometheus text exposition format.
+const PROMETHEUS_TEXT: &str = "text/plain; version=0.0.4";
+
+/// A responder for the metrics of a `/metrics` route, in the text format of Prometheus
+/// (`text/plain; version=0.0.4`), like the output of `prometheus::TextEncoder`.
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_okapi::openapi;
+/// use rocket_okapi::response::PrometheusText;
+///
+/// #[openapi(tag = "Observability")]
+/// #[get("/metrics")]
+/// fn metrics() -> PrometheusText {
+///     PrometheusText("# TYPE requests_total counter\nrequests_total 7\n".to_owned())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrometheusText(pub String);
+
+impl<'r> Responder<'r, 'static> for PrometheusText {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let content_type = ContentType::with_params(
+            "text",
+            "plain",
+            vec![("version", "0.0.4"), ("charset", "utf-8")],
+        );
+        (content_type, self.0).respond_to(req)
+    }
+}
+
+impl OpenApiResponderInner for PrometheusText {
+    fn responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        let mut responses = Responses::default();
+        let schema = gen.json_schema::<String>();
+        add_schema_response(&mut responses, 200, PROMETHEUS_TEXT, schema)?;
+        Ok(responses)
+    }
+}
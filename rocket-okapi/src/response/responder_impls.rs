@@ -3,8 +3,8 @@ use crate::{
     gen::OpenApiGenerator,
     util::{
         add_content_response, add_default_response_code, add_schema_response,
-        change_all_responses_to_default, ensure_status_code_exists, produce_any_responses,
-        set_content_type, set_status_code,
+        change_all_responses_to_default, ensure_status_code_exists, free_form_object_schema,
+        json_schema_or_free_form, produce_any_responses, set_content_type, set_status_code,
     },
 };
 use okapi::openapi3::Responses;
@@ -85,7 +85,7 @@ impl OpenApiResponderInner for () {
     }
 }
 
-impl<'r, 'o: 'r, T> OpenApiResponderInner for std::borrow::Cow<'o, T>
+impl<'o, T> OpenApiResponderInner for std::borrow::Cow<'o, T>
 where
     T: OpenApiResponderInner + Clone,
 {
@@ -104,7 +104,7 @@ impl<T: OpenApiResponderInner> OpenApiResponderInner for Option<T> {
     }
 }
 
-impl<'r, 'o, T, E> OpenApiResponderInner for std::result::Result<T, E>
+impl<T, E> OpenApiResponderInner for std::result::Result<T, E>
 where
     T: OpenApiResponderInner,
     E: OpenApiResponderInner,
@@ -116,7 +116,7 @@ where
     }
 }
 
-impl<'r, 'o, L, R> OpenApiResponderInner for either::Either<L, R>
+impl<L, R> OpenApiResponderInner for either::Either<L, R>
 where
     L: OpenApiResponderInner,
     R: OpenApiResponderInner,
@@ -158,8 +158,8 @@ impl<R: OpenApiResponderInner> OpenApiResponderInner for (rocket::http::Status,
 impl OpenApiResponderInner for Value {
     fn responses(_gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        let schema = schemars::schema::Schema::Bool(true);
-        add_schema_response(&mut responses, 200, "application/json", schema.into())?;
+        let schema = free_form_object_schema();
+        add_schema_response(&mut responses, 200, "application/json", schema)?;
         Ok(responses)
     }
 }
@@ -321,10 +321,11 @@ where
 /// Serializes the wrapped value into JSON. Returns a response with `Content-Type` `JSON` and a
 /// fixed-size body with the serialized value. If serialization fails,
 /// an `Err` of `Status::InternalServerError` is returned.
+/// A `Json<serde_json::Value>` response is documented as a free-form object.
 impl<T: Serialize + JsonSchema + Send> OpenApiResponderInner for Json<T> {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        let schema = gen.json_schema::<T>();
+        let schema = json_schema_or_free_form::<T>(gen);
         add_schema_response(&mut responses, 200, "application/json", schema)?;
         // 500 status is not added because an endpoint can handle this, so it might never return
         // this error type.
@@ -341,7 +342,7 @@ impl<T: Serialize + JsonSchema + Send> OpenApiResponderInner
 {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        let schema = gen.json_schema::<T>();
+        let schema = json_schema_or_free_form::<T>(gen);
         add_schema_response(&mut responses, 200, "application/msgpack", schema)?;
         // 500 status is not added because an endpoint can handle this, so it might never return
         // this error type.
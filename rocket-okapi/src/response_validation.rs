@@ -0,0 +1,189 @@
+use crate::request_validation::SpecOperations;
+use okapi::instance::validate_instance;
+use okapi::openapi3::{OpenApi, Response as SpecResponse};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Status};
+use rocket::{Request, Response};
+use std::io::Cursor;
+
+/// A fairing that checks the responses of documented operations against the OpenAPI document,
+/// to catch documentation that does not match the implementation during development.
+///
+/// For every response, the status code must be documented (as `200`, `2XX` or `default`), the
+/// content type must be one of the documented media types, and JSON bodies must match their
+/// schema. Mismatches are logged as warnings, or panic with
+/// [`panic_on_mismatch`](Self::panic_on_mismatch) (the response is then not sent).
+///
+/// The responses are only checked in debug builds (with `debug_assertions`), unless
+/// [`in_release`](Self::in_release) is used. Checking a response reads its whole body
+/// into memory.
+///
+/// ```rust,no_run
+/// use rocket_okapi::openapi_get_routes_spec;
+/// use rocket_okapi::response_validation::ResponseValidator;
+///
+/// let (routes, spec) = openapi_get_routes_spec![];
+/// rocket::build()
+///     .mount("/api", routes)
+///     .attach(ResponseValidator::new("/api", spec).panic_on_mismatch());
+/// ```
+pub struct ResponseValidator {
+    operations: SpecOperations,
+    panic_on_mismatch: bool,
+    in_release: bool,
+}
+
+impl ResponseValidator {
+    /// Create a fairing that checks the responses of the operations in `spec`. `base_path` is
+    /// the path the routes of the spec are mounted at, like `/api` (or `/`).
+    #[must_use]
+    pub fn new(base_path: &str, spec: OpenApi) -> Self {
+        ResponseValidator {
+            operations: SpecOperations::new(base_path, spec),
+            panic_on_mismatch: false,
+            in_release: false,
+        }
+    }
+
+    /// Panic when a response does not match the document, instead of logging a warning.
+    #[must_use]
+    pub fn panic_on_mismatch(mut self) -> Self {
+        self.panic_on_mismatch = true;
+        self
+    }
+
+    /// Also check the responses in release builds.
+    #[must_use]
+    pub fn in_release(mut self) -> Self {
+        self.in_release = true;
+        self
+    }
+
+    /// Returns the problems of the response, without its body.
+    fn check_status_and_content_type(
+        &self,
+        documented: Option<&SpecResponse>,
+        status: Status,
+        content_type: Option<&ContentType>,
+        has_body: bool,
+    ) -> Vec<String> {
+        let documented = match documented {
+            Some(documented) => documented,
+            None => return vec![format!("Status `{}` is not documented.", status.code)],
+        };
+        let media_type = match content_type {
+            Some(content_type) if has_body => {
+                format!("{}/{}", content_type.top(), content_type.sub())
+            }
+            _ => return Vec::new(),
+        };
+        if documented.content.is_empty() {
+            return vec![format!(
+                "Status `{}` has a `{}` body, but no content is documented.",
+                status.code, media_type
+            )];
+        }
+        if find_media_type(documented, &media_type).is_none() {
+            return vec![format!(
+                "Content type `{}` is not documented for status `{}`.",
+                media_type, status.code
+            )];
+        }
+        Vec::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ResponseValidator {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenAPI response validation",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if !self.in_release && !cfg!(debug_assertions) {
+            return;
+        }
+        let path: Vec<&str> = req.uri().path().segments().collect();
+        let operation = match self.operations.find(req.method(), &path) {
+            Some((operation, _)) => operation,
+            None => return,
+        };
+        let status = res.status();
+        let documented = documented_response(&operation.responses, status);
+        let content_type = res.content_type();
+        let mut problems = self.check_status_and_content_type(
+            documented,
+            status,
+            content_type.as_ref(),
+            res.body().is_some(),
+        );
+        let schema = documented
+            .zip(content_type.as_ref())
+            .filter(|(_, content_type)| content_type.is_json())
+            .and_then(|(documented, content_type)| {
+                let media_type = format!("{}/{}", content_type.top(), content_type.sub());
+                find_media_type(documented, &media_type)?.schema.as_ref()
+            });
+        if let (Some(schema), true) = (schema, problems.is_empty()) {
+            match res.body_mut().to_bytes().await {
+                Ok(body) => {
+                    match serde_json::from_slice(&body) {
+                        Ok(value) => {
+                            let spec = &self.operations.spec;
+                            if let Err(errors) = validate_instance(spec, schema, &value) {
+                                problems.extend(errors.iter().map(|error| error.to_string()));
+                            }
+                        }
+                        Err(err) => problems.push(format!("Invalid JSON body: {}.", err)),
+                    }
+                    res.set_sized_body(body.len(), Cursor::new(body));
+                }
+                Err(err) => problems.push(format!("Could not read the body: {}.", err)),
+            }
+        }
+        if problems.is_empty() {
+            return;
+        }
+        let message = format!(
+            "The response of `{} {}` does not match the OpenAPI spec: {}",
+            req.method(),
+            req.uri().path(),
+            problems.join(" ")
+        );
+        if self.panic_on_mismatch {
+            panic!("{}", message);
+        }
+        log::warn!("{}", message);
+    }
+}
+
+/// The documented response for a status code: an exact match (`404`), a range (`4XX`) or the
+/// `default` response.
+fn documented_response(
+    responses: &okapi::Map<String, SpecResponse>,
+    status: Status,
+) -> Option<&SpecResponse> {
+    let code = status.code.to_string();
+    let range = format!("{}XX", status.code / 100);
+    responses
+        .get(&code)
+        .or_else(|| responses.get(&range))
+        .or_else(|| responses.get("default"))
+}
+
+/// The documented content for a media type, like `application/json`. Ranges like `text/*` and
+/// `*/*` are also matched.
+fn find_media_type<'a>(
+    response: &'a SpecResponse,
+    media_type: &str,
+) -> Option<&'a okapi::openapi3::MediaType> {
+    let top = media_type.split('/').next().unwrap_or_default();
+    response
+        .content
+        .get(media_type)
+        .or_else(|| response.content.get(&format!("{}/*", top)))
+        .or_else(|| response.content.get("*/*"))
+}
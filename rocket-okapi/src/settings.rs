@@ -1,14 +1,249 @@
-use schemars::gen::SchemaSettings;
+use crate::gen::OpenApiGenerator;
+use okapi::openapi3::{
+    Contact, Example, ExternalDocs, Header, Info, License, MediaType, Object, OpenApi, Operation,
+    Parameter, ParameterValue, RefOr, RequestBody, Response, Server,
+};
+use rocket::data::Limits;
+use rocket::http::Method;
+use schemars::gen::{GenVisitor, SchemaSettings};
+use schemars::schema::{RootSchema, Schema, SchemaObject};
+use schemars::visit::{RemoveRefSiblings, ReplaceBoolSchemas, SetSingleExample, Visitor};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::PathBuf;
 
 /// Settings which are used to customize the behavior of the `OpenApiGenerator`.
 #[derive(Debug, Clone)]
 pub struct OpenApiSettings {
-    /// Settings to customize how JSON Schemas are generated.
+    /// Settings to customize how JSON Schemas are generated. The default is
+    /// `SchemaSettings::openapi3()`: the schemas of types are defined once in
+    /// `components.schemas` and referenced with a `$ref`, and `Option<T>` is documented with
+    /// `nullable: true`. See [`inline_subschemas`](Self::inline_subschemas) and
+    /// [`option_handling`](Self::option_handling) to change this.
+    ///
+    /// The definitions are always added to `components.schemas`, so the `definitions_path` is
+    /// always `#/components/schemas/`.
     pub schema_settings: SchemaSettings,
     /// The path to the json file that contains the API specification. Then default is
     /// `openapi.json`.
     pub json_path: String,
+    /// The path to a size-reduced version of the specification for machine consumers (like code
+    /// generators), without descriptions and examples and as compact JSON (see
+    /// [`OpenApi::strip_docs`](okapi::openapi3::OpenApi::strip_docs)). The full specification
+    /// is still served at `json_path`. The default is `None` (not served).
+    pub slim_json_path: Option<String>,
+    /// The path under which the specification of every tag is served, as `{path}/{tag}.json`
+    /// (see [`OpenApi::filter_tag`](okapi::openapi3::OpenApi::filter_tag)), like `/openapi`.
+    /// This only contains the paths and components used by the tag, so big APIs can be browsed
+    /// without loading the whole specification. The default is `None` (not served).
+    pub tag_shards_path: Option<String>,
+    /// If `true`, the `openapi.json` file is served as pretty-printed JSON, which is easier to
+    /// read but larger. The default is `false` (compact JSON).
+    pub json_pretty: bool,
+    /// If `true`, the `openapi.json` file is written to the response in chunks while it is
+    /// serialized, instead of serializing it into a `String` first, which avoids the memory spike
+    /// of serving very large specifications. The response then has no `Content-Length`.
+    /// The default is `false`.
+    pub stream_json: bool,
+    /// When the specification of `openapi_get_routes!` is generated. The default is
+    /// `SpecAssembly::Eager`. The other macros get a specification that is already generated.
+    pub spec_assembly: SpecAssembly,
+    /// The data limits used to document the size cap of `Capped<T>` request bodies.
+    /// If `None` (the default), the `limits` are read from Rocket's configuration
+    /// (`Rocket.toml` and `ROCKET_` environment variables) when the spec is generated.
+    pub data_limits: Option<Limits>,
+    /// If `true`, the content of every request body gets an `x-size-limit` extension containing
+    /// the applicable data limit (in bytes), based on the media type (`json`, `form`,
+    /// `data-form`, `file`, ...). The default is `false`.
+    pub annotate_data_limits: bool,
+    /// If `true`, the schemas of tagged enums (serde's internally and adjacently tagged
+    /// representations) get a `discriminator`, and their variants are moved to
+    /// `components.schemas`. See [`OpenApi::add_discriminators`]. The default is `false`.
+    pub discriminators: bool,
+    /// If `true`, the object schemas of structs get `additionalProperties: false`, so they do not
+    /// allow other properties. Schemas of maps and of structs with a flattened enum are left
+    /// open, but structs with a flattened map are closed (and can be opened again with
+    /// [`filter`](Self::filter)). See [`OpenApi::close_object_schemas`]. The default is `false`.
+    pub strict_schemas: bool,
+    /// If `true`, schemas with a `default` (like the fields with `#[serde(default)]`) also get
+    /// it as their `example`. See [`okapi::example::add_default_examples`]. The default is
+    /// `false`.
+    pub default_examples: bool,
+    /// If `true`, the `null` entries in the `type` of schemas are replaced by an `anyOf` with a
+    /// `null` schema. This is set by [`option_handling`](Self::option_handling) with
+    /// `OptionHandling::AnyOfNull`. The default is `false`.
+    pub option_any_of_null: bool,
+    /// How the schemas of untagged and adjacently tagged enums are written, for client generators
+    /// that reject the schemas Schemars generates for them (see
+    /// [`OpenApi::convert_enums`]). The default leaves them as they are.
+    ///
+    /// ```rust
+    /// use okapi::enums::{EnumRepresentation, UntaggedEnums};
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.enum_representation = EnumRepresentation {
+    ///     untagged: UntaggedEnums::OneOf,
+    ///     nullable_variants: true,
+    ///     ..EnumRepresentation::default()
+    /// };
+    /// ```
+    pub enum_representation: okapi::enums::EnumRepresentation,
+    /// How the schemas of integers are written: their formats, if 64-bit integers are strings
+    /// (for JavaScript clients) and if unsigned integers have `minimum: 0` (see
+    /// [`OpenApi::convert_integers`]). The default leaves them as they are.
+    ///
+    /// ```rust
+    /// use okapi::integers::{IntegerRepresentation, LargeIntegers};
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.integer_representation = IntegerRepresentation {
+    ///     standard_formats: true,
+    ///     large_integers: LargeIntegers::String,
+    ///     ..IntegerRepresentation::default()
+    /// };
+    /// ```
+    pub integer_representation: okapi::integers::IntegerRepresentation,
+    /// The `servers` of the served specification, if it has none. If `None` (the default), they
+    /// are read from the `openapi_servers` key of Rocket's configuration when the `openapi.json`
+    /// route is created, so every profile of `Rocket.toml` can have its own servers:
+    ///
+    /// ```toml
+    /// [debug]
+    /// openapi_servers = [{ url = "http://localhost:8000", description = "Local" }]
+    ///
+    /// [release]
+    /// openapi_servers = [{ url = "https://api.example.com" }]
+    /// ```
+    pub servers: Option<Vec<Server>>,
+    /// The `externalDocs` of the document root, like a link to the developer portal. The default
+    /// is `None`.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::ExternalDocs;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.external_docs = Some(ExternalDocs {
+    ///     url: "https://developer.example.com".to_owned(),
+    ///     description: Some("The developer portal".to_owned()),
+    ///     ..ExternalDocs::default()
+    /// });
+    /// ```
+    pub external_docs: Option<ExternalDocs>,
+    /// The version of the OpenAPI specification that is generated. The default is
+    /// `OpenApiVersion::V3_0`.
+    pub openapi_version: OpenApiVersion,
+    /// Validate the generated specification (see [`OpenApi::validate`](okapi::openapi3::OpenApi::validate))
+    /// when the `openapi.json` route is created. The default is `SpecValidation::Skip`.
+    pub validation: SpecValidation,
+    /// OpenAPI documents whose `paths` and `components` are merged into the generated
+    /// specification, to document endpoints that are not implemented with Rocket.
+    /// See [`merge_spec_file`](Self::merge_spec_file).
+    pub merge_spec_files: Vec<PathBuf>,
+    /// JSON Merge Patch files that are applied to the served specification.
+    /// See [`override_file`](Self::override_file).
+    pub override_files: Vec<PathBuf>,
+    /// The translations of the specification: a locale (like `de`) and a JSON Merge Patch file
+    /// with the translated summaries and descriptions. See
+    /// [`translation_file`](Self::translation_file).
+    pub translation_files: Vec<(String, PathBuf)>,
+    /// If `true`, the `merge_spec_files` and `override_files` are read again (and the code
+    /// samples and sorting applied again) for every request of the `openapi.json` route, so
+    /// changes to the files are served without restarting the application. This is only used in
+    /// debug builds, release builds always read the files once. The default is `false`.
+    pub reload_spec_files: bool,
+    /// If `true`, the specification is sorted (see [`OpenApi::sort`](okapi::openapi3::OpenApi::sort)),
+    /// so it is serialized the same way in every build, regardless of the order in which the
+    /// routes are mounted. The default is `false`.
+    pub sort_spec: bool,
+    /// If `true`, `x-codeSamples` with example requests in curl, Rust and JavaScript are added
+    /// to all operations (see
+    /// [`add_code_samples`](okapi::export::add_code_samples)), which ReDoc shows as tabs.
+    /// The default is `false`.
+    pub code_samples: bool,
+    /// If `true`, the first paragraph of the doc comment of a route (up to the first blank line)
+    /// is used as the `summary` of its operation, and the rest as the `description`. Otherwise
+    /// only a `# Heading` on the first line is used as the summary. The default is `false`.
+    pub summary_from_first_paragraph: bool,
+    /// The file the served specification is written to. The default is `None`.
+    /// See [`write_spec_to`](Self::write_spec_to).
+    pub write_spec_path: Option<PathBuf>,
+    /// Protects the `openapi.json` route, so only allowed requests get the specification.
+    /// The default is `None` (public). See [`DocsAuth`](crate::docs_auth::DocsAuth).
+    pub docs_auth: Option<crate::docs_auth::DocsAuth>,
+    /// The origins that may fetch the `openapi.json` file from a browser, like documentation
+    /// portals that are hosted elsewhere. The default is empty (no CORS headers).
+    /// See [`allow_cors_origin`](Self::allow_cors_origin).
+    pub cors_allowed_origins: Vec<String>,
+    /// If `true`, the `openapi.json` route adds the server the request was sent to (from its
+    /// `Host` header, or the `X-Forwarded-*` headers with
+    /// [`trust_forwarded_headers`](Self::trust_forwarded_headers)) as the first server of the
+    /// specification, for services that are reachable through multiple hostnames or proxies.
+    /// The default is `false`.
+    pub request_server: bool,
+    /// If `true`, the `openapi.json` route replaces the servers of the specification by a single
+    /// server with the relative URL the route is mounted at (like `/api/v1`), which works behind
+    /// reverse proxies. See [`OpenApiHandler::relative_server`](crate::handlers::OpenApiHandler::relative_server).
+    /// The default is `false`.
+    pub relative_server: bool,
+    /// If `true`, the `X-Forwarded-Proto`, `X-Forwarded-Host` and `X-Forwarded-Prefix` headers
+    /// are used for the servers of [`request_server`](Self::request_server) and
+    /// [`relative_server`](Self::relative_server). Only enable this behind a reverse proxy that
+    /// sets these headers, as clients can send any value. The default is `false`.
+    pub trust_forwarded_headers: bool,
+    /// The color theme of the documentation UIs. The UIs are created separately, so pass this to
+    /// their config (like `SwaggerUIConfig::doc_theme`), which keeps all UIs in the same theme.
+    /// The default is `None` (the theme options of each UI are used).
+    pub doc_theme: Option<DocTheme>,
+    /// The number of threads that generate the operations (and their schemas) of the routes,
+    /// which speeds up generating the specification of APIs with many routes. `0` uses one thread
+    /// per CPU. The operations are always merged in the order of the routes, so the
+    /// specification does not depend on the number of threads. The default is `1`.
+    pub generation_threads: usize,
+    /// Leave the routes for which this returns `true` out of the specification (they are still
+    /// mounted), like debug-only endpoints in release builds. Use `#[openapi(skip)]` or
+    /// `#[openapi(skip_if = "cfg(...)")]` to skip a single route. The default is `None`.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// if !cfg!(debug_assertions) {
+    ///     settings.skip_route = Some(|route| route.tags.iter().any(|tag| tag == "Debug"));
+    /// }
+    /// ```
+    pub skip_route: Option<fn(&crate::RouteInfo<'_>) -> bool>,
+    /// Filters the served specification. The default is `None`.
+    /// See [`filter`](Self::filter).
+    pub spec_filter: Option<SpecFilter>,
+    /// The endpoints that this server forwards to other services, which are added to the
+    /// served specification. See [`PathsBuilder`].
+    pub proxied_paths: PathsBuilder,
+    /// The fields of the `info` of the served specification that are set in code, instead of
+    /// taken from the package. See [`InfoBuilder`].
+    pub info: InfoBuilder,
+    /// Parameters that are defined once in `components.parameters`, and referenced by the routes
+    /// with `#[openapi(param_ref = "...")]`. See [`register_parameter`](Self::register_parameter).
+    pub parameters: okapi::Map<String, Parameter>,
+    /// Responses that are defined once in `components.responses`, and referenced by the routes
+    /// with `#[openapi(response_ref(...))]`. See [`register_response`](Self::register_response).
+    pub responses: okapi::Map<String, Response>,
+    /// Request bodies that are defined once in `components.requestBodies`. See
+    /// [`register_request_body`](Self::register_request_body).
+    pub request_bodies: okapi::Map<String, RequestBody>,
+    /// Headers that are defined once in `components.headers`. See
+    /// [`register_header`](Self::register_header).
+    pub headers: okapi::Map<String, Header>,
+    /// Examples that are defined once in `components.examples`. See
+    /// [`register_example`](Self::register_example).
+    pub examples: okapi::Map<String, Example>,
+    /// The schema generator that is shared by all generators that use these settings.
+    /// The default is `None`. See [`share_schemas`](Self::share_schemas).
+    pub shared_schema_generator: Option<crate::gen::SharedSchemaGenerator>,
 }
 
 impl Default for OpenApiSettings {
@@ -16,6 +251,48 @@ impl Default for OpenApiSettings {
         OpenApiSettings {
             schema_settings: SchemaSettings::openapi3(),
             json_path: "/openapi.json".to_owned(),
+            slim_json_path: None,
+            tag_shards_path: None,
+            json_pretty: false,
+            stream_json: false,
+            spec_assembly: SpecAssembly::default(),
+            data_limits: None,
+            annotate_data_limits: false,
+            discriminators: false,
+            strict_schemas: false,
+            default_examples: false,
+            option_any_of_null: false,
+            enum_representation: okapi::enums::EnumRepresentation::default(),
+            integer_representation: okapi::integers::IntegerRepresentation::default(),
+            servers: None,
+            external_docs: None,
+            openapi_version: OpenApiVersion::default(),
+            validation: SpecValidation::default(),
+            merge_spec_files: Vec::new(),
+            override_files: Vec::new(),
+            translation_files: Vec::new(),
+            reload_spec_files: false,
+            sort_spec: false,
+            code_samples: false,
+            summary_from_first_paragraph: false,
+            write_spec_path: None,
+            docs_auth: None,
+            cors_allowed_origins: Vec::new(),
+            request_server: false,
+            relative_server: false,
+            trust_forwarded_headers: false,
+            doc_theme: None,
+            generation_threads: 1,
+            skip_route: None,
+            spec_filter: None,
+            proxied_paths: PathsBuilder::new(),
+            info: InfoBuilder::new(),
+            parameters: okapi::Map::new(),
+            responses: okapi::Map::new(),
+            request_bodies: okapi::Map::new(),
+            headers: okapi::Map::new(),
+            examples: okapi::Map::new(),
+            shared_schema_generator: None,
         }
     }
 }
@@ -28,6 +305,1172 @@ impl OpenApiSettings {
             ..OpenApiSettings::default()
         }
     }
+
+    /// Merge the `paths` and `components` of the OpenAPI document at `path` into the generated
+    /// specification. The file is read when the specification is generated, it can be a JSON
+    /// or (with the `yaml` feature) a YAML file, depending on the file extension.
+    ///
+    /// The document should use the same OpenAPI version as the generated specification.
+    /// Paths, operations or components that are also generated are rejected. A file that can not
+    /// be read or merged is logged as an error and left out of the specification.
+    ///
+    /// ```rust
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.merge_spec_file("extra-paths.json");
+    /// assert_eq!(settings.merge_spec_files.len(), 1);
+    ///
+    /// // The file does not exist.
+    /// let spec = OpenApiGenerator::new(&settings).into_openapi();
+    /// assert!(spec.paths.is_empty());
+    /// ```
+    pub fn merge_spec_file<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.merge_spec_files.push(path.into());
+        self
+    }
+
+    /// Apply the JSON Merge Patch ([RFC 7386](https://tools.ietf.org/html/rfc7386)) in the file
+    /// at `path` to the specification, when the `openapi.json` route is created. This can be used
+    /// to add descriptions (or change anything else) without touching the code.
+    /// The file can be a JSON or (with the `yaml` feature) a YAML file, like:
+    ///
+    /// ```yaml
+    /// info:
+    ///   description: A longer description of the API.
+    /// paths:
+    ///   /users:
+    ///     get:
+    ///       description: Returns all users, ordered by name.
+    /// ```
+    ///
+    /// The overrides are applied by [`get_openapi_route`](crate::get_openapi_route), use
+    /// [`apply_spec_overrides`](crate::apply_spec_overrides) for a spec that is served manually.
+    pub fn override_file<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.override_files.push(path.into());
+        self
+    }
+
+    /// Serve a translation of the specification for requests with the query parameter
+    /// `lang=<locale>`, like `openapi.json?lang=de`. The file at `path` is a JSON Merge Patch
+    /// ([RFC 7386](https://tools.ietf.org/html/rfc7386)) with the translated texts, in the format
+    /// of [`override_file`](Self::override_file), like:
+    ///
+    /// ```yaml
+    /// info:
+    ///   description: Die API des Shops.
+    /// paths:
+    ///   /users:
+    ///     get:
+    ///       summary: Alle Benutzer abrufen
+    /// ```
+    ///
+    /// The file is read and applied once, when the `openapi.json` route is created. With
+    /// [`reload_spec_files`](Self::reload_spec_files) (or a lazy
+    /// [`spec_assembly`](Self::spec_assembly)) it is read again for every request, so the
+    /// translations can be changed without a restart.
+    /// Requests for other locales get the untranslated specification. The slim specification is
+    /// not translated, as it has no descriptions.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.translation_file("de", "docs/openapi.de.yaml");
+    /// ```
+    pub fn translation_file<P: Into<PathBuf>>(
+        &mut self,
+        locale: impl Into<String>,
+        path: P,
+    ) -> &mut Self {
+        self.translation_files.push((locale.into(), path.into()));
+        self
+    }
+
+    /// Share one schema generator between all specs that are generated with these settings (or
+    /// clones of them), like the groups of routes of
+    /// [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs). The schemas
+    /// of types that are used by several groups are then only generated once, and have the same
+    /// name in all groups.
+    ///
+    /// The `components.schemas` of every generated spec then contain the schemas of all specs that
+    /// were generated before it, so this is meant for specs that are merged.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.share_schemas();
+    /// ```
+    pub fn share_schemas(&mut self) -> &mut Self {
+        self.shared_schema_generator = Some(crate::gen::SharedSchemaGenerator::default());
+        self
+    }
+
+    /// Only serve a part of the specification, as selected by `filter`. The filter is applied
+    /// when the `openapi.json` route is created (after the `override_files`), so the routes are
+    /// still generated and mounted. The filter can be deserialized, so every deployment can
+    /// serve a different part of the specification from its configuration:
+    ///
+    /// ```rust,no_run
+    /// use rocket_okapi::settings::{OpenApiSettings, SpecFilter};
+    ///
+    /// let filter: SpecFilter = rocket::Config::figment()
+    ///     .extract_inner("openapi_filter")
+    ///     .unwrap_or_default();
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.filter(filter);
+    /// ```
+    pub fn filter(&mut self, filter: SpecFilter) -> &mut Self {
+        self.spec_filter = Some(filter);
+        self
+    }
+
+    /// Define a parameter that is used by many routes (like a tenant header) once, in
+    /// `components.parameters`. Routes reference it with `#[openapi(param_ref = "name")]`.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Parameter, ParameterValue};
+    /// use rocket::get;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use rocket_okapi::{openapi, openapi_get_spec};
+    ///
+    /// #[openapi(param_ref = "TenantHeader")]
+    /// #[get("/users")]
+    /// fn get_users() {}
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.register_parameter(
+    ///     "TenantHeader",
+    ///     Parameter {
+    ///         name: "X-Tenant".to_owned(),
+    ///         location: "header".to_owned(),
+    ///         description: Some("The tenant of the request.".to_owned()),
+    ///         required: true,
+    ///         deprecated: false,
+    ///         allow_empty_value: false,
+    ///         value: ParameterValue::Schema {
+    ///             style: None,
+    ///             explode: None,
+    ///             allow_reserved: false,
+    ///             schema: schemars::schema_for!(String).schema,
+    ///             example: None,
+    ///             examples: None,
+    ///         },
+    ///         extensions: Default::default(),
+    ///     },
+    /// );
+    /// let spec = openapi_get_spec![settings: get_users];
+    /// assert!(spec.components.unwrap().parameters.contains_key("TenantHeader"));
+    /// ```
+    pub fn register_parameter(
+        &mut self,
+        name: impl Into<String>,
+        parameter: Parameter,
+    ) -> &mut Self {
+        self.parameters.insert(name.into(), parameter);
+        self
+    }
+
+    /// Define a response that is used by many routes (like a standard error) once, in
+    /// `components.responses`. Routes reference it for a status code with
+    /// `#[openapi(response_ref(status = 401, name = "..."))]`, which replaces the response the
+    /// route documents for that status.
+    ///
+    /// ```rust
+    /// use okapi::openapi3::Response;
+    /// use rocket::get;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use rocket_okapi::{openapi, openapi_get_spec};
+    ///
+    /// #[openapi(response_ref(status = 401, name = "Unauthorized"))]
+    /// #[get("/users")]
+    /// fn get_users() {}
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.register_response(
+    ///     "Unauthorized",
+    ///     Response {
+    ///         description: "The request is not authenticated.".to_owned(),
+    ///         ..Response::default()
+    ///     },
+    /// );
+    /// let spec = openapi_get_spec![settings: get_users];
+    /// assert!(spec.components.unwrap().responses.contains_key("Unauthorized"));
+    /// ```
+    pub fn register_response(&mut self, name: impl Into<String>, response: Response) -> &mut Self {
+        self.responses.insert(name.into(), response);
+        self
+    }
+
+    /// Define a request body that is used by many routes once, in `components.requestBodies`.
+    /// Routes reference it with `#[openapi(request_body_ref = "name")]`, instead of documenting
+    /// their data guard.
+    pub fn register_request_body(
+        &mut self,
+        name: impl Into<String>,
+        request_body: RequestBody,
+    ) -> &mut Self {
+        self.request_bodies.insert(name.into(), request_body);
+        self
+    }
+
+    /// Define a response header that is used by many responses once, in `components.headers`.
+    /// Responders reference it with [`OpenApiGenerator::header_ref`].
+    ///
+    /// ```rust
+    /// use okapi::openapi3::{Header, ParameterValue};
+    /// use rocket_okapi::gen::OpenApiGenerator;
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.register_header(
+    ///     "RequestId",
+    ///     Header {
+    ///         description: Some("The id of the request, for support.".to_owned()),
+    ///         required: true,
+    ///         deprecated: false,
+    ///         allow_empty_value: false,
+    ///         value: ParameterValue::Schema {
+    ///             style: None,
+    ///             explode: None,
+    ///             allow_reserved: false,
+    ///             schema: schemars::schema_for!(String).schema,
+    ///             example: None,
+    ///             examples: None,
+    ///         },
+    ///         extensions: Default::default(),
+    ///     },
+    /// );
+    /// let gen = OpenApiGenerator::new(&settings);
+    /// assert!(gen.header_ref("RequestId").is_ok());
+    /// ```
+    pub fn register_header(&mut self, name: impl Into<String>, header: Header) -> &mut Self {
+        self.headers.insert(name.into(), header);
+        self
+    }
+
+    /// Define an example that is used by many media types or parameters once, in
+    /// `components.examples`. Responders reference it with [`OpenApiGenerator::example_ref`].
+    pub fn register_example(&mut self, name: impl Into<String>, example: Example) -> &mut Self {
+        self.examples.insert(name.into(), example);
+        self
+    }
+
+    /// Inline the schemas of types in the schemas that use them, instead of defining them in
+    /// `components.schemas` and referencing them with a `$ref`. This makes the spec
+    /// self-contained per operation, but larger. Recursive types are always referenced.
+    /// The default is `false`.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.inline_subschemas(true);
+    /// assert!(settings.schema_settings.inline_subschemas);
+    /// ```
+    pub fn inline_subschemas(&mut self, inline: bool) -> &mut Self {
+        self.schema_settings.inline_subschemas = inline;
+        self
+    }
+
+    /// Add a visitor that is run on the generated schemas, like
+    /// `SchemaSettings::with_visitor`. A visitor added like this is `Send` and `Sync`, so it
+    /// is also used with [`generation_threads`](Self::generation_threads) and
+    /// [`SpecAssembly::Lazy`](SpecAssembly::Lazy). Other visitors in `schema_settings.visitors`
+    /// (apart from the ones of `schemars` and [`XmlVisitor`](crate::util::XmlVisitor)) make
+    /// lazy assembly fail with an error, and the operations are then generated on one thread.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    /// use schemars::schema::SchemaObject;
+    /// use schemars::visit::Visitor;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Deprecate;
+    ///
+    /// impl Visitor for Deprecate {
+    ///     fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+    ///         schema.metadata().deprecated = true;
+    ///     }
+    /// }
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.add_schema_visitor(Deprecate);
+    /// let visitor = settings.schema_settings.visitors.last().unwrap();
+    /// assert!(format!("{:?}", visitor).contains("Deprecate"));
+    /// ```
+    pub fn add_schema_visitor<V>(&mut self, visitor: V) -> &mut Self
+    where
+        V: Visitor + Debug + Clone + Send + Sync + 'static,
+    {
+        self.schema_settings
+            .visitors
+            .push(Box::new(SharedVisitor(Box::new(visitor))));
+        self
+    }
+
+    /// Choose how `Option<T>` fields and values are documented. The default is
+    /// `OptionHandling::Nullable`.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::{OpenApiSettings, OptionHandling};
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.option_handling(OptionHandling::NullType);
+    /// assert!(settings.schema_settings.option_add_null_type);
+    /// assert!(!settings.schema_settings.option_nullable);
+    ///
+    /// settings.option_handling(OptionHandling::AnyOfNull);
+    /// assert!(settings.option_any_of_null);
+    /// ```
+    pub fn option_handling(&mut self, option_handling: OptionHandling) -> &mut Self {
+        let (nullable, null_type) = match option_handling {
+            OptionHandling::Nullable => (true, false),
+            OptionHandling::NullType | OptionHandling::AnyOfNull => (false, true),
+            OptionHandling::NotRequired => (false, false),
+        };
+        self.schema_settings.option_nullable = nullable;
+        self.schema_settings.option_add_null_type = null_type;
+        self.option_any_of_null = option_handling == OptionHandling::AnyOfNull;
+        self
+    }
+
+    /// Whether the spec files are read for every request (see `reload_spec_files`).
+    pub(crate) fn reloads_spec_files(&self) -> bool {
+        self.reload_spec_files && cfg!(debug_assertions)
+    }
+
+    /// Write the specification to the file at `path` when the `openapi.json` route is created
+    /// (so every time the application starts), to keep a committed copy of it up to date.
+    /// The file is written as JSON, or (with the `yaml` feature) as YAML if the file extension is
+    /// `.yaml` or `.yml`. Failing to write the file is logged, but does not stop the application.
+    ///
+    /// Unlike the served `openapi.json`, the file does not get a `servers` entry for the path the
+    /// route is mounted at.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.write_spec_to("target/openapi.json");
+    /// ```
+    pub fn write_spec_to<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.write_spec_path = Some(path.into());
+        self
+    }
+
+    /// Allow browsers to fetch the `openapi.json` file from `origin` (like
+    /// `https://example.readme.io`, or `*` for all origins), so documentation portals that are
+    /// hosted elsewhere can load the specification directly from the application. This adds the
+    /// `Access-Control-Allow-Origin` header to the responses of requests from the origin.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings.allow_cors_origin("https://example.readme.io");
+    /// ```
+    pub fn allow_cors_origin(&mut self, origin: impl Into<String>) -> &mut Self {
+        self.cors_allowed_origins.push(origin.into());
+        self
+    }
+}
+
+/// A schema visitor that can be shared between threads.
+trait SendVisitor: Visitor + Debug + Send + Sync {
+    fn clone_box(&self) -> Box<dyn SendVisitor>;
+}
+
+impl<T> SendVisitor for T
+where
+    T: Visitor + Debug + Clone + Send + Sync + 'static,
+{
+    fn clone_box(&self) -> Box<dyn SendVisitor> {
+        Box::new(self.clone())
+    }
+}
+
+/// The schema visitors added with [`OpenApiSettings::add_schema_visitor`].
+#[derive(Debug)]
+struct SharedVisitor(Box<dyn SendVisitor>);
+
+impl Clone for SharedVisitor {
+    fn clone(&self) -> Self {
+        SharedVisitor(self.0.clone_box())
+    }
+}
+
+impl Visitor for SharedVisitor {
+    fn visit_root_schema(&mut self, root: &mut RootSchema) {
+        self.0.visit_root_schema(root)
+    }
+
+    fn visit_schema(&mut self, schema: &mut Schema) {
+        self.0.visit_schema(schema)
+    }
+
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        self.0.visit_schema_object(schema)
+    }
+}
+
+/// The `SchemaSettings` of `OpenApiSettings`, in a form that can be shared between threads.
+#[derive(Debug, Clone)]
+struct SharedSchemaSettings {
+    option_nullable: bool,
+    option_add_null_type: bool,
+    definitions_path: String,
+    meta_schema: Option<String>,
+    inline_subschemas: bool,
+    /// The visitors, or the error for a visitor that is not `Send`.
+    visitors: crate::Result<Vec<SharedVisitor>>,
+}
+
+impl SharedSchemaSettings {
+    fn new(settings: &SchemaSettings) -> Self {
+        SharedSchemaSettings {
+            option_nullable: settings.option_nullable,
+            option_add_null_type: settings.option_add_null_type,
+            definitions_path: settings.definitions_path.clone(),
+            meta_schema: settings.meta_schema.clone(),
+            inline_subschemas: settings.inline_subschemas,
+            visitors: settings
+                .visitors
+                .iter()
+                .map(|visitor| shared_visitor(visitor.as_ref()))
+                .collect(),
+        }
+    }
+
+    fn to_settings(&self) -> crate::Result<SchemaSettings> {
+        // `SchemaSettings` is `non_exhaustive`, so it can not be created with all fields.
+        let mut settings = SchemaSettings::openapi3();
+        settings.option_nullable = self.option_nullable;
+        settings.option_add_null_type = self.option_add_null_type;
+        settings.definitions_path = self.definitions_path.clone();
+        settings.meta_schema = self.meta_schema.clone();
+        settings.inline_subschemas = self.inline_subschemas;
+        settings.visitors = self
+            .visitors
+            .clone()?
+            .into_iter()
+            .map(|visitor| Box::new(visitor) as Box<dyn GenVisitor>)
+            .collect();
+        Ok(settings)
+    }
+}
+
+/// Get the schema visitor as a `SharedVisitor`, if it is known to be `Send` and `Sync`.
+fn shared_visitor(visitor: &dyn GenVisitor) -> crate::Result<SharedVisitor> {
+    let visitor = visitor.as_any();
+    if let Some(visitor) = visitor.downcast_ref::<SharedVisitor>() {
+        Ok(visitor.clone())
+    } else if let Some(visitor) = visitor.downcast_ref::<ReplaceBoolSchemas>() {
+        Ok(SharedVisitor(Box::new(visitor.clone())))
+    } else if let Some(visitor) = visitor.downcast_ref::<RemoveRefSiblings>() {
+        Ok(SharedVisitor(Box::new(visitor.clone())))
+    } else if let Some(visitor) = visitor.downcast_ref::<SetSingleExample>() {
+        Ok(SharedVisitor(Box::new(visitor.clone())))
+    } else if let Some(visitor) = visitor.downcast_ref::<crate::util::XmlVisitor>() {
+        Ok(SharedVisitor(Box::new(*visitor)))
+    } else {
+        Err(crate::OpenApiError::new(format!(
+            "The schema visitor `{:?}` can not be shared between threads, add it with \
+            `OpenApiSettings::add_schema_visitor` instead.",
+            visitor
+        )))
+    }
+}
+
+/// Generates `SharedSettings`, with all fields of `OpenApiSettings` except for the
+/// `schema_settings` and the `shared_schema_generator`.
+macro_rules! shared_settings {
+    ($($field:ident: $ty:ty,)*) => {
+        /// The `OpenApiSettings`, in a form that can be shared between threads. The schema
+        /// generator of [`share_schemas`](OpenApiSettings::share_schemas) is not shared.
+        #[derive(Debug, Clone)]
+        pub(crate) struct SharedSettings {
+            schema_settings: SharedSchemaSettings,
+            $(pub(crate) $field: $ty,)*
+        }
+
+        impl SharedSettings {
+            pub(crate) fn new(settings: &OpenApiSettings) -> Self {
+                let OpenApiSettings {
+                    schema_settings,
+                    shared_schema_generator: _,
+                    $($field,)*
+                } = settings.clone();
+                SharedSettings {
+                    schema_settings: SharedSchemaSettings::new(&schema_settings),
+                    $($field,)*
+                }
+            }
+
+            /// Create the settings again. Fails if one of the schema visitors could not be
+            /// shared.
+            pub(crate) fn to_settings(&self) -> crate::Result<OpenApiSettings> {
+                Ok(OpenApiSettings {
+                    schema_settings: self.schema_settings.to_settings()?,
+                    shared_schema_generator: None,
+                    $($field: self.$field.clone(),)*
+                })
+            }
+        }
+    };
+}
+
+shared_settings! {
+    json_path: String,
+    slim_json_path: Option<String>,
+    tag_shards_path: Option<String>,
+    json_pretty: bool,
+    stream_json: bool,
+    spec_assembly: SpecAssembly,
+    data_limits: Option<Limits>,
+    annotate_data_limits: bool,
+    discriminators: bool,
+    strict_schemas: bool,
+    default_examples: bool,
+    option_any_of_null: bool,
+    enum_representation: okapi::enums::EnumRepresentation,
+    integer_representation: okapi::integers::IntegerRepresentation,
+    servers: Option<Vec<Server>>,
+    external_docs: Option<ExternalDocs>,
+    openapi_version: OpenApiVersion,
+    validation: SpecValidation,
+    merge_spec_files: Vec<PathBuf>,
+    override_files: Vec<PathBuf>,
+    translation_files: Vec<(String, PathBuf)>,
+    reload_spec_files: bool,
+    sort_spec: bool,
+    code_samples: bool,
+    summary_from_first_paragraph: bool,
+    write_spec_path: Option<PathBuf>,
+    docs_auth: Option<crate::docs_auth::DocsAuth>,
+    cors_allowed_origins: Vec<String>,
+    request_server: bool,
+    relative_server: bool,
+    trust_forwarded_headers: bool,
+    doc_theme: Option<DocTheme>,
+    generation_threads: usize,
+    skip_route: Option<fn(&crate::RouteInfo<'_>) -> bool>,
+    spec_filter: Option<SpecFilter>,
+    proxied_paths: PathsBuilder,
+    info: InfoBuilder,
+    parameters: okapi::Map<String, Parameter>,
+    responses: okapi::Map<String, Response>,
+    request_bodies: okapi::Map<String, RequestBody>,
+    headers: okapi::Map<String, Header>,
+    examples: okapi::Map<String, Example>,
+}
+
+/// The version of the OpenAPI specification to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0.0
+    #[default]
+    V3_0,
+    /// OpenAPI 3.1.0
+    ///
+    /// Binary bodies are documented with `contentMediaType` instead of `format: binary`, and
+    /// the schemas are converted with [`okapi::convert::to_v31`].
+    V3_1,
+}
+
+impl OpenApiVersion {
+    /// The value of the `openapi` field of the generated specification.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OpenApiVersion::V3_0 => "3.0.0",
+            OpenApiVersion::V3_1 => "3.1.0",
+        }
+    }
+}
+
+/// Selects the part of the specification that is served, see
+/// [`OpenApiSettings::filter`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SpecFilter {
+    /// Only serve the operations with one of these tags. If empty (the default), the operations
+    /// of all tags (and without tags) are served.
+    pub include_tags: Vec<String>,
+    /// Do not serve the paths that start with one of these prefixes, like `/internal`.
+    pub exclude_paths: Vec<String>,
+    /// Remove these specification extensions (like `x-internal`) from all objects.
+    pub exclude_extensions: Vec<String>,
+}
+
+impl SpecFilter {
+    /// Apply the filter to a specification. The components that are no longer used are removed.
+    #[must_use]
+    pub fn apply(&self, spec: &okapi::openapi3::OpenApi) -> okapi::openapi3::OpenApi {
+        let mut spec = spec.filter_operations(|path, operation| {
+            let included = self.include_tags.is_empty()
+                || operation
+                    .tags
+                    .iter()
+                    .any(|tag| self.include_tags.contains(tag));
+            included
+                && !self
+                    .exclude_paths
+                    .iter()
+                    .any(|prefix| path.starts_with(prefix.as_str()))
+        });
+        spec.remove_extensions(&self.exclude_extensions);
+        spec
+    }
+}
+
+/// The function that generates the schema of a type.
+type SchemaFn = fn(&mut OpenApiGenerator) -> SchemaObject;
+
+/// Declares endpoints that are not Rocket routes, like the endpoints of other services that
+/// this server reverse-proxies, so the served specification covers all endpoints of the
+/// gateway. The schemas are generated from Rust types, like the schemas of Rocket routes.
+///
+/// The endpoints are added when the `openapi.json` route is created (before the
+/// `override_files`), so they are only added once if the specs of several groups of routes are
+/// merged.
+///
+/// ```rust
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::JsonSchema;
+///
+/// #[derive(JsonSchema)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let mut settings = OpenApiSettings::new();
+/// settings
+///     .proxied_paths
+///     .get("/legacy/users/{id}")
+///     .operation_id("legacy_get_user")
+///     .tag("Legacy")
+///     .path_param::<u64>("id")
+///     .response::<User>(200, "The user.")
+///     .empty_response(404, "The user does not exist.");
+/// settings
+///     .proxied_paths
+///     .post("/legacy/users")
+///     .request_body::<User>()
+///     .response::<User>(201, "The created user.");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PathsBuilder {
+    endpoints: Vec<ProxiedEndpoint>,
+}
+
+impl PathsBuilder {
+    /// Create a builder without endpoints.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an endpoint. The `path` can use the syntax of Rocket (`/users/<id>`) or of OpenAPI
+    /// (`/users/{id}`).
+    pub fn endpoint(&mut self, method: Method, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoints.push(ProxiedEndpoint {
+            method,
+            path: path.into(),
+            operation: Operation::default(),
+            parameters: Vec::new(),
+            request_body: None,
+            responses: Vec::new(),
+        });
+        self.endpoints.last_mut().unwrap()
+    }
+
+    /// Add a `GET` endpoint.
+    pub fn get(&mut self, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoint(Method::Get, path)
+    }
+
+    /// Add a `POST` endpoint.
+    pub fn post(&mut self, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoint(Method::Post, path)
+    }
+
+    /// Add a `PUT` endpoint.
+    pub fn put(&mut self, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoint(Method::Put, path)
+    }
+
+    /// Add a `PATCH` endpoint.
+    pub fn patch(&mut self, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoint(Method::Patch, path)
+    }
+
+    /// Add a `DELETE` endpoint.
+    pub fn delete(&mut self, path: impl Into<String>) -> &mut ProxiedEndpoint {
+        self.endpoint(Method::Delete, path)
+    }
+
+    /// Add a GraphQL endpoint, which is handled by a crate like `juniper_rocket` or
+    /// `async-graphql-rocket` (so it is not an `#[openapi]` route): a `POST` endpoint with a
+    /// [`GraphQLRequest`](crate::graphql::GraphQLRequest) body and a
+    /// [`GraphQLResponse`](crate::graphql::GraphQLResponse). The GraphQL schema (SDL) at `sdl_url`
+    /// is linked as the external documentation of the operation.
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::OpenApiSettings;
+    ///
+    /// let mut settings = OpenApiSettings::new();
+    /// settings
+    ///     .proxied_paths
+    ///     .graphql("/graphql", Some("/graphql/schema.graphql"))
+    ///     .tag("GraphQL");
+    /// ```
+    pub fn graphql(
+        &mut self,
+        path: impl Into<String>,
+        sdl_url: Option<&str>,
+    ) -> &mut ProxiedEndpoint {
+        let endpoint = self
+            .post(path)
+            .summary("Run a GraphQL query or mutation")
+            .request_body::<crate::graphql::GraphQLRequest>()
+            .response::<crate::graphql::GraphQLResponse>(
+                200,
+                "The result of the operation, with the errors of the operation (if any).",
+            );
+        if let Some(sdl_url) = sdl_url {
+            endpoint.operation_mut().external_docs = Some(okapi::openapi3::ExternalDocs {
+                description: Some("The GraphQL schema (SDL).".to_owned()),
+                url: sdl_url.to_owned(),
+                extensions: Object::default(),
+            });
+        }
+        endpoint
+    }
+
+    /// If no endpoints were added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Add the operations of the endpoints to the generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a response can not be added.
+    pub fn add_to(&self, gen: &mut OpenApiGenerator) -> crate::Result<()> {
+        for endpoint in &self.endpoints {
+            let operation = endpoint.operation(gen)?;
+            gen.add_manual_operation(endpoint.method, &endpoint.path, operation);
+        }
+        Ok(())
+    }
+
+    /// Generate a specification with only the endpoints, with the schemas they use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a response can not be added.
+    pub fn spec(&self, settings: &OpenApiSettings) -> crate::Result<OpenApi> {
+        let mut settings = settings.clone();
+        // These are handled by the spec the endpoints are added to.
+        settings.merge_spec_files = Vec::new();
+        settings.sort_spec = false;
+        let mut gen = OpenApiGenerator::new(&settings);
+        self.add_to(&mut gen)?;
+        Ok(gen.into_openapi())
+    }
+}
+
+/// An endpoint of a [`PathsBuilder`].
+#[derive(Debug, Clone)]
+pub struct ProxiedEndpoint {
+    method: Method,
+    path: String,
+    operation: Operation,
+    /// The name, location, if it is required and the schema of parameters.
+    parameters: Vec<(String, &'static str, bool, SchemaFn)>,
+    request_body: Option<SchemaFn>,
+    /// The status code, description and schema of responses.
+    responses: Vec<(u16, String, Option<SchemaFn>)>,
+}
+
+impl ProxiedEndpoint {
+    /// Set the operation id.
+    pub fn operation_id(&mut self, operation_id: impl Into<String>) -> &mut Self {
+        self.operation.operation_id = Some(operation_id.into());
+        self
+    }
+
+    /// Set the summary.
+    pub fn summary(&mut self, summary: impl Into<String>) -> &mut Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    /// Add a tag.
+    pub fn tag(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    /// Add a path parameter with the schema of `T`.
+    pub fn path_param<T: JsonSchema>(&mut self, name: impl Into<String>) -> &mut Self {
+        self.parameters.push((
+            name.into(),
+            "path",
+            true,
+            OpenApiGenerator::json_schema::<T>,
+        ));
+        self
+    }
+
+    /// Add a query parameter with the schema of `T`.
+    pub fn query_param<T: JsonSchema>(
+        &mut self,
+        name: impl Into<String>,
+        required: bool,
+    ) -> &mut Self {
+        self.parameters.push((
+            name.into(),
+            "query",
+            required,
+            OpenApiGenerator::json_schema::<T>,
+        ));
+        self
+    }
+
+    /// Set the (required) JSON request body, with the schema of `T`.
+    pub fn request_body<T: JsonSchema>(&mut self) -> &mut Self {
+        self.request_body = Some(OpenApiGenerator::json_schema::<T>);
+        self
+    }
+
+    /// Add a JSON response, with the schema of `T`.
+    pub fn response<T: JsonSchema>(
+        &mut self,
+        status: u16,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.responses.push((
+            status,
+            description.into(),
+            Some(OpenApiGenerator::json_schema::<T>),
+        ));
+        self
+    }
+
+    /// Add a response without content.
+    pub fn empty_response(&mut self, status: u16, description: impl Into<String>) -> &mut Self {
+        self.responses.push((status, description.into(), None));
+        self
+    }
+
+    /// The operation, to set everything the other methods do not cover. The parameters,
+    /// request body and responses of the other methods are added to it.
+    pub fn operation_mut(&mut self) -> &mut Operation {
+        &mut self.operation
+    }
+
+    fn operation(&self, gen: &mut OpenApiGenerator) -> crate::Result<Operation> {
+        let mut operation = self.operation.clone();
+        for (name, location, required, schema) in &self.parameters {
+            operation.parameters.push(RefOr::Object(Parameter {
+                name: name.clone(),
+                location: (*location).to_owned(),
+                description: None,
+                required: *required,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema: schema(gen),
+                    example: None,
+                    examples: None,
+                },
+                extensions: Default::default(),
+            }));
+        }
+        if let Some(schema) = self.request_body {
+            operation.request_body = Some(RefOr::Object(RequestBody {
+                content: okapi::map! {
+                    "application/json".to_owned() => MediaType {
+                        schema: Some(schema(gen)),
+                        ..MediaType::default()
+                    },
+                },
+                required: true,
+                ..RequestBody::default()
+            }));
+        }
+        for (status, description, schema) in &self.responses {
+            if let Some(schema) = schema {
+                crate::util::add_schema_response(
+                    &mut operation.responses,
+                    *status,
+                    "application/json",
+                    schema(gen),
+                )?;
+            }
+            crate::util::set_response_description(
+                &mut operation.responses,
+                &status.to_string(),
+                description.clone(),
+            )?;
+        }
+        Ok(operation)
+    }
+}
+
+/// The fields of the `info` of the specification that are set in code (see
+/// [`OpenApiSettings::info`]). They replace the fields the macros take from the package (like
+/// the `title` and `version`) when the `openapi.json` route is created, before the
+/// `override_files` are applied. Fields that are not set are left as they are.
+///
+/// ```rust
+/// use rocket_okapi::settings::OpenApiSettings;
+///
+/// let mut settings = OpenApiSettings::new();
+/// settings
+///     .info
+///     .title("Shop API")
+///     .terms_of_service("https://example.com/terms")
+///     .contact_name("API Support")
+///     .contact_email("api@example.com")
+///     .license("Apache 2.0")
+///     .license_identifier("Apache-2.0");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InfoBuilder {
+    title: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    terms_of_service: Option<String>,
+    contact: Option<Contact>,
+    license: Option<License>,
+    license_identifier: Option<String>,
+}
+
+impl InfoBuilder {
+    /// Create a builder that does not set any fields.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the API.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the short summary of the API. This is only added to OpenAPI 3.1 specifications, 3.0
+    /// has no `summary` in the `info`.
+    pub fn summary(&mut self, summary: impl Into<String>) -> &mut Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the description of the API.
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the version of the API.
+    pub fn version(&mut self, version: impl Into<String>) -> &mut Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the URL of the terms of service.
+    pub fn terms_of_service(&mut self, url: impl Into<String>) -> &mut Self {
+        self.terms_of_service = Some(url.into());
+        self
+    }
+
+    /// Set the name of the contact.
+    pub fn contact_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.contact.get_or_insert_with(Contact::default).name = Some(name.into());
+        self
+    }
+
+    /// Set the email address of the contact.
+    pub fn contact_email(&mut self, email: impl Into<String>) -> &mut Self {
+        self.contact.get_or_insert_with(Contact::default).email = Some(email.into());
+        self
+    }
+
+    /// Set the URL of the contact.
+    pub fn contact_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.contact.get_or_insert_with(Contact::default).url = Some(url.into());
+        self
+    }
+
+    /// Set the name of the license, like `Apache 2.0`.
+    pub fn license(&mut self, name: impl Into<String>) -> &mut Self {
+        self.license.get_or_insert_with(License::default).name = name.into();
+        self
+    }
+
+    /// Set the URL of the license.
+    pub fn license_url(&mut self, url: impl Into<String>) -> &mut Self {
+        self.license.get_or_insert_with(License::default).url = Some(url.into());
+        self
+    }
+
+    /// Set the [SPDX](https://spdx.org/licenses/) identifier of the license, like `Apache-2.0`.
+    /// This is only added to OpenAPI 3.1 specifications, 3.0 licenses have no `identifier`.
+    pub fn license_identifier(&mut self, identifier: impl Into<String>) -> &mut Self {
+        self.license_identifier = Some(identifier.into());
+        self
+    }
+
+    /// If no fields are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.summary.is_none()
+            && self.description.is_none()
+            && self.version.is_none()
+            && self.terms_of_service.is_none()
+            && self.contact.is_none()
+            && self.license.is_none()
+            && self.license_identifier.is_none()
+    }
+
+    /// Set the fields of `info`. The `summary` and license `identifier` are only set for
+    /// `OpenApiVersion::V3_1`.
+    pub fn apply(&self, info: &mut Info, version: OpenApiVersion) {
+        let set = |field: &mut Option<String>, value: &Option<String>| {
+            if value.is_some() {
+                field.clone_from(value);
+            }
+        };
+        if let Some(title) = &self.title {
+            info.title.clone_from(title);
+        }
+        if let Some(version) = &self.version {
+            info.version.clone_from(version);
+        }
+        set(&mut info.description, &self.description);
+        set(&mut info.terms_of_service, &self.terms_of_service);
+        if let Some(contact) = &self.contact {
+            let info_contact = info.contact.get_or_insert_with(Contact::default);
+            set(&mut info_contact.name, &contact.name);
+            set(&mut info_contact.email, &contact.email);
+            set(&mut info_contact.url, &contact.url);
+        }
+        if let Some(license) = &self.license {
+            let info_license = info.license.get_or_insert_with(License::default);
+            if !license.name.is_empty() {
+                info_license.name.clone_from(&license.name);
+            }
+            set(&mut info_license.url, &license.url);
+        }
+        if version == OpenApiVersion::V3_1 {
+            if let Some(summary) = &self.summary {
+                info.extensions
+                    .insert("summary".to_owned(), summary.clone().into());
+            }
+            if let (Some(identifier), Some(license)) = (&self.license_identifier, &mut info.license)
+            {
+                license
+                    .extensions
+                    .insert("identifier".to_owned(), identifier.clone().into());
+            }
+        }
+    }
+}
+
+/// How `Option<T>` fields and values are documented in schemas. Optional fields are never
+/// `required`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptionHandling {
+    /// Add `nullable: true` to the schema (OpenAPI 3.0).
+    #[default]
+    Nullable,
+    /// Add `null` to the `type` of the schema, like `type: [string, null]` (OpenAPI 3.1 and JSON
+    /// Schema).
+    NullType,
+    /// Only document that fields are not required, `null` values are not documented.
+    NotRequired,
+    /// Combine the schema with a `null` schema, like `anyOf: [{type: string}, {type: null}]`
+    /// (OpenAPI 3.1 and JSON Schema, for tools that do not support type arrays). See
+    /// [`okapi::convert::null_types_to_any_of`].
+    AnyOfNull,
+}
+
+/// When the specification of the `openapi.json` route is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecAssembly {
+    /// Generate the specification when the routes are mounted, and keep it in memory.
+    #[default]
+    Eager,
+    /// Only keep the route metadata, and generate the specification for every request of the
+    /// `openapi.json` route, so it does not use memory when the documentation is not requested.
+    /// The schema visitors are shared with the request threads, so custom visitors have to be
+    /// added with [`add_schema_visitor`](OpenApiSettings::add_schema_visitor). Validation
+    /// problems (and failed spec files or visitors) are logged and answered with a 500 error.
+    Lazy,
+    /// Like `Lazy`, but keep the specification in memory after the first request.
+    LazyCached,
+}
+
+/// What to do with the problems found when validating the generated specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecValidation {
+    /// Do not validate the specification.
+    #[default]
+    Skip,
+    /// Log all problems as warnings.
+    Log,
+    /// Log all problems as errors and panic, so the application does not launch.
+    Abort,
+}
+
+/// The color theme of the documentation UIs (Swagger UI and RapiDoc).
+///
+/// ```rust
+/// use rocket_okapi::settings::{DocTheme, OpenApiSettings, ThemeMode};
+///
+/// let mut settings = OpenApiSettings::new();
+/// settings.doc_theme = Some(DocTheme {
+///     mode: ThemeMode::Dark,
+///     accent_color: Some("#ff6600".to_owned()),
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DocTheme {
+    /// Whether the UIs are light or dark. The default is `ThemeMode::Auto`.
+    pub mode: ThemeMode,
+    /// A CSS color (like `#ff6600`) for the buttons and highlights of the UIs.
+    /// The default is `None` (the colors of the UI).
+    pub accent_color: Option<String>,
+}
+
+/// Whether the documentation UIs are light or dark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeMode {
+    /// Follow the preference of the browser (`prefers-color-scheme`).
+    #[default]
+    Auto,
+    /// Always use a light theme.
+    Light,
+    /// Always use a dark theme.
+    Dark,
 }
 
 /// Contains a named url.
@@ -49,3 +1492,67 @@ impl UrlObject {
         }
     }
 }
+
+/// Where the web UIs (Swagger UI and RapiDoc) load their JavaScript and CSS files from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum UiAssets {
+    /// Serve the copies that are embedded in the binary.
+    #[default]
+    Embedded,
+    /// Load the files from a CDN, for example to pin a different version of the UI. The embedded
+    /// copies are then not mounted (the html pages of the UI are still served by the application).
+    ///
+    /// ```rust
+    /// use rocket_okapi::settings::UiAssets;
+    ///
+    /// let assets = UiAssets::Cdn {
+    ///     base_url: "https://cdn.jsdelivr.net/npm/swagger-ui-dist@4.1.3".to_owned(),
+    ///     integrity: vec![(
+    ///         "swagger-ui-bundle.js".to_owned(),
+    ///         "sha384-...".to_owned(),
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    /// };
+    /// ```
+    Cdn {
+        /// The url of the folder that contains the files, like
+        /// `https://unpkg.com/swagger-ui-dist@4.1.3` or `https://unpkg.com/rapidoc@9.0.0/dist`.
+        base_url: String,
+        /// The [Subresource Integrity](https://developer.mozilla.org/en-US/docs/Web/Security/Subresource_Integrity)
+        /// hashes of the files (like `sha384-...`), by file name (like `swagger-ui-bundle.js`).
+        /// The browser does not load a file if its hash is different. Files without a hash are
+        /// loaded without checking them.
+        integrity: HashMap<String, String>,
+    },
+}
+
+impl UiAssets {
+    /// Get the attributes of the html element that loads `file` from the CDN (like
+    /// `src="https://..." integrity="..." crossorigin="anonymous"`), or `None` if the embedded
+    /// files are used. `attribute` is the attribute of the url, `src` or `href`.
+    #[cfg(any(feature = "swagger", feature = "rapidoc"))]
+    pub(crate) fn cdn_attributes(&self, file: &str, attribute: &str) -> Option<String> {
+        match self {
+            UiAssets::Embedded => None,
+            UiAssets::Cdn {
+                base_url,
+                integrity,
+            } => {
+                let mut attributes = format!(
+                    "{}=\"{}/{}\"",
+                    attribute,
+                    base_url.trim_end_matches('/'),
+                    file
+                );
+                if let Some(hash) = integrity.get(file) {
+                    attributes.push_str(&format!(
+                        " integrity=\"{}\" crossorigin=\"anonymous\"",
+                        hash
+                    ));
+                }
+                Some(attributes)
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::handlers::{ContentHandler, RedirectHandler};
-use crate::settings::UrlObject;
+use crate::settings::{DocTheme, ThemeMode, UiAssets, UrlObject};
 use rocket::http::ContentType;
 use rocket::Route;
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,45 @@ pub enum Filter {
     Str(String),
 }
 
+/// A HTTP method for which "Try it out" can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmitMethod {
+    /// `GET`
+    Get,
+    /// `PUT`
+    Put,
+    /// `POST`
+    Post,
+    /// `DELETE`
+    Delete,
+    /// `OPTIONS`
+    Options,
+    /// `HEAD`
+    Head,
+    /// `PATCH`
+    Patch,
+    /// `TRACE`
+    Trace,
+}
+
+impl SubmitMethod {
+    /// All methods, the default of `SwaggerUIConfig::supported_submit_methods`.
+    #[must_use]
+    pub fn all() -> Vec<SubmitMethod> {
+        vec![
+            SubmitMethod::Get,
+            SubmitMethod::Put,
+            SubmitMethod::Post,
+            SubmitMethod::Delete,
+            SubmitMethod::Options,
+            SubmitMethod::Head,
+            SubmitMethod::Patch,
+            SubmitMethod::Trace,
+        ]
+    }
+}
+
 fn is_zero(num: &u32) -> bool {
     *num == 0
 }
@@ -104,6 +143,66 @@ pub struct SwaggerUIConfig {
     /// `minimum`) fields and values for Parameters.
     /// Default: `false`.
     pub show_common_extensions: bool,
+    // network options:
+    /// Controls whether the "Try it out" section is enabled by default, so requests can be sent
+    /// without clicking "Try it out" first.
+    /// Default: `false`.
+    #[serde(default)]
+    pub try_it_out_enabled: bool,
+    /// The HTTP methods that have the "Try it out" feature enabled. An empty list disables
+    /// "Try it out" for all operations.
+    /// Default: `SubmitMethod::all()`.
+    #[serde(default = "SubmitMethod::all")]
+    pub supported_submit_methods: Vec<SubmitMethod>,
+    /// If set to true, the authorization data is kept when the browser is closed or refreshed.
+    /// Default: `false`.
+    #[serde(default)]
+    pub persist_authorization: bool,
+    /// If set to true, enables passing credentials (like cookies) in CORS requests that are sent
+    /// by the browser.
+    /// Default: `false`.
+    #[serde(default)]
+    pub with_credentials: bool,
+    /// If set to true, shows snippets (like curl commands) for the requests sent with
+    /// "Try it out".
+    /// Default: `false`.
+    #[serde(default)]
+    pub request_snippets_enabled: bool,
+    /// The url of the validator that is used to check the specification, which is shown as a
+    /// badge at the bottom of the page. Set it to `"none"` to disable validation.
+    /// Default: `None` (uses the validator of swagger.io).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator_url: Option<String>,
+    // branding options (not part of the Swagger UI config):
+    /// CSS that is added to the `index.html` page (in a `<style>` element), for example to
+    /// change the colors or hide the top bar.
+    /// Default: `None`.
+    #[serde(skip)]
+    pub custom_css: Option<String>,
+    /// JavaScript that is added to the end of the `index.html` page (in a `<script>` element).
+    /// It runs after Swagger UI is created, which is available as `window.ui`.
+    /// Default: `None`.
+    #[serde(skip)]
+    pub custom_js: Option<String>,
+    /// The url of the favicon of the `index.html` page.
+    /// Default: `None` (no favicon).
+    #[serde(skip)]
+    pub custom_favicon: Option<String>,
+    /// HTML that is added to the `<head>` of the `index.html` page, like `<meta>` or `<link>`
+    /// elements.
+    /// Default: `None`.
+    #[serde(skip)]
+    pub custom_html_head: Option<String>,
+    /// Where the JavaScript and CSS files of Swagger UI are loaded from.
+    /// Default: `UiAssets::Embedded`.
+    #[serde(skip)]
+    pub assets: UiAssets,
+    /// The color theme of the page, usually the
+    /// [`OpenApiSettings::doc_theme`](crate::settings::OpenApiSettings::doc_theme).
+    /// The `custom_css` can still change the colors.
+    /// Default: `None` (the light theme of Swagger UI).
+    #[serde(skip)]
+    pub doc_theme: Option<DocTheme>,
 }
 
 impl Default for SwaggerUIConfig {
@@ -122,6 +221,18 @@ impl Default for SwaggerUIConfig {
             max_displayed_tags: 0,
             show_extensions: false,
             show_common_extensions: false,
+            try_it_out_enabled: false,
+            supported_submit_methods: SubmitMethod::all(),
+            persist_authorization: false,
+            with_credentials: false,
+            request_snippets_enabled: false,
+            validator_url: None,
+            custom_css: None,
+            custom_js: None,
+            custom_favicon: None,
+            custom_html_head: None,
+            assets: UiAssets::Embedded,
+            doc_theme: None,
         }
     }
 }
@@ -130,15 +241,88 @@ impl Default for SwaggerUIConfig {
 #[must_use]
 pub fn make_swagger_ui(config: &SwaggerUIConfig) -> impl Into<Vec<Route>> {
     let config_handler = ContentHandler::json(config);
-    vec![
+    let mut routes = vec![
         RedirectHandler::to("index.html").into_route("/"),
         // Add custom config file
         config_handler.into_route("/swagger-ui-config.json"),
+        // Add custom html file
+        ContentHandler::bytes_owned(ContentType::HTML, index_page(config).into_bytes())
+            .into_route("/index.html"),
         // Add other static files
-        static_file!("index.html", HTML),
         static_file!("oauth2-redirect.html", HTML),
-        static_file!("swagger-ui-standalone-preset.js", JavaScript),
-        static_file!("swagger-ui-bundle.js", JavaScript),
-        static_file!("swagger-ui.css", CSS),
-    ]
+    ];
+    if config.assets == UiAssets::Embedded {
+        routes.extend(vec![
+            static_file!("swagger-ui-standalone-preset.js", JavaScript),
+            static_file!("swagger-ui-bundle.js", JavaScript),
+            static_file!("swagger-ui.css", CSS),
+        ]);
+    }
+    routes
+}
+
+/// Create the `index.html` page, with the branding options and assets of the config.
+fn index_page(config: &SwaggerUIConfig) -> String {
+    let mut page = include_str!("../swagger-ui/index.html").to_owned();
+    for (file, attribute) in &[
+        ("swagger-ui.css", "href"),
+        ("swagger-ui-bundle.js", "src"),
+        ("swagger-ui-standalone-preset.js", "src"),
+    ] {
+        if let Some(attributes) = config.assets.cdn_attributes(file, attribute) {
+            page = page.replace(&format!("{}=\"./{}\"", attribute, file), &attributes);
+        }
+    }
+    let mut head = String::new();
+    if let Some(favicon) = &config.custom_favicon {
+        head.push_str(&format!(
+            "    <link rel=\"icon\" href=\"{}\" />\n",
+            favicon.replace('&', "&amp;").replace('"', "&quot;")
+        ));
+    }
+    if let Some(theme) = &config.doc_theme {
+        head.push_str(&format!("    <style>\n{}    </style>\n", theme_css(theme)));
+    }
+    if let Some(css) = &config.custom_css {
+        head.push_str(&format!("    <style>\n{}\n    </style>\n", css));
+    }
+    if let Some(html) = &config.custom_html_head {
+        head.push_str(html);
+        head.push('\n');
+    }
+    let mut body = String::new();
+    if let Some(js) = &config.custom_js {
+        // Run after the `window.onload` handler that creates Swagger UI.
+        body.push_str(&format!(
+            "  <script>\n    window.addEventListener(\"load\", function() {{\n{}\n    }});\n  </script>\n",
+            js
+        ));
+    }
+    page.replacen("  </head>", &format!("{}  </head>", head), 1)
+        .replacen("  </body>", &format!("{}  </body>", body), 1)
+}
+
+/// Create the CSS for a theme. Swagger UI does not have a dark theme, so the colors of the page
+/// are inverted (except for images).
+fn theme_css(theme: &DocTheme) -> String {
+    let dark = "      html { background: #fff; filter: invert(88%) hue-rotate(180deg); }\n      \
+                img, video { filter: invert(100%) hue-rotate(180deg); }\n";
+    let mut css = match theme.mode {
+        ThemeMode::Light => String::new(),
+        ThemeMode::Dark => dark.to_owned(),
+        ThemeMode::Auto => format!(
+            "      @media (prefers-color-scheme: dark) {{\n{}      }}\n",
+            dark
+        ),
+    };
+    if let Some(color) = &theme.accent_color {
+        css.push_str(&format!(
+            "      .swagger-ui .topbar, .swagger-ui .btn.execute {{ background-color: {0}; }}\n      \
+             .swagger-ui .btn.execute, .swagger-ui .btn.authorize {{ border-color: {0}; }}\n      \
+             .swagger-ui .btn.authorize, .swagger-ui .info a, .swagger-ui .opblock-tag a {{ color: {0}; }}\n      \
+             .swagger-ui .btn.authorize svg {{ fill: {0}; }}\n",
+            color
+        ));
+    }
+    css
 }
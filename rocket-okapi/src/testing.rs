@@ -0,0 +1,110 @@
+use crate::util::{read_spec_file, write_spec_file};
+use okapi::openapi3::OpenApi;
+use serde_json::Value;
+use std::path::Path;
+
+/// The environment variable that makes [`assert_spec_matches_snapshot`] update the snapshots,
+/// instead of comparing them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "OKAPI_UPDATE_SNAPSHOTS";
+
+/// Assert that the OpenAPI document served by `rocket` (see
+/// [`get_openapi_spec`](crate::get_openapi_spec)) matches the snapshot in the file at `path`.
+///
+/// The documents are compared with
+/// [`OpenApi::semantically_equals`](okapi::openapi3::OpenApi::semantically_equals), so the order of
+/// the keys and the use of references do not matter. If they are different, the panic message
+/// lists the changed values.
+///
+/// If the snapshot does not exist yet, or if the `OKAPI_UPDATE_SNAPSHOTS` environment variable is
+/// set to `1`, the snapshot is written (sorted, see
+/// [`OpenApi::sort`](okapi::openapi3::OpenApi::sort)) instead. The snapshot is stored as JSON, or
+/// (with the `yaml` feature) as YAML if the file extension is `.yaml` or `.yml`.
+///
+/// ```rust,no_run
+/// # fn rocket() -> rocket::Rocket<rocket::Build> { rocket::build() }
+/// #[test]
+/// fn openapi_spec_is_unchanged() {
+///     rocket_okapi::testing::assert_spec_matches_snapshot(rocket(), "tests/openapi.snap");
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the spec does not match the snapshot, or if the spec or snapshot can not be read
+/// or written.
+pub fn assert_spec_matches_snapshot<P: AsRef<Path>>(
+    rocket: rocket::Rocket<rocket::Build>,
+    path: P,
+) {
+    let path = path.as_ref();
+    let mut spec = match crate::get_openapi_spec(rocket) {
+        Ok(spec) => spec,
+        Err(err) => panic!("Could not get the OpenAPI spec: {}", err),
+    };
+    let update = std::env::var(UPDATE_SNAPSHOTS_VAR).as_deref() == Ok("1");
+    if update || !path.exists() {
+        spec.sort();
+        if let Err(err) = write_spec_file(path, &spec) {
+            panic!("Could not write the OpenAPI snapshot: {}", err);
+        }
+        return;
+    }
+    let snapshot: OpenApi = match read_spec_file(path) {
+        Ok(snapshot) => snapshot,
+        Err(err) => panic!("Could not read the OpenAPI snapshot: {}", err),
+    };
+    if spec.semantically_equals(&snapshot) {
+        return;
+    }
+    let mut changes = Vec::new();
+    match (serde_json::to_value(&snapshot), serde_json::to_value(&spec)) {
+        (Ok(old), Ok(new)) => diff_values(&old, &new, &mut String::new(), &mut changes),
+        _ => changes.push("The documents could not be serialized.".to_owned()),
+    }
+    panic!(
+        "The OpenAPI spec does not match the snapshot `{}`:\n{}\n\
+         Run with `{}=1` to update the snapshot.",
+        path.display(),
+        changes.join("\n"),
+        UPDATE_SNAPSHOTS_VAR
+    );
+}
+
+/// Add the differences between `old` and `new` to `changes`, as lines like
+/// `~ /info/title: "Old" -> "New"`. `pointer` is the JSON Pointer to the values.
+fn diff_values(old: &Value, new: &Value, pointer: &mut String, changes: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let length = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                match new.get(key) {
+                    Some(new_value) => diff_values(old_value, new_value, pointer, changes),
+                    None => changes.push(format!("- {}: {}", pointer, old_value)),
+                }
+                pointer.truncate(length);
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let key = key.replace('~', "~0").replace('/', "~1");
+                    changes.push(format!("+ {}/{}: {}", pointer, key, new_value));
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items))
+            if old_items.len() == new_items.len() =>
+        {
+            for (index, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+                let length = pointer.len();
+                pointer.push_str(&format!("/{}", index));
+                diff_values(old_item, new_item, pointer, changes);
+                pointer.truncate(length);
+            }
+        }
+        (old, new) if old != new => {
+            changes.push(format!("~ {}: {} -> {}", pointer, old, new));
+        }
+        _ => {}
+    }
+}
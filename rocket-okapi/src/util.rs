@@ -1,6 +1,13 @@
+use crate::gen::OpenApiGenerator;
 use crate::{OpenApiError, Result};
-use okapi::openapi3::{MediaType, RefOr, Response, Responses, SchemaObject};
+use okapi::openapi3::{
+    Encoding, Header, MediaType, Object, Parameter, ParameterValue, RefOr, RequestBody, Response,
+    Responses, SchemaObject, Xml,
+};
 use okapi::Map;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema};
+use schemars::JsonSchema;
 
 // FIXME this whole file is a huge mess...
 
@@ -30,6 +37,28 @@ pub fn ensure_status_code_exists(responses: &mut Responses, status: u16) -> &mut
         .or_insert_with(|| Response::default().into())
 }
 
+/// Set the description of the response with the status code `status` (like `404`, `4XX` or
+/// `default`), which is added if the responses do not have it yet. This is used by
+/// `#[openapi(response(status = 404, description = "..."))]`.
+pub fn set_response_description(
+    responses: &mut Responses,
+    status: &str,
+    description: impl Into<String>,
+) -> Result<()> {
+    let response = if status == "default" {
+        responses
+            .default
+            .get_or_insert_with(|| Response::default().into())
+    } else {
+        responses
+            .responses
+            .entry(status.to_owned())
+            .or_insert_with(|| Response::default().into())
+    };
+    ensure_not_ref(response)?.description = description.into();
+    Ok(())
+}
+
 /// Change all responses in the map to "default" response code.
 /// In case of doubles the first items will be reserved.
 pub fn change_all_responses_to_default(responses: &mut Responses) {
@@ -107,8 +136,8 @@ pub fn add_media_type(
 
 /// Replaces the Content-Type for all responses with `content_type`.
 pub fn set_content_type(responses: &mut Responses, content_type: impl ToString) -> Result<()> {
-    for ref mut resp_refor in responses.responses.values_mut() {
-        let response = ensure_not_ref(*resp_refor)?;
+    for resp_refor in responses.responses.values_mut() {
+        let response = ensure_not_ref(resp_refor)?;
         let content = &mut response.content;
         let mt = if content.values().len() == 1 {
             content.values().next().unwrap().clone()
@@ -137,6 +166,287 @@ pub fn add_schema_response(
     add_content_response(responses, status, content_type, media)
 }
 
+/// Returns a schema for an object that allows any properties:
+/// `{ "type": "object", "additionalProperties": true }`.
+#[must_use]
+pub fn free_form_object_schema() -> SchemaObject {
+    let mut schema = SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        ..SchemaObject::default()
+    };
+    schema.object().additional_properties = Some(Box::new(Schema::Bool(true)));
+    schema
+}
+
+/// Returns a schema for binary data, like a file upload:
+/// `{ "type": "string", "format": "binary" }`.
+#[must_use]
+pub fn binary_schema() -> SchemaObject {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        format: Some("binary".to_owned()),
+        ..SchemaObject::default()
+    }
+}
+
+/// Same as [`binary_schema`], but can be used with `#[schemars(schema_with = "...")]`.
+/// This is useful to document a `TempFile` field of a multipart form:
+/// ```rust
+/// # use rocket_okapi::JsonSchema;
+/// #[derive(rocket::FromForm, JsonSchema)]
+/// struct Upload<'r> {
+///     name: String,
+///     #[schemars(schema_with = "rocket_okapi::util::file_schema")]
+///     file: rocket::fs::TempFile<'r>,
+/// }
+/// ```
+pub fn file_schema(_gen: &mut SchemaGenerator) -> Schema {
+    binary_schema().into()
+}
+
+/// Returns a schema that only allows `value`, like `{ "type": "string", "enum": ["card"] }`.
+/// This is an `enum` with one value instead of a `const`, because OpenAPI 3.0 has no `const`.
+/// Marker types that always have the same value can be created with
+/// [`literal_type!`](crate::literal_type).
+#[must_use]
+pub fn literal_schema(value: serde_json::Value) -> SchemaObject {
+    let instance_type = match &value {
+        serde_json::Value::Null => InstanceType::Null,
+        serde_json::Value::Bool(_) => InstanceType::Boolean,
+        serde_json::Value::Number(number) if number.is_f64() => InstanceType::Number,
+        serde_json::Value::Number(_) => InstanceType::Integer,
+        serde_json::Value::String(_) => InstanceType::String,
+        serde_json::Value::Array(_) => InstanceType::Array,
+        serde_json::Value::Object(_) => InstanceType::Object,
+    };
+    SchemaObject {
+        instance_type: Some(instance_type.into()),
+        enum_values: Some(vec![value]),
+        ..SchemaObject::default()
+    }
+}
+
+/// Returns the `encoding` of a `multipart/form-data` body with the given `schema`.
+///
+/// An explicit `contentType` is added for every property that contains binary data (file parts).
+/// The `contentMediaType` of the property is used if set, `application/octet-stream` otherwise.
+#[must_use]
+pub fn multipart_encoding(gen: &OpenApiGenerator, schema: &SchemaObject) -> Map<String, Encoding> {
+    let schema = Schema::Object(schema.clone());
+    let schema = match gen.schema_generator().dereference(&schema) {
+        Some(Schema::Object(schema)) => schema,
+        _ => match &schema {
+            Schema::Object(schema) => schema,
+            Schema::Bool(_) => return Map::new(),
+        },
+    };
+    let properties = match &schema.object {
+        Some(object) => &object.properties,
+        None => return Map::new(),
+    };
+    properties
+        .iter()
+        .filter_map(|(name, property)| {
+            let content_type = binary_content_type(property)?;
+            Some((
+                name.clone(),
+                Encoding {
+                    content_type: Some(content_type),
+                    ..Encoding::default()
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Returns the media type of a binary property (or an array of binary data).
+fn binary_content_type(schema: &Schema) -> Option<String> {
+    let schema = match schema {
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => return None,
+    };
+    if let Some(media_type) = schema.extensions.get("contentMediaType") {
+        return media_type.as_str().map(ToOwned::to_owned);
+    }
+    if schema.has_type(InstanceType::String) && schema.format.as_deref() == Some("binary") {
+        return Some("application/octet-stream".to_owned());
+    }
+    match schema.array.as_ref().and_then(|array| array.items.as_ref()) {
+        Some(schemars::schema::SingleOrVec::Single(items)) => binary_content_type(items),
+        _ => None,
+    }
+}
+
+/// A `schemars` visitor that adds the OpenAPI `xml` object to the properties of a schema,
+/// based on the naming convention used by XML serializers like `quick-xml`:
+/// a property renamed with `#[serde(rename = "@name")]` is an XML attribute called `name`.
+///
+/// Add it using `OpenApiSettings::schema_settings`:
+/// ```rust
+/// let mut settings = rocket_okapi::settings::OpenApiSettings::new();
+/// settings.schema_settings = settings
+///     .schema_settings
+///     .with_visitor(rocket_okapi::util::XmlVisitor);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlVisitor;
+
+impl schemars::visit::Visitor for XmlVisitor {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(object) = &mut schema.object {
+            for (name, property) in object.properties.iter_mut() {
+                if let (Some(attribute), Schema::Object(property)) =
+                    (name.strip_prefix('@'), property)
+                {
+                    let mut xml = Xml::from_schema(property).unwrap_or_default();
+                    xml.name = Some(attribute.to_owned());
+                    xml.attribute = true;
+                    xml.set_on(property);
+                }
+            }
+        }
+        schemars::visit::visit_schema_object(self, schema);
+    }
+}
+
+/// Returns a schema for binary data with the given media type, as used by OpenAPI 3.1:
+/// `{ "type": "string", "contentMediaType": "<mime_type>" }`.
+#[must_use]
+pub fn binary_schema_v31(mime_type: &str) -> SchemaObject {
+    let mut schema = SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        ..SchemaObject::default()
+    };
+    schema
+        .extensions
+        .insert("contentMediaType".to_owned(), mime_type.into());
+    schema
+}
+
+/// Returns a JSON Schema object for the type `T`, like `OpenApiGenerator::json_schema`.
+/// If the schema does not restrict the value at all (for example for `serde_json::Value`) a
+/// free-form object schema is returned instead, see [`free_form_object_schema`].
+pub fn json_schema_or_free_form<T: ?Sized + JsonSchema>(
+    gen: &mut OpenApiGenerator,
+) -> SchemaObject {
+    let schema = gen.json_schema::<T>();
+    if schema == SchemaObject::default() {
+        free_form_object_schema()
+    } else {
+        schema
+    }
+}
+
+/// Combine the request bodies generated for different media types into one `RequestBody`, with
+/// a `content` entry for every media type.
+///
+/// If a body does not contain an entry for its media type (for example, `Json<T>` always
+/// documents `application/json`), its first entry is used for that media type.
+/// The body is only required if all bodies are required.
+#[must_use]
+pub fn combine_request_bodies(bodies: Vec<(&str, RequestBody)>) -> RequestBody {
+    let mut result = RequestBody {
+        required: !bodies.is_empty(),
+        ..RequestBody::default()
+    };
+    for (media_type, body) in bodies {
+        let mut contents = body.content;
+        let content = contents
+            .remove(media_type)
+            .or_else(|| contents.into_iter().next().map(|(_, content)| content));
+        if let Some(content) = content {
+            result.content.insert(media_type.to_owned(), content);
+        }
+        result.required &= body.required;
+        if result.description.is_none() {
+            result.description = body.description;
+        }
+        result.extensions.extend(body.extensions);
+    }
+    result
+}
+
+/// The responses of a WebSocket route: a `101 Switching Protocols` response with the `Upgrade`,
+/// `Connection` and `Sec-WebSocket-Accept` headers. This is used by
+/// `#[openapi(websocket)]`.
+pub fn websocket_responses(gen: &mut OpenApiGenerator) -> Responses {
+    let mut headers = Map::new();
+    for (name, description) in [
+        ("Upgrade", "`websocket`"),
+        ("Connection", "`Upgrade`"),
+        (
+            "Sec-WebSocket-Accept",
+            "The accept key, derived from `Sec-WebSocket-Key`.",
+        ),
+    ] {
+        headers.insert(
+            name.to_owned(),
+            RefOr::Object(Header {
+                description: Some(description.to_owned()),
+                required: true,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema: gen.json_schema::<String>(),
+                    example: None,
+                    examples: None,
+                },
+                extensions: Object::default(),
+            }),
+        );
+    }
+    let mut responses = Responses::default();
+    responses.responses.insert(
+        "101".to_owned(),
+        Response {
+            description: "The connection is upgraded to a WebSocket.".to_owned(),
+            headers,
+            ..Response::default()
+        }
+        .into(),
+    );
+    responses
+}
+
+/// The request headers of a WebSocket handshake: `Upgrade`, `Connection`, `Sec-WebSocket-Key`
+/// and `Sec-WebSocket-Version`. This is used by `#[openapi(websocket)]`.
+pub fn websocket_parameters(gen: &mut OpenApiGenerator) -> Vec<Parameter> {
+    [
+        ("Upgrade", "`websocket`"),
+        ("Connection", "`Upgrade`"),
+        ("Sec-WebSocket-Key", "A random key of the handshake."),
+        ("Sec-WebSocket-Version", "`13`"),
+    ]
+    .iter()
+    .map(|(name, description)| Parameter {
+        name: (*name).to_owned(),
+        location: "header".to_owned(),
+        description: Some((*description).to_owned()),
+        required: true,
+        deprecated: false,
+        allow_empty_value: false,
+        value: ParameterValue::Schema {
+            style: None,
+            explode: None,
+            allow_reserved: false,
+            schema: gen.json_schema::<String>(),
+            example: None,
+            examples: None,
+        },
+        extensions: Object::default(),
+    })
+    .collect()
+}
+
+/// The value of the `x-websocket-messages` extension of a WebSocket route: the schema of the
+/// messages `T`. This is used by `#[openapi(websocket(message = "T"))]`.
+pub fn websocket_messages<T: JsonSchema>(gen: &mut OpenApiGenerator) -> serde_json::Value {
+    serde_json::to_value(gen.json_schema::<T>()).unwrap_or_default()
+}
+
 /// Merges the the two given `Responses`.
 pub fn produce_any_responses(r1: Responses, r2: Responses) -> Result<Responses> {
     let mut result = Responses {
@@ -221,3 +531,79 @@ fn accept_either_schema(
     schema.subschemas().any_of = Some(vec![s1.into(), s2.into()]);
     Some(schema)
 }
+
+/// Read a JSON or YAML (with the `yaml` feature) file, depending on its file extension.
+pub(crate) fn read_spec_file<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> Result<T> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        OpenApiError::new(format!("Could not read `{}`: {}", path.display(), err))
+    })?;
+    let is_yaml = matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("yaml") | Some("yml")
+    );
+    let parsed = if is_yaml {
+        #[cfg(feature = "yaml")]
+        {
+            serde_yaml::from_str(&content).map_err(|err| err.to_string())
+        }
+        #[cfg(not(feature = "yaml"))]
+        {
+            Err("the `yaml` feature of `rocket_okapi` is required to read YAML files".to_owned())
+        }
+    } else {
+        serde_json::from_str(&content).map_err(|err| err.to_string())
+    };
+    parsed
+        .map_err(|err| OpenApiError::new(format!("Could not parse `{}`: {}", path.display(), err)))
+}
+
+/// Write the specification to a JSON or YAML (with the `yaml` feature) file, depending on its
+/// file extension.
+///
+/// Together with [`get_openapi_spec`](crate::get_openapi_spec), this can be used in a test or a
+/// small binary to store the `openapi.json` file of an application, without starting a server.
+pub fn write_spec_file(path: &std::path::Path, spec: &okapi::openapi3::OpenApi) -> Result<()> {
+    let is_yaml = matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("yaml") | Some("yml")
+    );
+    let content = if is_yaml {
+        #[cfg(feature = "yaml")]
+        {
+            serde_yaml::to_string(spec).map_err(|err| err.to_string())
+        }
+        #[cfg(not(feature = "yaml"))]
+        {
+            Err("the `yaml` feature of `rocket_okapi` is required to write YAML files".to_owned())
+        }
+    } else {
+        serde_json::to_string_pretty(spec).map_err(|err| err.to_string())
+    };
+    let content = content.map_err(|err| {
+        OpenApiError::new(format!("Could not serialize `{}`: {}", path.display(), err))
+    })?;
+    std::fs::write(path, content)
+        .map_err(|err| OpenApiError::new(format!("Could not write `{}`: {}", path.display(), err)))
+}
+
+/// Write the `.http` request files of the specification (see [`okapi::export::http_files`]) to
+/// a directory, which is created if it does not exist. Existing files with the same names are
+/// replaced.
+///
+/// ```rust,no_run
+/// # fn rocket() -> rocket::Rocket<rocket::Build> { rocket::build() }
+/// let spec = rocket_okapi::get_openapi_spec(rocket()).unwrap();
+/// rocket_okapi::util::write_http_files("requests".as_ref(), &spec).unwrap();
+/// ```
+pub fn write_http_files(dir: &std::path::Path, spec: &okapi::openapi3::OpenApi) -> Result<()> {
+    std::fs::create_dir_all(dir).map_err(|err| {
+        OpenApiError::new(format!("Could not create `{}`: {}", dir.display(), err))
+    })?;
+    for (file_name, content) in okapi::export::http_files(spec) {
+        let path = dir.join(file_name);
+        std::fs::write(&path, content).map_err(|err| {
+            OpenApiError::new(format!("Could not write `{}`: {}", path.display(), err))
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,305 @@
+use crate::gen::OpenApiGenerator;
+use crate::settings::OpenApiSettings;
+use okapi::openapi3::{MediaType, OpenApi, Operation, Response, Responses, SchemaObject};
+use rocket::futures::future::BoxFuture;
+use rocket::http::{Method, Status};
+use rocket::route::{Handler, Outcome};
+use rocket::serde::json::Json;
+use rocket::{Data, Orbit, Request, Rocket, Route};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// The default payload of the health and readiness endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct HealthStatus {
+    /// `ok`, or `unavailable` if the check failed.
+    pub status: String,
+}
+
+impl HealthStatus {
+    /// The status `ok`.
+    #[must_use]
+    pub fn ok() -> Self {
+        HealthStatus {
+            status: "ok".to_owned(),
+        }
+    }
+
+    /// The status `unavailable`.
+    #[must_use]
+    pub fn unavailable() -> Self {
+        HealthStatus {
+            status: "unavailable".to_owned(),
+        }
+    }
+}
+
+/// The check of an endpoint: the status code and the payload.
+type CheckFn =
+    dyn for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, (Status, serde_json::Value)> + Send + Sync;
+
+/// The documented `GET /healthz` (liveness) and `GET /readyz` (readiness) endpoints, for
+/// orchestrators like Kubernetes. The checks return `Ok` (`200 OK`) or `Err`
+/// (`503 Service Unavailable`) with a payload, which is a [`HealthStatus`] unless a custom
+/// check sets another type. Both endpoints return `ok` by default.
+///
+/// [`routes_and_spec`](Self::routes_and_spec) creates the routes and their documentation, so they
+/// can be mounted with [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs):
+///
+/// ```rust,no_run
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::wellknown::{HealthEndpoints, HealthStatus};
+/// use rocket_okapi::mount_endpoints_and_merged_docs;
+///
+/// struct Db;
+///
+/// impl Db {
+///     async fn ping(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// let health = HealthEndpoints::new().readiness_check(|rocket| {
+///     Box::pin(async move {
+///         match rocket.state::<Db>() {
+///             Some(db) if db.ping().await => Ok(HealthStatus::ok()),
+///             _ => Err(HealthStatus::unavailable()),
+///         }
+///     })
+/// });
+/// let settings = OpenApiSettings::new();
+/// let mut building_rocket = rocket::build().manage(Db);
+/// mount_endpoints_and_merged_docs! {
+///     building_rocket, "/", settings,
+///     "/" => health.routes_and_spec(&settings),
+/// };
+/// ```
+#[derive(Clone)]
+pub struct HealthEndpoints {
+    health: Check,
+    readiness: Check,
+}
+
+#[derive(Clone)]
+struct Check {
+    check: Arc<CheckFn>,
+    schema: fn(&mut OpenApiGenerator) -> SchemaObject,
+}
+
+impl Check {
+    fn new<T, F>(check: F) -> Self
+    where
+        T: Serialize + JsonSchema,
+        F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<T, T>> + Send + Sync + 'static,
+    {
+        let check = Arc::new(check);
+        Check {
+            check: Arc::new(move |rocket| {
+                let check = check.clone();
+                Box::pin(async move {
+                    let (status, payload) = match check(rocket).await {
+                        Ok(payload) => (Status::Ok, serde_json::to_value(payload)),
+                        Err(payload) => (Status::ServiceUnavailable, serde_json::to_value(payload)),
+                    };
+                    match payload {
+                        Ok(payload) => (status, payload),
+                        Err(err) => {
+                            log::error!("Could not serialize the health status: {}", err);
+                            (Status::InternalServerError, serde_json::Value::Null)
+                        }
+                    }
+                })
+            }),
+            schema: OpenApiGenerator::json_schema::<T>,
+        }
+    }
+
+    fn ok() -> Self {
+        Check::new(|_| Box::pin(async { Ok(HealthStatus::ok()) }))
+    }
+
+    fn operation(
+        &self,
+        gen: &mut OpenApiGenerator,
+        operation_id: &str,
+        summary: &str,
+    ) -> Operation {
+        let schema = (self.schema)(gen);
+        let mut responses = Responses::default();
+        for (status, description) in [
+            ("200", "The service is available."),
+            ("503", "The service is not available."),
+        ] {
+            let mut response = Response {
+                description: description.to_owned(),
+                ..Response::default()
+            };
+            response.content.insert(
+                "application/json".to_owned(),
+                MediaType {
+                    schema: Some(schema.clone()),
+                    ..MediaType::default()
+                },
+            );
+            responses
+                .responses
+                .insert(status.to_owned(), response.into());
+        }
+        Operation {
+            operation_id: Some(operation_id.to_owned()),
+            summary: Some(summary.to_owned()),
+            tags: vec!["Health".to_owned()],
+            responses,
+            ..Operation::default()
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Check {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let (status, payload) = (self.check)(req.rocket()).await;
+        Outcome::from(req, (status, Json(payload)))
+    }
+}
+
+impl HealthEndpoints {
+    /// Create the endpoints, which both return `ok`.
+    #[must_use]
+    pub fn new() -> Self {
+        HealthEndpoints {
+            health: Check::ok(),
+            readiness: Check::ok(),
+        }
+    }
+
+    /// Set the check of `/healthz`, which tells if the service is running (and should not be
+    /// restarted).
+    #[must_use]
+    pub fn health_check<T, F>(mut self, check: F) -> Self
+    where
+        T: Serialize + JsonSchema,
+        F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<T, T>> + Send + Sync + 'static,
+    {
+        self.health = Check::new(check);
+        self
+    }
+
+    /// Set the check of `/readyz`, which tells if the service can handle requests, like when its
+    /// database is reachable.
+    #[must_use]
+    pub fn readiness_check<T, F>(mut self, check: F) -> Self
+    where
+        T: Serialize + JsonSchema,
+        F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<T, T>> + Send + Sync + 'static,
+    {
+        self.readiness = Check::new(check);
+        self
+    }
+
+    /// Create the routes, and their documentation in the `Health` tag.
+    #[must_use]
+    pub fn routes_and_spec(&self, settings: &OpenApiSettings) -> (Vec<Route>, OpenApi) {
+        let mut gen = OpenApiGenerator::new(settings);
+        let endpoints = [
+            (
+                &self.health,
+                "/healthz",
+                "healthz",
+                "Check if the service is running",
+            ),
+            (
+                &self.readiness,
+                "/readyz",
+                "readyz",
+                "Check if the service can handle requests",
+            ),
+        ];
+        let mut routes = Vec::new();
+        for (check, path, operation_id, summary) in endpoints {
+            let operation = check.operation(&mut gen, operation_id, summary);
+            gen.add_manual_operation(Method::Get, path, operation);
+            let mut route = Route::new(Method::Get, path, check.clone());
+            route.name = Some(operation_id.into());
+            routes.push(route);
+        }
+        (routes, gen.into_openapi())
+    }
+}
+
+impl Default for HealthEndpoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The version of the service, served by the documented `GET /version` endpoint. Create it with
+/// [`build_info!`](crate::build_info), which reads the version of the crate that calls it, and
+/// the `GIT_SHA` and `BUILD_TIMESTAMP` environment variables at build time (which can be set by
+/// the CI, or by a build script with `cargo:rustc-env=GIT_SHA=...`).
+///
+/// ```rust,no_run
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::{build_info, mount_endpoints_and_merged_docs};
+///
+/// let settings = OpenApiSettings::new();
+/// let mut building_rocket = rocket::build();
+/// mount_endpoints_and_merged_docs! {
+///     building_rocket, "/", settings,
+///     "/" => build_info!().routes_and_spec(&settings),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[schemars(description = "The version of the service.")]
+pub struct BuildInfo {
+    /// The name of the package.
+    pub name: String,
+    /// The version of the package.
+    pub version: String,
+    /// The git commit the service is built from.
+    pub git_sha: Option<String>,
+    /// When the service was built.
+    pub build_timestamp: Option<String>,
+}
+
+#[rocket::async_trait]
+impl Handler for BuildInfo {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, Json(self.clone()))
+    }
+}
+
+impl BuildInfo {
+    /// Create the `GET /version` route, and its documentation in the `Health` tag.
+    #[must_use]
+    pub fn routes_and_spec(&self, settings: &OpenApiSettings) -> (Vec<Route>, OpenApi) {
+        let mut gen = OpenApiGenerator::new(settings);
+        let mut response = Response {
+            description: "The version of the service.".to_owned(),
+            ..Response::default()
+        };
+        response.content.insert(
+            "application/json".to_owned(),
+            MediaType {
+                schema: Some(gen.json_schema::<BuildInfo>()),
+                ..MediaType::default()
+            },
+        );
+        let mut responses = Responses::default();
+        responses
+            .responses
+            .insert("200".to_owned(), response.into());
+        let operation = Operation {
+            operation_id: Some("version".to_owned()),
+            summary: Some("Get the version of the service".to_owned()),
+            tags: vec!["Health".to_owned()],
+            responses,
+            ..Operation::default()
+        };
+        gen.add_manual_operation(Method::Get, "/version", operation);
+        let mut route = Route::new(Method::Get, "/version", self.clone());
+        route.name = Some("version".into());
+        (vec![route], gen.into_openapi())
+    }
+}